@@ -0,0 +1,1056 @@
+//! Parsing of `#[schema(...)]` attributes.
+//!
+//! Unless a container opts out with `#[schema(ignore_serde)]`, the serde
+//! attributes that change the wire format (`rename`, `rename_all`, `alias`,
+//! `default`, `skip`, `flatten`, `deny_unknown_fields` and the enum
+//! representation) are read as well, so validation and schemas match what
+//! serde deserializes. A `#[schema(...)]` attribute takes precedence over the
+//! corresponding serde attribute. All other serde attributes are ignored.
+
+use std::collections::BTreeMap;
+
+use syn::meta::ParseNestedMeta;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Expr, ExprLit, ExprPath, Lit, LitStr, Token, WherePredicate};
+
+use crate::case::RenameRule;
+
+/// Parses `key = "string"` from a nested meta item.
+fn parse_string(meta: &ParseNestedMeta, name: &str) -> syn::Result<String> {
+    let value: Expr = meta.value()?.parse()?;
+    string_value(meta, &value, name)
+}
+
+/// Reads a string literal from an attribute value.
+fn string_value(meta: &ParseNestedMeta, value: &Expr, name: &str) -> syn::Result<String> {
+    if let Expr::Lit(ExprLit {
+        lit: Lit::Str(lit_str),
+        ..
+    }) = value
+    {
+        Ok(lit_str.value())
+    } else {
+        Err(meta.error(format!("expected string literal for {}", name)))
+    }
+}
+
+/// Parses the value of a constraint, given either as `key = value` or as
+/// `key(value, message = "...")`. Returns the value and the custom message.
+fn parse_constraint_value(meta: &ParseNestedMeta) -> syn::Result<(Expr, Option<LitStr>)> {
+    if meta.input.peek(Token![=]) {
+        return Ok((meta.value()?.parse()?, None));
+    }
+
+    let content;
+    syn::parenthesized!(content in meta.input);
+    let value: Expr = content.parse()?;
+    let mut message = None;
+    if content.parse::<Option<Token![,]>>()?.is_some() && !content.is_empty() {
+        let key: syn::Ident = content.parse()?;
+        if key != "message" {
+            return Err(syn::Error::new_spanned(key, "expected `message = \"...\"`"));
+        }
+        content.parse::<Token![=]>()?;
+        message = Some(content.parse()?);
+        content.parse::<Option<Token![,]>>()?;
+    }
+    if !content.is_empty() {
+        return Err(content.error("unexpected tokens after message"));
+    }
+    Ok((value, message))
+}
+
+/// Parses a flag constraint, given either as `key` or as
+/// `key(message = "...")`. Returns the custom message.
+fn parse_flag_message(meta: &ParseNestedMeta) -> syn::Result<Option<LitStr>> {
+    if meta.input.is_empty() || meta.input.peek(Token![,]) {
+        return Ok(None);
+    }
+
+    let mut message = None;
+    meta.parse_nested_meta(|nested| {
+        if nested.path.is_ident("message") {
+            message = Some(nested.value()?.parse()?);
+            Ok(())
+        } else {
+            Err(nested.error("expected `message = \"...\"`"))
+        }
+    })?;
+    Ok(message)
+}
+
+/// Reads an integer literal from an attribute value.
+fn parse_usize(meta: &ParseNestedMeta, value: &Expr, name: &str) -> syn::Result<usize> {
+    if let Expr::Lit(ExprLit {
+        lit: Lit::Int(lit_int),
+        ..
+    }) = value
+    {
+        lit_int.base10_parse()
+    } else {
+        Err(meta.error(format!("expected integer literal for {}", name)))
+    }
+}
+
+/// A numeric literal from an attribute.
+#[derive(Clone)]
+pub(crate) struct NumberLit {
+    /// The value as a float, used to compare bounds with each other.
+    pub(crate) value: f64,
+    /// The exact value of an integer literal, which fits an `i64` or a `u64`.
+    pub(crate) integer: Option<i128>,
+}
+
+/// Reads an integer or float literal, possibly negative, from an attribute
+/// value.
+fn parse_number(meta: &ParseNestedMeta, expr: Expr, name: &str) -> syn::Result<NumberLit> {
+    let (negative, lit) = match &expr {
+        Expr::Lit(ExprLit { lit, .. }) => (false, lit),
+        Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr: inner,
+            ..
+        }) => match inner.as_ref() {
+            Expr::Lit(ExprLit { lit, .. }) => (true, lit),
+            _ => return Err(meta.error(format!("expected number literal for {}", name))),
+        },
+        _ => return Err(meta.error(format!("expected number literal for {}", name))),
+    };
+
+    match lit {
+        Lit::Int(lit_int) => {
+            let value: i128 = lit_int.base10_parse()?;
+            let value = if negative { -value } else { value };
+            if i64::try_from(value).is_err() && u64::try_from(value).is_err() {
+                return Err(meta.error(format!("{} is out of range", name)));
+            }
+            Ok(NumberLit {
+                value: value as f64,
+                integer: Some(value),
+            })
+        }
+        Lit::Float(lit_float) => {
+            let value: f64 = lit_float.base10_parse()?;
+            Ok(NumberLit {
+                value: if negative { -value } else { value },
+                integer: None,
+            })
+        }
+        _ => Err(meta.error(format!("expected number literal for {}", name))),
+    }
+}
+
+/// String formats accepted by `format = "..."`, with the matching
+/// `valrs::validators::StringFormat` variant.
+pub(crate) const STRING_FORMATS: [(&str, &str); 8] = [
+    ("email", "Email"),
+    ("uri", "Uri"),
+    ("uuid", "Uuid"),
+    ("date-time", "DateTime"),
+    ("date", "Date"),
+    ("ipv4", "Ipv4"),
+    ("ipv6", "Ipv6"),
+    ("hostname", "Hostname"),
+];
+
+/// The constraints accepting a custom message, with the placeholders their
+/// message may use besides `{field}`.
+const CONSTRAINT_PLACEHOLDERS: [(&str, &[&str]); 12] = [
+    ("min_length", &["min", "actual"]),
+    ("max_length", &["max", "actual"]),
+    ("format", &["format", "actual"]),
+    ("pattern", &["pattern", "actual"]),
+    ("minimum", &["min", "actual"]),
+    ("maximum", &["max", "actual"]),
+    ("exclusive_minimum", &["min", "actual"]),
+    ("exclusive_maximum", &["max", "actual"]),
+    ("multiple_of", &["multiple_of", "actual"]),
+    ("min_items", &["min", "actual"]),
+    ("max_items", &["max", "actual"]),
+    ("unique_items", &["index"]),
+];
+
+/// A custom issue message, with `{name}` placeholders filled in when the issue
+/// is raised. `{{` and `}}` are literal braces.
+#[derive(Clone)]
+pub(crate) struct Message {
+    /// The message as a `format!` string.
+    pub(crate) format: String,
+    /// The placeholders used, each once, in order of first use.
+    pub(crate) placeholders: Vec<String>,
+}
+
+impl Message {
+    /// Parses a message, checking it only uses `{field}` and `placeholders`.
+    fn parse(lit: &LitStr, placeholders: &[&str]) -> syn::Result<Self> {
+        let text = lit.value();
+        let mut message = Message {
+            format: String::new(),
+            placeholders: Vec::new(),
+        };
+
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    message.format.push_str("{{");
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    message.format.push_str("}}");
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => {
+                                return Err(syn::Error::new_spanned(
+                                    lit,
+                                    "unclosed `{` in message, use `{{` for a literal brace",
+                                ));
+                            }
+                        }
+                    }
+                    if name != "field" && !placeholders.contains(&name.as_str()) {
+                        let expected = std::iter::once(&"field")
+                            .chain(placeholders)
+                            .map(|name| format!("{{{}}}", name))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        return Err(syn::Error::new_spanned(
+                            lit,
+                            format!(
+                                "unknown placeholder {{{}}} in message, expected one of {}",
+                                name, expected
+                            ),
+                        ));
+                    }
+                    message.format.push_str(&format!("{{{}}}", name));
+                    if !message.placeholders.contains(&name) {
+                        message.placeholders.push(name);
+                    }
+                }
+                '}' => {
+                    return Err(syn::Error::new_spanned(
+                        lit,
+                        "unmatched `}` in message, use `}}` for a literal brace",
+                    ));
+                }
+                c => message.format.push(c),
+            }
+        }
+        Ok(message)
+    }
+}
+
+/// Parses `missing_message = "..."`, which may use the `{field}` placeholder.
+fn parse_missing_message(meta: &ParseNestedMeta) -> syn::Result<Message> {
+    let message: LitStr = meta.value()?.parse()?;
+    Message::parse(&message, &[])
+}
+
+/// Reads a `format` value, checking it is a supported format.
+fn parse_format(meta: &ParseNestedMeta, value: &Expr) -> syn::Result<String> {
+    let format = string_value(meta, value, "format")?;
+    if STRING_FORMATS.iter().any(|(name, _)| *name == format) {
+        Ok(format)
+    } else {
+        let expected = STRING_FORMATS
+            .iter()
+            .map(|(name, _)| format!("\"{}\"", name))
+            .collect::<Vec<_>>()
+            .join(", ");
+        Err(meta.error(format!(
+            "unknown format \"{}\", expected one of {}",
+            format, expected
+        )))
+    }
+}
+
+/// Reads a `pattern` value, checking it is a valid regular expression.
+fn parse_pattern(meta: &ParseNestedMeta, value: &Expr) -> syn::Result<String> {
+    let pattern = string_value(meta, value, "pattern")?;
+    regex::Regex::new(&pattern).map_err(|e| meta.error(format!("invalid pattern: {}", e)))?;
+    Ok(pattern)
+}
+
+/// Parses a rule name into a `RenameRule`.
+fn parse_rename_rule(meta: &ParseNestedMeta, name: &str) -> syn::Result<RenameRule> {
+    RenameRule::from_name(name).ok_or_else(|| {
+        meta.error(format!(
+            "unknown rename_all rule \"{}\", expected one of {}",
+            name,
+            RenameRule::expected()
+        ))
+    })
+}
+
+/// Parses `bound = "T: Trait, ..."`, which applies to both derived traits, or
+/// `bound(valrs = "...", schema = "...")`, which gives the bounds of the
+/// `Valrs` and `StandardJsonSchema` impls separately.
+fn parse_bound(meta: &ParseNestedMeta, container_attrs: &mut ContainerAttrs) -> syn::Result<()> {
+    if meta.input.peek(Token![=]) {
+        let predicates = parse_predicates(meta, "bound")?;
+        container_attrs.valrs_bound = Some(predicates.clone());
+        container_attrs.schema_bound = Some(predicates);
+        return Ok(());
+    }
+
+    meta.parse_nested_meta(|nested| {
+        if nested.path.is_ident("valrs") {
+            container_attrs.valrs_bound = Some(parse_predicates(&nested, "bound")?);
+            Ok(())
+        } else if nested.path.is_ident("schema") {
+            container_attrs.schema_bound = Some(parse_predicates(&nested, "bound")?);
+            Ok(())
+        } else {
+            Err(nested.error("expected `valrs = \"...\"` or `schema = \"...\"`"))
+        }
+    })
+}
+
+/// Parses a string of where-predicates, such as `"T: Trait, ..."`.
+fn parse_predicates(meta: &ParseNestedMeta, name: &str) -> syn::Result<Vec<WherePredicate>> {
+    let bound = parse_string(meta, name)?;
+    let predicates = Punctuated::<WherePredicate, Token![,]>::parse_terminated
+        .parse_str(&bound)
+        .map_err(|e| meta.error(format!("invalid bound: {}", e)))?;
+    Ok(predicates.into_iter().collect())
+}
+
+/// Parses `key("a", "b", ...)`, a list of at least two field names.
+fn parse_field_names(meta: &ParseNestedMeta, name: &str) -> syn::Result<Vec<LitStr>> {
+    let content;
+    syn::parenthesized!(content in meta.input);
+    let names = Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?;
+    if names.len() < 2 {
+        return Err(meta.error(format!("{} expects at least two field names", name)));
+    }
+    Ok(names.into_iter().collect())
+}
+
+/// Parses a serde `rename` or `rename_all` value, which is either `= "name"` or
+/// `(serialize = "...", deserialize = "...")`. Returns the name used when
+/// deserializing, if any.
+fn parse_serde_name(meta: &ParseNestedMeta, name: &str) -> syn::Result<Option<String>> {
+    if meta.input.peek(Token![=]) {
+        return parse_string(meta, name).map(Some);
+    }
+
+    let mut deserialize = None;
+    meta.parse_nested_meta(|nested| {
+        if nested.path.is_ident("deserialize") {
+            deserialize = Some(parse_string(&nested, "deserialize")?);
+        } else {
+            skip_meta(&nested)?;
+        }
+        Ok(())
+    })?;
+    Ok(deserialize)
+}
+
+/// Consumes a serde meta item that does not affect validation.
+fn skip_meta(meta: &ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.parse_nested_meta(|nested| skip_meta(&nested))?;
+    }
+    Ok(())
+}
+
+/// Parses a serde `default` or `default = "path"`, returning the path if any.
+fn parse_serde_default(meta: &ParseNestedMeta) -> syn::Result<Option<ExprPath>> {
+    if !meta.input.peek(Token![=]) {
+        return Ok(None);
+    }
+    let path = parse_string(meta, "default")?;
+    syn::parse_str(&path)
+        .map(Some)
+        .map_err(|e| meta.error(format!("invalid default function path: {}", e)))
+}
+
+/// Parses `example = <literal>`, a string, number or boolean literal.
+fn parse_example(meta: &ParseNestedMeta) -> syn::Result<Expr> {
+    let expr: Expr = meta.value()?.parse()?;
+    match &expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(_) | Lit::Int(_) | Lit::Float(_) | Lit::Bool(_),
+            ..
+        }) => Ok(expr),
+        Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr: inner,
+            ..
+        }) if matches!(
+            inner.as_ref(),
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(_) | Lit::Float(_),
+                ..
+            })
+        ) =>
+        {
+            Ok(expr)
+        }
+        _ => Err(meta.error("expected string, number or boolean literal for example")),
+    }
+}
+
+/// Schema annotations, shared by containers and fields.
+#[derive(Default)]
+pub(crate) struct Metadata {
+    /// `description`, from `description = "..."` or the doc comment.
+    pub(crate) description: Option<String>,
+    /// `title`.
+    pub(crate) title: Option<String>,
+    /// Example values, in order.
+    pub(crate) examples: Vec<Expr>,
+    /// Whether `deprecated` is set.
+    pub(crate) deprecated: bool,
+    /// Whether `readOnly` is set.
+    pub(crate) read_only: bool,
+    /// Whether `writeOnly` is set.
+    pub(crate) write_only: bool,
+}
+
+impl Metadata {
+    /// Parses one metadata item of a `#[schema(...)]` attribute. Returns
+    /// `false` if the item is not a metadata key.
+    fn parse_meta(&mut self, meta: &ParseNestedMeta) -> syn::Result<bool> {
+        if meta.path.is_ident("description") {
+            self.description = Some(parse_string(meta, "description")?);
+        } else if meta.path.is_ident("title") {
+            self.title = Some(parse_string(meta, "title")?);
+        } else if meta.path.is_ident("example") {
+            self.examples.push(parse_example(meta)?);
+        } else if meta.path.is_ident("deprecated") {
+            self.deprecated = true;
+        } else if meta.path.is_ident("read_only") {
+            self.read_only = true;
+        } else if meta.path.is_ident("write_only") {
+            self.write_only = true;
+        } else {
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    /// Uses the doc comment as the description, unless one was given.
+    ///
+    /// Lines are joined with newlines, without the space that usually follows
+    /// `///`, and surrounding blank lines are trimmed.
+    fn apply_doc_comment(&mut self, attrs: &[Attribute]) {
+        if self.description.is_some() {
+            return;
+        }
+
+        let lines: Vec<String> = attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("doc"))
+            .filter_map(|attr| match &attr.meta {
+                syn::Meta::NameValue(syn::MetaNameValue {
+                    value:
+                        Expr::Lit(ExprLit {
+                            lit: Lit::Str(doc), ..
+                        }),
+                    ..
+                }) => Some(doc.value()),
+                _ => None,
+            })
+            .map(|line| {
+                line.strip_prefix(' ')
+                    .unwrap_or(&line)
+                    .trim_end()
+                    .to_string()
+            })
+            .collect();
+
+        let doc = lines.join("\n");
+        let doc = doc.trim_matches('\n');
+        if !doc.is_empty() {
+            self.description = Some(doc.to_string());
+        }
+    }
+
+    /// Whether any annotation is set.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.description.is_none()
+            && self.title.is_none()
+            && self.examples.is_empty()
+            && !self.deprecated
+            && !self.read_only
+            && !self.write_only
+    }
+}
+
+/// What a struct does with input keys that are not its fields.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum UnknownFields {
+    /// Ignore them.
+    #[default]
+    Strip,
+    /// Report an issue for each (`deny_unknown_fields`).
+    Deny,
+    /// Collect them into the `#[schema(extra)]` field (`passthrough`).
+    Passthrough,
+}
+
+/// Container-level schema attributes (on the struct or enum itself).
+#[derive(Default)]
+pub(crate) struct ContainerAttrs {
+    /// Case conversion applied to all field names (structs) or variant names (enums).
+    pub(crate) rename_all: Option<RenameRule>,
+    /// Enum tag key (internally or adjacently tagged representation).
+    pub(crate) tag: Option<String>,
+    /// Enum content key (adjacently tagged representation, requires `tag`).
+    pub(crate) content: Option<String>,
+    /// Whether the enum is untagged.
+    pub(crate) untagged: bool,
+    /// Where-predicates replacing the automatic bounds on type parameters in
+    /// the `Valrs` impl.
+    pub(crate) valrs_bound: Option<Vec<WherePredicate>>,
+    /// Where-predicates replacing the automatic bounds on type parameters in
+    /// the `StandardJsonSchema` impl.
+    pub(crate) schema_bound: Option<Vec<WherePredicate>>,
+    /// Whether missing fields take their value from a default instance of the
+    /// struct, built by `Default` or the given function (serde only).
+    pub(crate) default: Option<Option<ExprPath>>,
+    /// Whether serde attributes are read on this container and its fields.
+    pub(crate) serde: bool,
+    /// Functions run on the constructed struct, in order.
+    pub(crate) checks: Vec<CustomValidator>,
+    /// Groups of fields of which at least one must be present.
+    pub(crate) require_one_of: Vec<Vec<LitStr>>,
+    /// Groups of fields that must be present together or not at all.
+    pub(crate) require_together: Vec<Vec<LitStr>>,
+    /// Custom message for the issue raised when a field is missing.
+    pub(crate) missing_message: Option<Message>,
+    /// Whether `Option<T>` fields are optional without `#[schema(optional)]`.
+    pub(crate) implicit_optional: bool,
+    /// What to do with unknown keys.
+    pub(crate) unknown_fields: UnknownFields,
+    /// Annotations for the container's schema.
+    pub(crate) metadata: Metadata,
+}
+
+impl ContainerAttrs {
+    pub(crate) fn from_attributes(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut container_attrs = Self::from_schema_attributes(attrs)?;
+        if !container_attrs.serde {
+            return Ok(container_attrs);
+        }
+
+        let mut serde_attrs = ContainerAttrs::default();
+        for attr in attrs {
+            if !attr.path().is_ident("serde") {
+                continue;
+            }
+
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename_all") {
+                    if let Some(name) = parse_serde_name(&meta, "rename_all")? {
+                        serde_attrs.rename_all = Some(parse_rename_rule(&meta, &name)?);
+                    }
+                } else if meta.path.is_ident("tag") {
+                    serde_attrs.tag = Some(parse_string(&meta, "tag")?);
+                } else if meta.path.is_ident("content") {
+                    serde_attrs.content = Some(parse_string(&meta, "content")?);
+                } else if meta.path.is_ident("untagged") {
+                    serde_attrs.untagged = true;
+                } else if meta.path.is_ident("default") {
+                    serde_attrs.default = Some(parse_serde_default(&meta)?);
+                } else if meta.path.is_ident("deny_unknown_fields") {
+                    serde_attrs.unknown_fields = UnknownFields::Deny;
+                } else {
+                    skip_meta(&meta)?;
+                }
+                Ok(())
+            })?;
+        }
+
+        container_attrs.rename_all = container_attrs.rename_all.or(serde_attrs.rename_all);
+        container_attrs.tag = container_attrs.tag.or(serde_attrs.tag);
+        container_attrs.content = container_attrs.content.or(serde_attrs.content);
+        container_attrs.untagged |= serde_attrs.untagged;
+        container_attrs.default = serde_attrs.default;
+        if container_attrs.unknown_fields == UnknownFields::Strip {
+            container_attrs.unknown_fields = serde_attrs.unknown_fields;
+        }
+
+        Ok(container_attrs)
+    }
+
+    /// Parses only the `#[schema(...)]` attributes, ignoring serde.
+    pub(crate) fn from_schema_attributes(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut container_attrs = ContainerAttrs {
+            serde: true,
+            ..ContainerAttrs::default()
+        };
+
+        for attr in attrs {
+            if !attr.path().is_ident("schema") {
+                continue;
+            }
+
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename_all") {
+                    let name = parse_string(&meta, "rename_all")?;
+                    container_attrs.rename_all = Some(parse_rename_rule(&meta, &name)?);
+                    Ok(())
+                } else if meta.path.is_ident("tag") {
+                    container_attrs.tag = Some(parse_string(&meta, "tag")?);
+                    Ok(())
+                } else if meta.path.is_ident("content") {
+                    container_attrs.content = Some(parse_string(&meta, "content")?);
+                    Ok(())
+                } else if meta.path.is_ident("untagged") {
+                    container_attrs.untagged = true;
+                    Ok(())
+                } else if meta.path.is_ident("bound") {
+                    parse_bound(&meta, &mut container_attrs)
+                } else if meta.path.is_ident("ignore_serde") {
+                    container_attrs.serde = false;
+                    Ok(())
+                } else if meta.path.is_ident("check") || meta.path.is_ident("check_with_context") {
+                    container_attrs
+                        .checks
+                        .push(CustomValidator::parse(&meta, "check")?);
+                    Ok(())
+                } else if meta.path.is_ident("require_one_of") {
+                    container_attrs
+                        .require_one_of
+                        .push(parse_field_names(&meta, "require_one_of")?);
+                    Ok(())
+                } else if meta.path.is_ident("require_together") {
+                    container_attrs
+                        .require_together
+                        .push(parse_field_names(&meta, "require_together")?);
+                    Ok(())
+                } else if meta.path.is_ident("missing_message") {
+                    container_attrs.missing_message = Some(parse_missing_message(&meta)?);
+                    Ok(())
+                } else if meta.path.is_ident("implicit_optional") {
+                    container_attrs.implicit_optional = true;
+                    Ok(())
+                } else if meta.path.is_ident("deny_unknown_fields")
+                    || meta.path.is_ident("passthrough")
+                {
+                    let policy = if meta.path.is_ident("passthrough") {
+                        UnknownFields::Passthrough
+                    } else {
+                        UnknownFields::Deny
+                    };
+                    if container_attrs.unknown_fields != UnknownFields::Strip
+                        && container_attrs.unknown_fields != policy
+                    {
+                        return Err(
+                            meta.error("deny_unknown_fields and passthrough cannot be combined")
+                        );
+                    }
+                    container_attrs.unknown_fields = policy;
+                    Ok(())
+                } else if container_attrs.metadata.parse_meta(&meta)? {
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown schema container attribute"))
+                }
+            })?;
+        }
+        container_attrs.metadata.apply_doc_comment(attrs);
+
+        Ok(container_attrs)
+    }
+}
+
+/// Variant-level schema attributes.
+#[derive(Default)]
+pub(crate) struct VariantAttrs {
+    /// Rename the JSON value for this variant.
+    pub(crate) rename: Option<String>,
+    /// Case conversion applied to the fields of a struct variant (serde only).
+    pub(crate) rename_all: Option<RenameRule>,
+}
+
+impl VariantAttrs {
+    /// Parses variant attributes, reading serde attributes when `serde` is set.
+    pub(crate) fn from_attributes(attrs: &[Attribute], serde: bool) -> syn::Result<Self> {
+        let mut variant_attrs = VariantAttrs::default();
+        let mut serde_attrs = VariantAttrs::default();
+
+        for attr in attrs {
+            if attr.path().is_ident("schema") {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("rename") {
+                        variant_attrs.rename = Some(parse_string(&meta, "rename")?);
+                        Ok(())
+                    } else {
+                        Err(meta.error("unknown schema variant attribute"))
+                    }
+                })?;
+            } else if serde && attr.path().is_ident("serde") {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("rename") {
+                        serde_attrs.rename = parse_serde_name(&meta, "rename")?;
+                    } else if meta.path.is_ident("rename_all") {
+                        if let Some(name) = parse_serde_name(&meta, "rename_all")? {
+                            serde_attrs.rename_all = Some(parse_rename_rule(&meta, &name)?);
+                        }
+                    } else {
+                        skip_meta(&meta)?;
+                    }
+                    Ok(())
+                })?;
+            }
+        }
+
+        variant_attrs.rename = variant_attrs.rename.or(serde_attrs.rename);
+        variant_attrs.rename_all = serde_attrs.rename_all;
+
+        Ok(variant_attrs)
+    }
+}
+
+/// Where the value of a missing field comes from.
+pub(crate) enum FieldDefault {
+    /// `Default::default()` for the field type.
+    Trait,
+    /// A function returning the value.
+    Path(ExprPath),
+    /// The field's value in a default instance of the container, built by
+    /// `Default` or the given function (serde container `default`).
+    Container(Option<ExprPath>),
+    /// A number or boolean literal, also emitted as the schema `default`.
+    Literal(Expr),
+}
+
+/// Parses a schema field `default`, `default = "path::to::fn"` or
+/// `default = <number or boolean literal>`.
+fn parse_default(meta: &ParseNestedMeta) -> syn::Result<FieldDefault> {
+    if !meta.input.peek(Token![=]) {
+        return Ok(FieldDefault::Trait);
+    }
+
+    let expr: Expr = meta.value()?.parse()?;
+    let lit = match &expr {
+        Expr::Lit(ExprLit { lit, .. }) => lit,
+        Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr: inner,
+            ..
+        }) => match inner.as_ref() {
+            Expr::Lit(ExprLit {
+                lit: lit @ (Lit::Int(_) | Lit::Float(_)),
+                ..
+            }) => lit,
+            _ => return Err(meta.error("expected number literal for default")),
+        },
+        _ => return Err(meta.error("expected string, number or boolean literal for default")),
+    };
+
+    match lit {
+        Lit::Str(path) => path
+            .parse()
+            .map(FieldDefault::Path)
+            .map_err(|e| meta.error(format!("invalid default function path: {}", e))),
+        Lit::Int(_) | Lit::Float(_) | Lit::Bool(_) => Ok(FieldDefault::Literal(expr)),
+        _ => Err(meta.error("expected string, number or boolean literal for default")),
+    }
+}
+
+/// A custom validator or check function.
+pub(crate) struct CustomValidator {
+    pub(crate) path: ExprPath,
+    /// Whether the function also takes the `ValidationContext`, for
+    /// `validate_with_context` and `check_with_context`.
+    pub(crate) with_context: bool,
+}
+
+impl CustomValidator {
+    /// Parses `key = "path::to::fn"`; `what` names the function in errors.
+    fn parse(meta: &ParseNestedMeta, what: &str) -> syn::Result<Self> {
+        let key = meta
+            .path
+            .get_ident()
+            .map(ToString::to_string)
+            .unwrap_or_default();
+        let path = parse_string(meta, &key)?;
+        Ok(CustomValidator {
+            path: syn::parse_str(&path)
+                .map_err(|e| meta.error(format!("invalid {} function path: {}", what, e)))?,
+            with_context: key.ends_with("_with_context"),
+        })
+    }
+}
+
+/// Constraints checked on a validated value, with their custom messages.
+#[derive(Default)]
+pub(crate) struct Constraints {
+    /// Minimum string length validation.
+    pub(crate) min_length: Option<usize>,
+    /// Maximum string length validation.
+    pub(crate) max_length: Option<usize>,
+    /// String format, named as in JSON Schema (`email`, `uri`, ...).
+    pub(crate) format: Option<String>,
+    /// Regular expression that strings must match.
+    pub(crate) pattern: Option<String>,
+    /// Inclusive lower bound for numbers.
+    pub(crate) minimum: Option<NumberLit>,
+    /// Inclusive upper bound for numbers.
+    pub(crate) maximum: Option<NumberLit>,
+    /// Exclusive lower bound for numbers.
+    pub(crate) exclusive_minimum: Option<NumberLit>,
+    /// Exclusive upper bound for numbers.
+    pub(crate) exclusive_maximum: Option<NumberLit>,
+    /// Numbers must be a multiple of this (positive) value.
+    pub(crate) multiple_of: Option<NumberLit>,
+    /// Minimum number of items in a collection.
+    pub(crate) min_items: Option<usize>,
+    /// Maximum number of items in a collection.
+    pub(crate) max_items: Option<usize>,
+    /// Whether the items of a collection must all be different.
+    pub(crate) unique_items: bool,
+    /// Constraints checked on each item of a collection.
+    pub(crate) items: Option<Box<Constraints>>,
+    /// Custom validator functions, run in order on the validated value.
+    pub(crate) validate_with: Vec<CustomValidator>,
+    /// Custom messages for the constraints above, by constraint name.
+    pub(crate) messages: BTreeMap<&'static str, Message>,
+}
+
+impl Constraints {
+    /// Parses one constraint, with its custom message if any. Returns whether
+    /// `meta` was a constraint.
+    pub(crate) fn parse_meta(&mut self, meta: &ParseNestedMeta) -> syn::Result<bool> {
+        if meta.path.is_ident("validate_with") || meta.path.is_ident("validate_with_context") {
+            self.validate_with
+                .push(CustomValidator::parse(meta, "validator")?);
+            return Ok(true);
+        } else if meta.path.is_ident("items") {
+            let items = self.items.get_or_insert_with(Default::default);
+            meta.parse_nested_meta(|nested| {
+                if items.parse_meta(&nested)? {
+                    Ok(())
+                } else {
+                    Err(nested.error("unknown item constraint"))
+                }
+            })?;
+            return Ok(true);
+        }
+
+        let Some(&(name, placeholders)) = CONSTRAINT_PLACEHOLDERS
+            .iter()
+            .find(|(name, _)| meta.path.is_ident(name))
+        else {
+            return Ok(false);
+        };
+
+        let message = if name == "unique_items" {
+            self.unique_items = true;
+            parse_flag_message(meta)?
+        } else {
+            let (value, message) = parse_constraint_value(meta)?;
+            match name {
+                "min_length" => self.min_length = Some(parse_usize(meta, &value, name)?),
+                "max_length" => self.max_length = Some(parse_usize(meta, &value, name)?),
+                "min_items" => self.min_items = Some(parse_usize(meta, &value, name)?),
+                "max_items" => self.max_items = Some(parse_usize(meta, &value, name)?),
+                "format" => self.format = Some(parse_format(meta, &value)?),
+                "pattern" => self.pattern = Some(parse_pattern(meta, &value)?),
+                "minimum" => self.minimum = Some(parse_number(meta, value, name)?),
+                "maximum" => self.maximum = Some(parse_number(meta, value, name)?),
+                "exclusive_minimum" => {
+                    self.exclusive_minimum = Some(parse_number(meta, value, name)?)
+                }
+                "exclusive_maximum" => {
+                    self.exclusive_maximum = Some(parse_number(meta, value, name)?)
+                }
+                "multiple_of" => {
+                    let multiple_of = parse_number(meta, value, name)?;
+                    if multiple_of.value <= 0.0 {
+                        return Err(meta.error("multiple_of must be positive"));
+                    }
+                    self.multiple_of = Some(multiple_of);
+                }
+                _ => unreachable!("every constraint in CONSTRAINT_PLACEHOLDERS is handled"),
+            }
+            message
+        };
+
+        if let Some(message) = message {
+            self.messages
+                .insert(name, Message::parse(&message, placeholders)?);
+        }
+        Ok(true)
+    }
+
+    /// Whether there are constraints to check.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.min_length.is_none()
+            && self.max_length.is_none()
+            && self.format.is_none()
+            && self.pattern.is_none()
+            && self.minimum.is_none()
+            && self.maximum.is_none()
+            && self.exclusive_minimum.is_none()
+            && self.exclusive_maximum.is_none()
+            && self.multiple_of.is_none()
+            && self.min_items.is_none()
+            && self.max_items.is_none()
+            && !self.unique_items
+            && self.items.is_none()
+            && self.validate_with.is_empty()
+    }
+}
+
+/// Field-level schema attributes.
+#[derive(Default)]
+pub(crate) struct FieldAttrs {
+    /// Whether the field is optional (can be missing from input).
+    pub(crate) optional: bool,
+    /// Whether an optional field rejects `null` instead of treating it as
+    /// missing.
+    pub(crate) non_nullable: bool,
+    /// Rename the JSON key for this field.
+    pub(crate) rename: Option<String>,
+    /// Whether the field is validated in coercion mode.
+    pub(crate) coerce: bool,
+    /// Constraints checked on the validated value.
+    pub(crate) constraints: Constraints,
+    /// Custom message for the issue raised when the field is missing.
+    pub(crate) missing_message: Option<Message>,
+    /// Additional keys accepted in input for this field.
+    pub(crate) aliases: Vec<String>,
+    /// The value used when the field is missing.
+    pub(crate) default: Option<FieldDefault>,
+    /// Whether the field is never read from input and always defaulted.
+    pub(crate) skip: bool,
+    /// Whether the field's own fields are read from the enclosing object.
+    pub(crate) flatten: bool,
+    /// Whether the field collects the unknown keys of a `passthrough` struct.
+    pub(crate) extra: bool,
+    /// Annotations for the field's schema.
+    pub(crate) metadata: Metadata,
+}
+
+impl FieldAttrs {
+    /// Parses field attributes, reading serde attributes when `serde` is set.
+    pub(crate) fn from_attributes(attrs: &[Attribute], serde: bool) -> syn::Result<Self> {
+        let mut field_attrs = FieldAttrs::default();
+        let mut serde_attrs = FieldAttrs::default();
+
+        for attr in attrs {
+            if attr.path().is_ident("schema") {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("optional") {
+                        field_attrs.optional = true;
+                        Ok(())
+                    } else if meta.path.is_ident("non_nullable") {
+                        field_attrs.non_nullable = true;
+                        Ok(())
+                    } else if meta.path.is_ident("rename") {
+                        field_attrs.rename = Some(parse_string(&meta, "rename")?);
+                        Ok(())
+                    } else if meta.path.is_ident("coerce") {
+                        field_attrs.coerce = true;
+                        Ok(())
+                    } else if field_attrs.constraints.parse_meta(&meta)? {
+                        Ok(())
+                    } else if meta.path.is_ident("missing_message") {
+                        field_attrs.missing_message = Some(parse_missing_message(&meta)?);
+                        Ok(())
+                    } else if meta.path.is_ident("alias") {
+                        field_attrs.aliases.push(parse_string(&meta, "alias")?);
+                        Ok(())
+                    } else if meta.path.is_ident("default") {
+                        field_attrs.default = Some(parse_default(&meta)?);
+                        Ok(())
+                    } else if meta.path.is_ident("flatten") {
+                        field_attrs.flatten = true;
+                        Ok(())
+                    } else if meta.path.is_ident("extra") {
+                        field_attrs.extra = true;
+                        Ok(())
+                    } else if field_attrs.metadata.parse_meta(&meta)? {
+                        Ok(())
+                    } else {
+                        Err(meta.error("unknown schema attribute"))
+                    }
+                })?;
+            } else if serde && attr.path().is_ident("serde") {
+                attr.parse_nested_meta(|meta| serde_attrs.parse_serde_meta(&meta))?;
+            }
+        }
+        field_attrs.metadata.apply_doc_comment(attrs);
+
+        field_attrs.rename = field_attrs.rename.or(serde_attrs.rename);
+        field_attrs.aliases.extend(serde_attrs.aliases);
+        field_attrs.default = field_attrs.default.or(serde_attrs.default);
+        field_attrs.skip |= serde_attrs.skip;
+        field_attrs.flatten |= serde_attrs.flatten;
+
+        Ok(field_attrs)
+    }
+
+    /// Whether the field has constraints checked on its validated value.
+    pub(crate) fn has_constraints(&self) -> bool {
+        !self.constraints.is_empty()
+    }
+
+    /// Parses one item of a `#[serde(...)]` field attribute.
+    fn parse_serde_meta(&mut self, meta: &ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("rename") {
+            self.rename = parse_serde_name(meta, "rename")?;
+        } else if meta.path.is_ident("alias") {
+            self.aliases.push(parse_string(meta, "alias")?);
+        } else if meta.path.is_ident("default") {
+            self.default = Some(match parse_serde_default(meta)? {
+                Some(path) => FieldDefault::Path(path),
+                None => FieldDefault::Trait,
+            });
+        } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_deserializing") {
+            self.skip = true;
+        } else if meta.path.is_ident("flatten") {
+            self.flatten = true;
+        } else {
+            skip_meta(meta)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_message(text: &str) -> syn::Result<Message> {
+        Message::parse(&LitStr::new(text, proc_macro2::Span::call_site()), &["min"])
+    }
+
+    #[test]
+    fn test_message_placeholders() {
+        let message = parse_message("{field} needs {min} {{items}}").unwrap();
+        assert_eq!(message.format, "{field} needs {min} {{items}}");
+        assert_eq!(message.placeholders, ["field", "min"]);
+    }
+
+    #[test]
+    fn test_message_errors() {
+        let error = |text| parse_message(text).err().unwrap().to_string();
+        assert_eq!(
+            error("at least {min"),
+            "unclosed `{` in message, use `{{` for a literal brace"
+        );
+        assert_eq!(
+            error("at least {max}"),
+            "unknown placeholder {max} in message, expected one of {field}, {min}"
+        );
+        assert_eq!(
+            error("at least }"),
+            "unmatched `}` in message, use `}}` for a literal brace"
+        );
+    }
+}
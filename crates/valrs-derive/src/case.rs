@@ -0,0 +1,169 @@
+//! Case conversion rules for `rename_all`.
+//!
+//! These mirror serde's `rename_all` rules so the same strings can be used in
+//! both `#[serde(...)]` and `#[schema(...)]` attributes.
+
+/// A case conversion applied to field or variant names.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum RenameRule {
+    /// `lowercase`
+    Lower,
+    /// `UPPERCASE`
+    Upper,
+    /// `PascalCase`
+    Pascal,
+    /// `camelCase`
+    Camel,
+    /// `snake_case`
+    Snake,
+    /// `SCREAMING_SNAKE_CASE`
+    ScreamingSnake,
+    /// `kebab-case`
+    Kebab,
+    /// `SCREAMING-KEBAB-CASE`
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    /// All rules with their attribute spelling, in the order listed in errors.
+    const ALL: [(&'static str, RenameRule); 8] = [
+        ("lowercase", RenameRule::Lower),
+        ("UPPERCASE", RenameRule::Upper),
+        ("PascalCase", RenameRule::Pascal),
+        ("camelCase", RenameRule::Camel),
+        ("snake_case", RenameRule::Snake),
+        ("SCREAMING_SNAKE_CASE", RenameRule::ScreamingSnake),
+        ("kebab-case", RenameRule::Kebab),
+        ("SCREAMING-KEBAB-CASE", RenameRule::ScreamingKebab),
+    ];
+
+    /// Parses a rule from its attribute spelling.
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .find(|(spelling, _)| *spelling == name)
+            .map(|(_, rule)| *rule)
+    }
+
+    /// Returns a comma-separated list of all accepted spellings.
+    pub(crate) fn expected() -> String {
+        Self::ALL
+            .iter()
+            .map(|(spelling, _)| format!("\"{}\"", spelling))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Applies the rule to a variant name, which is expected to be `PascalCase`.
+    pub(crate) fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            RenameRule::Pascal => variant.to_owned(),
+            RenameRule::Lower => variant.to_ascii_lowercase(),
+            RenameRule::Upper => variant.to_ascii_uppercase(),
+            RenameRule::Camel => {
+                let mut chars = variant.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                    None => String::new(),
+                }
+            }
+            RenameRule::Snake => {
+                let mut snake = String::new();
+                for (i, ch) in variant.char_indices() {
+                    if i > 0 && ch.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(ch.to_ascii_lowercase());
+                }
+                snake
+            }
+            RenameRule::ScreamingSnake => RenameRule::Snake
+                .apply_to_variant(variant)
+                .to_ascii_uppercase(),
+            RenameRule::Kebab => RenameRule::Snake
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+            RenameRule::ScreamingKebab => RenameRule::ScreamingSnake
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+        }
+    }
+
+    /// Applies the rule to a field name, which is expected to be `snake_case`.
+    pub(crate) fn apply_to_field(self, field: &str) -> String {
+        match self {
+            RenameRule::Lower | RenameRule::Snake => field.to_owned(),
+            RenameRule::Upper | RenameRule::ScreamingSnake => field.to_ascii_uppercase(),
+            RenameRule::Pascal => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(ch);
+                    }
+                }
+                pascal
+            }
+            RenameRule::Camel => {
+                let pascal = RenameRule::Pascal.apply_to_field(field);
+                let mut chars = pascal.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                    None => String::new(),
+                }
+            }
+            RenameRule::Kebab => field.replace('_', "-"),
+            RenameRule::ScreamingKebab => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variant_rules() {
+        let cases = [
+            (RenameRule::Lower, "verylongvariant"),
+            (RenameRule::Upper, "VERYLONGVARIANT"),
+            (RenameRule::Pascal, "VeryLongVariant"),
+            (RenameRule::Camel, "veryLongVariant"),
+            (RenameRule::Snake, "very_long_variant"),
+            (RenameRule::ScreamingSnake, "VERY_LONG_VARIANT"),
+            (RenameRule::Kebab, "very-long-variant"),
+            (RenameRule::ScreamingKebab, "VERY-LONG-VARIANT"),
+        ];
+        for (rule, expected) in cases {
+            assert_eq!(rule.apply_to_variant("VeryLongVariant"), expected);
+        }
+    }
+
+    #[test]
+    fn test_field_rules() {
+        let cases = [
+            (RenameRule::Lower, "very_long_field"),
+            (RenameRule::Upper, "VERY_LONG_FIELD"),
+            (RenameRule::Pascal, "VeryLongField"),
+            (RenameRule::Camel, "veryLongField"),
+            (RenameRule::Snake, "very_long_field"),
+            (RenameRule::ScreamingSnake, "VERY_LONG_FIELD"),
+            (RenameRule::Kebab, "very-long-field"),
+            (RenameRule::ScreamingKebab, "VERY-LONG-FIELD"),
+        ];
+        for (rule, expected) in cases {
+            assert_eq!(rule.apply_to_field("very_long_field"), expected);
+        }
+    }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(RenameRule::from_name("camelCase"), Some(RenameRule::Camel));
+        assert_eq!(RenameRule::from_name("camelcase"), None);
+    }
+}
@@ -0,0 +1,226 @@
+//! Code generation for struct-level checks: `check` functions run on the
+//! constructed struct, and the `require_one_of` and `require_together` field
+//! presence rules.
+//!
+//! Presence rules are checked on the input object alongside the fields, and
+//! are also expressed in the struct's JSON Schema.
+
+use std::collections::BTreeMap;
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::{Error, LitStr};
+
+use crate::attrs::ContainerAttrs;
+use crate::{ParsedField, constraints};
+
+/// A field presence rule from the container attributes.
+pub(crate) enum PresenceRule<'a> {
+    /// At least one of the fields must be present.
+    OneOf(Vec<&'a ParsedField>),
+    /// If one of the fields is present, all of them must be.
+    Together(Vec<&'a ParsedField>),
+}
+
+/// Resolves the field names of the container's presence rules.
+pub(crate) fn parse_presence_rules<'a>(
+    container_attrs: &ContainerAttrs,
+    fields: &'a [ParsedField],
+) -> syn::Result<Vec<PresenceRule<'a>>> {
+    let resolve = |names: &Vec<LitStr>| -> syn::Result<Vec<&'a ParsedField>> {
+        names
+            .iter()
+            .map(|name| {
+                let field = fields
+                    .iter()
+                    .find(|f| f.ident.unraw() == name.value())
+                    .ok_or_else(|| {
+                        Error::new_spanned(name, format!("unknown field `{}`", name.value()))
+                    })?;
+                if field.attrs.skip || field.attrs.flatten {
+                    return Err(Error::new_spanned(
+                        name,
+                        format!(
+                            "field `{}` is skipped or flattened and cannot be required",
+                            name.value()
+                        ),
+                    ));
+                }
+                Ok(field)
+            })
+            .collect()
+    };
+
+    let one_of = container_attrs
+        .require_one_of
+        .iter()
+        .map(|names| resolve(names).map(PresenceRule::OneOf));
+    let together = container_attrs
+        .require_together
+        .iter()
+        .map(|names| resolve(names).map(PresenceRule::Together));
+    one_of.chain(together).collect()
+}
+
+/// Generates an expression that is true when `field` is present in
+/// `__valrs_obj`, under its key or any of its aliases.
+///
+/// A key set to null is present, as for the `required` keyword the rules
+/// become in the schema.
+fn generate_presence(field: &ParsedField) -> TokenStream2 {
+    let keys = std::iter::once(&field.key).chain(&field.attrs.aliases);
+    quote! {
+        [#(#keys),*].iter().any(|key| __valrs_obj.contains_key(*key))
+    }
+}
+
+/// Generates statements checking the presence rules against `__valrs_obj`,
+/// pushing any issues to `__valrs_issues`.
+pub(crate) fn generate_presence_checks(rules: &[PresenceRule]) -> TokenStream2 {
+    let checks = rules.iter().map(|rule| match rule {
+        PresenceRule::OneOf(fields) => {
+            let present = fields.iter().map(|f| generate_presence(f));
+            let message = format!(
+                "At least one of {} is required",
+                fields
+                    .iter()
+                    .map(|f| format!("'{}'", f.key))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            let keys = fields.iter().map(|f| &f.key);
+            quote! {
+                if !(#(#present)||*) {
+                    __valrs_issues.push(
+                        ::valrs::ValidationIssue::missing_one_of(#message, &[#(#keys),*]).at(vec![]),
+                    );
+                }
+            }
+        }
+        PresenceRule::Together(fields) => {
+            let present = fields.iter().map(|f| generate_presence(f));
+            let keys = fields.iter().map(|f| &f.key);
+            quote! {{
+                let keys = [#(#keys),*];
+                let present = [#(#present),*];
+                if let Some(first) = present.iter().position(|p| *p) {
+                    for (key, _) in keys.iter().zip(present).filter(|(_, p)| !p) {
+                        __valrs_issues.push(
+                            ::valrs::ValidationIssue::missing_field(format!(
+                                "Field '{}' is required when '{}' is present",
+                                key, keys[first]
+                            ))
+                            .at(vec![::valrs::PathSegment::Key(key.to_string())]),
+                        );
+                    }
+                }
+            }}
+        }
+    });
+
+    quote! {
+        #(#checks)*
+    }
+}
+
+/// Generates statements calling the container's `check` functions on
+/// `__valrs_value`, in order, pushing their issues to `__valrs_issues`.
+pub(crate) fn generate_struct_checks(container_attrs: &ContainerAttrs) -> TokenStream2 {
+    let checks = container_attrs.checks.iter().map(|check| {
+        let call = constraints::generate_custom_call(check, quote! { &__valrs_value });
+        quote! {
+            __valrs_issues.extend(::valrs::IntoIssues::into_issues(#call));
+        }
+    });
+
+    quote! {
+        #(#checks)*
+    }
+}
+
+/// Generates statements adding the presence rules to the object schema in
+/// `schema`.
+///
+/// `require_one_of` becomes an `anyOf` of `required` keywords.
+/// `require_together` becomes `dependentRequired` (`dependencies` in Draft 07);
+/// OpenAPI 3.0 has neither, so there it is spelled out as "all of the fields or
+/// none of them" with `anyOf` and `not`. Several `anyOf` keywords are combined
+/// with `allOf`, extending the one added for flattened fields if any. The
+/// statements expect `target` to be in scope.
+pub(crate) fn generate_presence_schema(rules: &[PresenceRule]) -> TokenStream2 {
+    if rules.is_empty() {
+        return quote! {};
+    }
+
+    let mut clauses = Vec::new();
+    let mut openapi_clauses = Vec::new();
+    let mut dependent: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+
+    for rule in rules {
+        match rule {
+            PresenceRule::OneOf(fields) => {
+                let keys = fields.iter().map(|f| &f.key);
+                clauses.push(quote! {
+                    ::serde_json::json!({ "anyOf": [#({ "required": [#keys] }),*] })
+                });
+            }
+            PresenceRule::Together(fields) => {
+                let keys: Vec<&str> = fields.iter().map(|f| f.key.as_str()).collect();
+                for key in &keys {
+                    let others = dependent.entry(key).or_default();
+                    for other in &keys {
+                        if other != key && !others.contains(other) {
+                            others.push(other);
+                        }
+                    }
+                }
+                openapi_clauses.push(quote! {
+                    ::serde_json::json!({
+                        "anyOf": [
+                            { "required": [#(#keys),*] },
+                            { "not": { "anyOf": [#({ "required": [#keys] }),*] } }
+                        ]
+                    })
+                });
+            }
+        }
+    }
+
+    let dependent_keys = dependent.keys();
+    let dependent_values = dependent.values().map(|others| quote! { [#(#others),*] });
+    let dependent_insert = (!dependent.is_empty()).then(|| {
+        quote! {
+            if target != ::valrs::JsonSchemaTarget::OpenApi30 {
+                let keyword = match target {
+                    ::valrs::JsonSchemaTarget::Draft07 => "dependencies",
+                    _ => "dependentRequired",
+                };
+                map.insert(
+                    keyword.to_string(),
+                    ::serde_json::json!({ #(#dependent_keys: #dependent_values),* }),
+                );
+            }
+        }
+    });
+
+    quote! {
+        if let ::serde_json::Value::Object(ref mut map) = schema {
+            let mut clauses: Vec<::serde_json::Value> = vec![#(#clauses),*];
+            if target == ::valrs::JsonSchemaTarget::OpenApi30 {
+                clauses.extend([#(#openapi_clauses),*]);
+            }
+            #dependent_insert
+            if clauses.len() > 1 {
+                match map.get_mut("allOf") {
+                    Some(::serde_json::Value::Array(all_of)) => all_of.extend(clauses),
+                    _ => {
+                        map.insert("allOf".to_string(), ::serde_json::Value::Array(clauses));
+                    }
+                }
+            } else if let Some(::serde_json::Value::Object(clause)) = clauses.pop() {
+                map.extend(clause);
+            }
+        }
+    }
+}
@@ -0,0 +1,600 @@
+//! Code generation for field constraints: string lengths, formats and
+//! patterns, numeric bounds, collection sizes and per-item constraints, and
+//! custom validator functions.
+//!
+//! Checks run on the validated value of a field, and the matching JSON Schema
+//! keywords are added to the field's schema.
+
+use proc_macro2::{Literal, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+
+use crate::attrs::{Constraints, CustomValidator, Message, NumberLit, STRING_FORMATS};
+use crate::{IssuePath, ParsedField};
+
+/// Generates code running `field`'s constraints against `validated_val`,
+/// pushing any issues to `__valrs_issues`. Empty if the field has no
+/// constraints.
+pub(crate) fn generate_constraint_checks(field: &ParsedField, path: &IssuePath) -> TokenStream2 {
+    if !field.attrs.has_constraints() {
+        return quote! {};
+    }
+
+    let checks = generate_checks(&field.attrs.constraints, &field.key, path);
+    quote! {
+        let val = &validated_val;
+        #checks
+    }
+}
+
+/// Generates code running `constraints` against `val`. `key` is the field's
+/// key, for the `{field}` placeholder of custom messages.
+fn generate_checks(constraints: &Constraints, key: &str, path: &IssuePath) -> TokenStream2 {
+    let length_validations = generate_length_validations(constraints, key, path);
+    let string_validations = generate_string_validations(constraints, key, path);
+    let number_validations = generate_number_validations(constraints, key, path);
+    let collection_validations = generate_collection_validations(constraints, key, path);
+    let custom_validations = generate_custom_validations(constraints, path);
+
+    quote! {
+        #length_validations
+        #string_validations
+        #number_validations
+        #collection_validations
+        #custom_validations
+    }
+}
+
+fn generate_length_validations(
+    constraints: &Constraints,
+    key: &str,
+    path: &IssuePath,
+) -> TokenStream2 {
+    let mut validations = Vec::new();
+
+    // For string length validations, `val` is a reference to the validated value.
+    // For required String fields, val is &String.
+    // For optional String fields with length validation, val is &String (inner type).
+
+    if let Some(min_len) = constraints.min_length {
+        let message = quote! { format!("String must be at least {} characters, got {}", #min_len, val.len()) };
+        let custom_message = generate_custom_message(
+            constraints,
+            key,
+            "min_length",
+            &[
+                ("min", quote! { #min_len }),
+                ("actual", quote! { val.len() }),
+            ],
+        );
+        let issue = path.prefix(quote! {
+            ::valrs::ValidationIssue::too_small(#message, ::valrs::SizeType::String, #min_len as f64, true)
+            #custom_message
+        });
+        validations.push(quote! {
+            if val.len() < #min_len {
+                __valrs_issues.push(#issue);
+            }
+        });
+    }
+
+    if let Some(max_len) = constraints.max_length {
+        let message =
+            quote! { format!("String must be at most {} characters, got {}", #max_len, val.len()) };
+        let custom_message = generate_custom_message(
+            constraints,
+            key,
+            "max_length",
+            &[
+                ("max", quote! { #max_len }),
+                ("actual", quote! { val.len() }),
+            ],
+        );
+        let issue = path.prefix(quote! {
+            ::valrs::ValidationIssue::too_big(#message, ::valrs::SizeType::String, #max_len as f64, true)
+            #custom_message
+        });
+        validations.push(quote! {
+            if val.len() > #max_len {
+                __valrs_issues.push(#issue);
+            }
+        });
+    }
+
+    quote! {
+        #(#validations)*
+    }
+}
+
+/// Generates format and pattern checks, using the checkers in `valrs::validators`.
+fn generate_string_validations(
+    constraints: &Constraints,
+    key: &str,
+    path: &IssuePath,
+) -> TokenStream2 {
+    let mut validations = Vec::new();
+
+    if let Some(format_name) = &constraints.format {
+        let variant = STRING_FORMATS
+            .iter()
+            .find(|(name, _)| name == format_name)
+            .map(|(_, variant)| format_ident!("{}", variant))
+            .expect("format is checked when parsing attributes");
+        let format = quote! { ::valrs::validators::StringFormat::#variant };
+        let message = quote! { #format.message() };
+        let custom_message = generate_custom_message(
+            constraints,
+            key,
+            "format",
+            &[
+                ("format", quote! { #format_name }),
+                ("actual", quote! { val }),
+            ],
+        );
+        let issue = path.prefix(quote! {
+            ::valrs::ValidationIssue::invalid_format(#message, #format.validation())
+            #custom_message
+        });
+        validations.push(quote! {
+            if !#format.is_valid(val) {
+                __valrs_issues.push(#issue);
+            }
+        });
+    }
+
+    if let Some(pattern) = &constraints.pattern {
+        let message = quote! { "String does not match pattern" };
+        let custom_message = generate_custom_message(
+            constraints,
+            key,
+            "pattern",
+            &[("pattern", quote! { #pattern }), ("actual", quote! { val })],
+        );
+        let issue = path.prefix(quote! {
+            ::valrs::ValidationIssue::invalid_format(#message, "regex")
+            #custom_message
+        });
+        validations.push(quote! {
+            static PATTERN: ::valrs::validators::Pattern =
+                ::valrs::validators::Pattern::new(#pattern);
+            if !PATTERN.is_match(val) {
+                __valrs_issues.push(#issue);
+            }
+        });
+    }
+
+    quote! {
+        #(#validations)*
+    }
+}
+
+/// Generates numeric bound checks. Integer values are compared with integer
+/// bounds exactly, see `valrs::validators::BoundedNumber`.
+fn generate_number_validations(
+    constraints: &Constraints,
+    key: &str,
+    path: &IssuePath,
+) -> TokenStream2 {
+    let mut validations = Vec::new();
+
+    // Each bound fails when the value orders against it as one of `failed`,
+    // raising a `constructor` issue; custom messages refer to the limit as
+    // `{placeholder}`
+    let bounds = [
+        (
+            &constraints.minimum,
+            "minimum",
+            "min",
+            quote! { ::std::cmp::Ordering::Less },
+            "greater than or equal to",
+            quote! { too_small },
+            true,
+        ),
+        (
+            &constraints.exclusive_minimum,
+            "exclusive_minimum",
+            "min",
+            quote! { ::std::cmp::Ordering::Less | ::std::cmp::Ordering::Equal },
+            "greater than",
+            quote! { too_small },
+            false,
+        ),
+        (
+            &constraints.maximum,
+            "maximum",
+            "max",
+            quote! { ::std::cmp::Ordering::Greater },
+            "less than or equal to",
+            quote! { too_big },
+            true,
+        ),
+        (
+            &constraints.exclusive_maximum,
+            "exclusive_maximum",
+            "max",
+            quote! { ::std::cmp::Ordering::Greater | ::std::cmp::Ordering::Equal },
+            "less than",
+            quote! { too_big },
+            false,
+        ),
+    ];
+    for (limit, name, placeholder, failed, description, constructor, inclusive) in bounds {
+        if let Some(limit) = limit {
+            let literal = number_literal(limit);
+            let bound = number_bound(limit);
+            let default = format!("Number must be {} {{}}, got {{}}", description);
+            let message = quote! { format!(#default, #literal, val) };
+            let custom_message = generate_custom_message(
+                constraints,
+                key,
+                name,
+                &[
+                    (placeholder, quote! { #literal }),
+                    ("actual", quote! { val }),
+                ],
+            );
+            let issue = path.prefix(quote! {
+                ::valrs::ValidationIssue::#constructor(#message, ::valrs::SizeType::Number, #bound, #inclusive)
+                #custom_message
+            });
+            validations.push(quote! {
+                if matches!(
+                    ::valrs::validators::BoundedNumber::cmp_bound(*val, #bound),
+                    Some(#failed)
+                ) {
+                    __valrs_issues.push(#issue);
+                }
+            });
+        }
+    }
+
+    if let Some(multiple_of) = &constraints.multiple_of {
+        let literal = number_literal(multiple_of);
+        let bound = number_bound(multiple_of);
+        let message = quote! { format!("Number must be a multiple of {}, got {}", #literal, val) };
+        let custom_message = generate_custom_message(
+            constraints,
+            key,
+            "multiple_of",
+            &[
+                ("multiple_of", quote! { #literal }),
+                ("actual", quote! { val }),
+            ],
+        );
+        let issue = path.prefix(quote! {
+            ::valrs::ValidationIssue::not_multiple_of(#message, #bound)
+            #custom_message
+        });
+        validations.push(quote! {
+            if !::valrs::validators::BoundedNumber::is_multiple_of_bound(*val, #bound) {
+                __valrs_issues.push(#issue);
+            }
+        });
+    }
+
+    quote! {
+        #(#validations)*
+    }
+}
+
+/// The literal for `number`, typed so that integers beyond `i32` compile.
+fn number_literal(number: &NumberLit) -> Literal {
+    match number.integer {
+        Some(n) => match i64::try_from(n) {
+            Ok(n) => Literal::i64_suffixed(n),
+            Err(_) => Literal::u64_suffixed(n as u64),
+        },
+        None => Literal::f64_unsuffixed(number.value),
+    }
+}
+
+/// A `valrs::NumberBound` expression for `number`.
+fn number_bound(number: &NumberLit) -> TokenStream2 {
+    match number.integer {
+        Some(n) => {
+            let n = Literal::i128_suffixed(n);
+            quote! { ::valrs::NumberBound::Integer(#n) }
+        }
+        None => {
+            let n = Literal::f64_unsuffixed(number.value);
+            quote! { ::valrs::NumberBound::Float(#n) }
+        }
+    }
+}
+
+/// Generates collection size, uniqueness and per-item checks.
+///
+/// Size issues are reported at the collection; uniqueness and item issues at
+/// the offending item's index within it.
+fn generate_collection_validations(
+    constraints: &Constraints,
+    key: &str,
+    path: &IssuePath,
+) -> TokenStream2 {
+    let mut validations = Vec::new();
+
+    if let Some(min_items) = constraints.min_items {
+        let message = quote! { format!("Array must contain at least {} items, got {}", #min_items, val.len()) };
+        let custom_message = generate_custom_message(
+            constraints,
+            key,
+            "min_items",
+            &[
+                ("min", quote! { #min_items }),
+                ("actual", quote! { val.len() }),
+            ],
+        );
+        let issue = path.prefix(quote! {
+            ::valrs::ValidationIssue::too_small(#message, ::valrs::SizeType::Array, #min_items as f64, true)
+            #custom_message
+        });
+        validations.push(quote! {
+            if val.len() < #min_items {
+                __valrs_issues.push(#issue);
+            }
+        });
+    }
+
+    if let Some(max_items) = constraints.max_items {
+        let message = quote! { format!("Array must contain at most {} items, got {}", #max_items, val.len()) };
+        let custom_message = generate_custom_message(
+            constraints,
+            key,
+            "max_items",
+            &[
+                ("max", quote! { #max_items }),
+                ("actual", quote! { val.len() }),
+            ],
+        );
+        let issue = path.prefix(quote! {
+            ::valrs::ValidationIssue::too_big(#message, ::valrs::SizeType::Array, #max_items as f64, true)
+            #custom_message
+        });
+        validations.push(quote! {
+            if val.len() > #max_items {
+                __valrs_issues.push(#issue);
+            }
+        });
+    }
+
+    if constraints.unique_items {
+        let message = quote! { format!("Duplicate item, same as index {}", first) };
+        let custom_message = generate_custom_message(
+            constraints,
+            key,
+            "unique_items",
+            &[("index", quote! { first })],
+        );
+        let issue = path.prefix(quote! {
+            ::valrs::ValidationIssue::not_unique(#message, first)
+                .at(vec![::valrs::PathSegment::Index(index)])
+            #custom_message
+        });
+        validations.push(quote! {
+            for (index, first) in ::valrs::validators::duplicate_items(val.iter()) {
+                __valrs_issues.push(#issue);
+            }
+        });
+    }
+
+    // Item checks are generated relative to the item, collected, then moved
+    // under the item's index
+    if let Some(items) = &constraints.items {
+        let item_checks = generate_checks(items, key, &IssuePath::Root);
+        let issue = path.prefix(quote! {
+            issue.with_path_prefix(::valrs::PathSegment::Index(index))
+        });
+        validations.push(quote! {
+            for (index, val) in val.iter().enumerate() {
+                let item_issues = {
+                    let mut __valrs_issues: Vec<::valrs::ValidationIssue> = Vec::new();
+                    #item_checks
+                    __valrs_issues
+                };
+                for issue in item_issues {
+                    __valrs_issues.push(#issue);
+                }
+            }
+        });
+    }
+
+    quote! {
+        #(#validations)*
+    }
+}
+
+/// Generates a `.with_message(...)` call setting the field's custom message for
+/// `constraint` on an issue, if it has one.
+///
+/// Custom messages are set once the issue is created with its default
+/// message, so that error maps do not replace them.
+fn generate_custom_message(
+    constraints: &Constraints,
+    key: &str,
+    constraint: &str,
+    values: &[(&str, TokenStream2)],
+) -> TokenStream2 {
+    match constraints.messages.get(constraint) {
+        Some(message) => {
+            let message = generate_message(key, message, values);
+            quote! { .with_message(#message) }
+        }
+        None => quote! {},
+    }
+}
+
+/// Generates a `format!` call filling in `message`'s placeholders: `{field}`
+/// with `key`, and the others from `values`.
+pub(crate) fn generate_message(
+    key: &str,
+    message: &Message,
+    values: &[(&str, TokenStream2)],
+) -> TokenStream2 {
+    let format = &message.format;
+    let args = message.placeholders.iter().map(|name| {
+        let ident = format_ident!("{}", name);
+        let value = match values.iter().find(|(value_name, _)| value_name == name) {
+            Some((_, value)) => value.clone(),
+            None => quote! { #key },
+        };
+        quote! { #ident = #value }
+    });
+
+    quote! { format!(#format #(, #args)*) }
+}
+
+/// Generates calls to the field's `validate_with` functions, in declaration
+/// order. Their issues are relative to the field, so they are moved to `path`.
+fn generate_custom_validations(constraints: &Constraints, path: &IssuePath) -> TokenStream2 {
+    let issue = path.prefix(quote! { issue });
+    let validations = constraints.validate_with.iter().map(|validator| {
+        let call = generate_custom_call(validator, quote! { val });
+        quote! {
+            for issue in ::valrs::IntoIssues::into_issues(#call) {
+                __valrs_issues.push(#issue);
+            }
+        }
+    });
+
+    quote! {
+        #(#validations)*
+    }
+}
+
+/// Generates a call of a custom validator or check function on `value`,
+/// passing `__valrs_ctx` as well for `validate_with_context` and
+/// `check_with_context`.
+pub(crate) fn generate_custom_call(
+    validator: &CustomValidator,
+    value: TokenStream2,
+) -> TokenStream2 {
+    let path = &validator.path;
+    if validator.with_context {
+        quote! { #path(#value, __valrs_ctx) }
+    } else {
+        quote! { #path(#value) }
+    }
+}
+
+/// Generates statements adding the keywords of `constraints` to `schema`, a
+/// place expression. Item constraints are added to its `items` schema.
+///
+/// The statements expect `target` to be in scope.
+pub(crate) fn generate_constraint_schema(
+    constraints: &Constraints,
+    schema: TokenStream2,
+) -> TokenStream2 {
+    let mut keywords = Vec::new();
+
+    let mut insert = |key: &str, value: TokenStream2| {
+        keywords.push(quote! {
+            m.insert(#key.to_string(), #value);
+        });
+    };
+
+    if let Some(min) = constraints.min_length {
+        insert("minLength", quote! { Value::Number(#min.into()) });
+    }
+    if let Some(max) = constraints.max_length {
+        insert("maxLength", quote! { Value::Number(#max.into()) });
+    }
+    if let Some(format) = &constraints.format {
+        insert("format", quote! { Value::String(#format.to_string()) });
+    }
+    if let Some(pattern) = &constraints.pattern {
+        insert("pattern", quote! { Value::String(#pattern.to_string()) });
+    }
+    if let Some(multiple_of) = &constraints.multiple_of {
+        let literal = number_literal(multiple_of);
+        insert("multipleOf", quote! { ::serde_json::json!(#literal) });
+    }
+    if let Some(min) = constraints.min_items {
+        insert("minItems", quote! { Value::Number(#min.into()) });
+    }
+    if let Some(max) = constraints.max_items {
+        insert("maxItems", quote! { Value::Number(#max.into()) });
+    }
+    if constraints.unique_items {
+        insert("uniqueItems", quote! { Value::Bool(true) });
+    }
+    if let Some(items) = &constraints.items {
+        let item_schema = generate_constraint_schema(items, quote! { *items });
+        keywords.push(quote! {
+            if let Some(items) = m.get_mut("items") {
+                #item_schema
+            }
+        });
+    }
+
+    let lower = generate_bound_schema(
+        &constraints.minimum,
+        &constraints.exclusive_minimum,
+        "minimum",
+        "exclusiveMinimum",
+        |exclusive, inclusive| exclusive >= inclusive,
+    );
+    let upper = generate_bound_schema(
+        &constraints.maximum,
+        &constraints.exclusive_maximum,
+        "maximum",
+        "exclusiveMaximum",
+        |exclusive, inclusive| exclusive <= inclusive,
+    );
+
+    if keywords.is_empty() && lower.is_empty() && upper.is_empty() {
+        return quote! {};
+    }
+
+    quote! {
+        if let Value::Object(ref mut m) = #schema {
+            #(#keywords)*
+            #lower
+            #upper
+        }
+    }
+}
+
+/// Generates statements for one side of a numeric range.
+///
+/// JSON Schema has separate numeric `minimum` and `exclusiveMinimum` keywords
+/// (likewise for the maximum). OpenAPI 3.0 instead has a boolean
+/// `exclusiveMinimum` modifying `minimum`, so only the stricter of the two
+/// bounds can be expressed; `exclusive_is_stricter` decides which.
+fn generate_bound_schema(
+    inclusive: &Option<NumberLit>,
+    exclusive: &Option<NumberLit>,
+    inclusive_key: &str,
+    exclusive_key: &str,
+    exclusive_is_stricter: impl Fn(f64, f64) -> bool,
+) -> TokenStream2 {
+    let inclusive_insert = inclusive.as_ref().map(|limit| {
+        let literal = number_literal(limit);
+        quote! { m.insert(#inclusive_key.to_string(), ::serde_json::json!(#literal)); }
+    });
+
+    let Some(exclusive) = exclusive else {
+        return quote! { #inclusive_insert };
+    };
+    let literal = number_literal(exclusive);
+
+    let openapi = match inclusive {
+        Some(inclusive) if !exclusive_is_stricter(exclusive.value, inclusive.value) => {
+            quote! { #inclusive_insert }
+        }
+        _ => quote! {
+            m.insert(#inclusive_key.to_string(), ::serde_json::json!(#literal));
+            m.insert(#exclusive_key.to_string(), Value::Bool(true));
+        },
+    };
+
+    quote! {
+        match target {
+            ::valrs::JsonSchemaTarget::OpenApi30 => {
+                #openapi
+            }
+            _ => {
+                #inclusive_insert
+                m.insert(#exclusive_key.to_string(), ::serde_json::json!(#literal));
+            }
+        }
+    }
+}
@@ -0,0 +1,736 @@
+//! Code generation for enums.
+//!
+//! Enums follow serde's wire formats so that validated input matches what
+//! `serde_json` would deserialize:
+//!
+//! - Externally tagged (default): `"Unit"` or `{ "Variant": content }`
+//! - Internally tagged (`tag = "type"`): `{ "type": "Variant", ...fields }`
+//! - Adjacently tagged (`tag = "t", content = "c"`): `{ "t": "Variant", "c": content }`
+//! - Untagged (`untagged`): the content of the first variant that validates
+//!
+//! Unit-only, externally tagged enums are represented as a set of string
+//! literals.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{DataEnum, DeriveInput, Error, Fields, Ident, Type};
+
+use crate::attrs::{ContainerAttrs, UnknownFields, VariantAttrs};
+use crate::metadata::generate_metadata_schema;
+use crate::{
+    DerivedTrait, ParsedField, SchemaKind, bounded_generics, generate_named_fields_validation,
+    generate_object_schema, parse_field, unknown,
+};
+
+/// The JSON representation of an enum.
+enum Tagging {
+    External,
+    Internal { tag: String },
+    Adjacent { tag: String, content: String },
+    Untagged,
+}
+
+/// The shape of a variant's data.
+enum VariantShape {
+    Unit,
+    /// A tuple variant with exactly one field.
+    Newtype(Type),
+    /// A tuple variant with several fields, represented as a JSON array.
+    Tuple(Vec<Type>),
+    Struct(Vec<ParsedField>),
+}
+
+/// Parsed variant information.
+struct ParsedVariant {
+    ident: Ident,
+    /// The string this variant is represented by in JSON.
+    name: String,
+    shape: VariantShape,
+}
+
+/// Parsed enum information.
+struct ParsedEnum {
+    tagging: Tagging,
+    variants: Vec<ParsedVariant>,
+}
+
+impl ParsedEnum {
+    /// Whether the enum is represented as a plain set of strings.
+    fn is_string_set(&self) -> bool {
+        matches!(self.tagging, Tagging::External)
+            && self
+                .variants
+                .iter()
+                .all(|v| matches!(v.shape, VariantShape::Unit))
+    }
+
+    /// Generates an issue for `other`, a name matching none of the variants
+    /// matching `filter`. Names of tagged enums are discriminators, and the
+    /// others enum values.
+    fn unknown_variant_issue(
+        &self,
+        filter: impl Fn(&ParsedVariant) -> bool,
+        discriminator: bool,
+    ) -> TokenStream2 {
+        let names: Vec<&str> = self
+            .variants
+            .iter()
+            .filter(|v| filter(v))
+            .map(|v| v.name.as_str())
+            .collect();
+        let expected = names
+            .iter()
+            .map(|name| format!("{:?}", name))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        if discriminator {
+            quote! {
+                ::valrs::ValidationIssue::invalid_union_discriminator(
+                    format!("Expected one of {}, got {:?}", #expected, other),
+                    &[#(#names),*],
+                )
+            }
+        } else {
+            quote! {
+                ::valrs::ValidationIssue::invalid_enum_value(
+                    format!("Expected one of {}, got {:?}", #expected, other),
+                    &[#(#names),*],
+                    other,
+                )
+            }
+        }
+    }
+}
+
+/// Parses an enum and its variants, applying renames and checking the tagging mode.
+fn parse_enum(input: &DeriveInput, data: &DataEnum, derive_name: &str) -> syn::Result<ParsedEnum> {
+    let container_attrs = ContainerAttrs::from_attributes(&input.attrs)?;
+
+    if data.variants.is_empty() {
+        return Err(Error::new_spanned(
+            &input.ident,
+            format!(
+                "{} derive does not support enums with no variants",
+                derive_name
+            ),
+        ));
+    }
+
+    let tagging = match (
+        container_attrs.tag.clone(),
+        container_attrs.content.clone(),
+        container_attrs.untagged,
+    ) {
+        (None, None, false) => Tagging::External,
+        (Some(tag), None, false) => Tagging::Internal { tag },
+        (Some(tag), Some(content), false) => Tagging::Adjacent { tag, content },
+        (None, None, true) => Tagging::Untagged,
+        (None, Some(_), _) => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "#[schema(content = \"...\")] requires #[schema(tag = \"...\")]",
+            ));
+        }
+        (_, _, true) => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "#[schema(untagged)] cannot be combined with tag or content",
+            ));
+        }
+    };
+
+    let variants = data
+        .variants
+        .iter()
+        .map(|variant| {
+            let attrs = VariantAttrs::from_attributes(&variant.attrs, container_attrs.serde)?;
+            let name = match (attrs.rename, container_attrs.rename_all) {
+                (Some(rename), _) => rename,
+                (None, Some(rule)) => rule.apply_to_variant(&variant.ident.to_string()),
+                (None, None) => variant.ident.to_string(),
+            };
+
+            let shape = match &variant.fields {
+                Fields::Unit => VariantShape::Unit,
+                Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => {
+                    VariantShape::Newtype(unnamed.unnamed[0].ty.clone())
+                }
+                Fields::Unnamed(unnamed) => {
+                    if matches!(tagging, Tagging::Internal { .. }) {
+                        return Err(Error::new_spanned(
+                            variant,
+                            "internally tagged enums do not support tuple variants",
+                        ));
+                    }
+                    VariantShape::Tuple(unnamed.unnamed.iter().map(|f| f.ty.clone()).collect())
+                }
+                Fields::Named(named) => VariantShape::Struct(
+                    named
+                        .named
+                        .iter()
+                        .map(|field| parse_field(field, attrs.rename_all, &container_attrs))
+                        .collect::<syn::Result<_>>()?,
+                ),
+            };
+
+            Ok(ParsedVariant {
+                ident: variant.ident.clone(),
+                name,
+                shape,
+            })
+        })
+        .collect::<syn::Result<_>>()?;
+
+    Ok(ParsedEnum { tagging, variants })
+}
+
+// =============================================================================
+// Valrs
+// =============================================================================
+
+/// Generates the `Valrs` implementation for an enum.
+pub(crate) fn derive_valrs_enum(input: &DeriveInput, data: &DataEnum) -> syn::Result<TokenStream2> {
+    let enum_name = &input.ident;
+    let parsed = parse_enum(input, data, "Valrs")?;
+
+    let body = if parsed.is_string_set() {
+        generate_string_set_validation(enum_name, &parsed)
+    } else {
+        match &parsed.tagging {
+            Tagging::External => generate_external_validation(enum_name, &parsed),
+            Tagging::Internal { tag } => generate_internal_validation(enum_name, &parsed, tag),
+            Tagging::Adjacent { tag, content } => {
+                generate_adjacent_validation(enum_name, &parsed, tag, content)
+            }
+            Tagging::Untagged => generate_untagged_validation(enum_name, &parsed),
+        }
+    };
+
+    let generics = bounded_generics(input, DerivedTrait::Valrs)?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::valrs::Valrs for #enum_name #ty_generics #where_clause {
+            type Input = #enum_name #ty_generics;
+            type Output = #enum_name #ty_generics;
+
+            fn validate(value: &::serde_json::Value) -> ::valrs::ValidationResult<Self::Output> {
+                Self::validate_with(value, &::valrs::ValidationContext::default())
+            }
+
+            fn validate_with(
+                __valrs_value: &::serde_json::Value,
+                __valrs_ctx: &::valrs::ValidationContext,
+            ) -> ::valrs::ValidationResult<Self::Output> {
+                #body
+            }
+        }
+    })
+}
+
+/// Generates an expression validating `content` as the data of a variant.
+///
+/// Unit variants expect `null` content. With internal tagging, `tag` is the
+/// tag key, which the content object also holds.
+fn generate_content_validation(
+    enum_name: &Ident,
+    variant: &ParsedVariant,
+    tag: Option<&str>,
+) -> TokenStream2 {
+    let ident = &variant.ident;
+    match &variant.shape {
+        VariantShape::Unit => quote! {
+            match content {
+                ::serde_json::Value::Null => ::valrs::ValidationResult::success(#enum_name::#ident),
+                _ => ::valrs::ValidationResult::failures(vec![
+                    ::valrs::ValidationIssue::invalid_type("Expected null", "null", content),
+                ]),
+            }
+        },
+        // The inner type does not know the tag, so it is passed as a key of
+        // the enclosing enum
+        VariantShape::Newtype(ty) if tag.is_some() => quote! {
+            <#ty as ::valrs::Valrs>::validate_with(
+                content,
+                &__valrs_ctx.clone().with_enclosing_key(#tag),
+            )
+            .map(#enum_name::#ident)
+        },
+        VariantShape::Newtype(ty) => quote! {
+            <#ty as ::valrs::Valrs>::validate_with(content, __valrs_ctx).map(#enum_name::#ident)
+        },
+        VariantShape::Tuple(types) => {
+            let bindings: Vec<_> = (0..types.len())
+                .map(|i| quote::format_ident!("field{}", i))
+                .collect();
+            quote! {
+                <(#(#types,)*) as ::valrs::Valrs>::validate_with(content, __valrs_ctx)
+                    .map(|(#(#bindings,)*)| #enum_name::#ident(#(#bindings),*))
+            }
+        }
+        VariantShape::Struct(fields) => {
+            // Unknown keys are only checked in a strict context, as for structs
+            let unknown_key_checks =
+                unknown::generate_unknown_key_checks(UnknownFields::Strip, fields, tag.as_slice());
+            let body = generate_named_fields_validation(
+                fields,
+                quote! { #enum_name::#ident },
+                unknown_key_checks,
+                quote! {},
+            );
+            quote! {
+                match content.as_object() {
+                    Some(__valrs_obj) => {
+                        let __valrs_object = content;
+                        #body
+                    }
+                    None => ::valrs::ValidationResult::failures(vec![
+                        ::valrs::ValidationIssue::invalid_type("Expected object", "object", content),
+                    ]),
+                }
+            }
+        }
+    }
+}
+
+/// Generates validation for a unit-only enum represented as strings.
+fn generate_string_set_validation(enum_name: &Ident, parsed: &ParsedEnum) -> TokenStream2 {
+    let match_arms = parsed.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let name = &variant.name;
+        quote! {
+            Some(#name) => ::valrs::ValidationResult::success(#enum_name::#ident),
+        }
+    });
+    let unknown = parsed.unknown_variant_issue(|_| true, false);
+
+    quote! {
+        match __valrs_value.as_str() {
+            #(#match_arms)*
+            Some(other) => ::valrs::ValidationResult::failures(vec![#unknown]),
+            None => ::valrs::ValidationResult::failures(vec![
+                ::valrs::ValidationIssue::invalid_type("Expected string", "string", __valrs_value),
+            ]),
+        }
+    }
+}
+
+/// Generates validation for `"Unit"` / `{ "Variant": content }`.
+fn generate_external_validation(enum_name: &Ident, parsed: &ParsedEnum) -> TokenStream2 {
+    let unit_arms = parsed
+        .variants
+        .iter()
+        .filter(|v| matches!(v.shape, VariantShape::Unit))
+        .map(|variant| {
+            let ident = &variant.ident;
+            let name = &variant.name;
+            quote! {
+                #name => ::valrs::ValidationResult::success(#enum_name::#ident),
+            }
+        });
+
+    let keyed_arms = parsed.variants.iter().map(|variant| {
+        let name = &variant.name;
+        let content_validation = generate_content_validation(enum_name, variant, None);
+        quote! {
+            #name => {
+                let result: ::valrs::ValidationResult<Self::Output> = #content_validation;
+                result.with_path_prefix(::valrs::PathSegment::Key(#name.to_string()))
+            }
+        }
+    });
+
+    let unknown_unit =
+        parsed.unknown_variant_issue(|v| matches!(v.shape, VariantShape::Unit), false);
+    let unknown_key = parsed.unknown_variant_issue(|_| true, true);
+
+    quote! {
+        // The content is nested, so enclosing keys do not apply to it
+        let __valrs_content_ctx = __valrs_ctx.without_enclosing_keys();
+        let __valrs_ctx: &::valrs::ValidationContext = &__valrs_content_ctx;
+
+        match __valrs_value {
+            ::serde_json::Value::String(name) => match name.as_str() {
+                #(#unit_arms)*
+                other => ::valrs::ValidationResult::failures(vec![#unknown_unit]),
+            },
+            ::serde_json::Value::Object(__valrs_obj) if __valrs_obj.len() == 1 => {
+                let (key, content) = __valrs_obj.iter().next().expect("guarded by len() == 1");
+                match key.as_str() {
+                    #(#keyed_arms)*
+                    other => ::valrs::ValidationResult::failures(vec![#unknown_key]),
+                }
+            }
+            _ => ::valrs::ValidationResult::failures(vec![
+                ::valrs::ValidationIssue::invalid_type(
+                    "Expected variant name or object with a single variant key",
+                    "string",
+                    __valrs_value,
+                ),
+            ]),
+        }
+    }
+}
+
+/// Generates code reading the string tag `tag` from `__valrs_obj` into
+/// `tag_value`.
+fn generate_tag_lookup(tag: &str) -> TokenStream2 {
+    quote! {
+        let __valrs_obj = match __valrs_value.as_object() {
+            Some(o) => o,
+            None => {
+                return ::valrs::ValidationResult::failures(vec![
+                    ::valrs::ValidationIssue::invalid_type("Expected object", "object", __valrs_value),
+                ]);
+            }
+        };
+
+        let tag_value = match __valrs_obj.get(#tag) {
+            Some(::serde_json::Value::String(t)) => t.as_str(),
+            Some(other) => {
+                return ::valrs::ValidationResult::failures(vec![
+                    ::valrs::ValidationIssue::invalid_type("Expected string", "string", other)
+                        .at(vec![::valrs::PathSegment::Key(#tag.to_string())]),
+                ]);
+            }
+            None => {
+                return ::valrs::ValidationResult::failures(vec![
+                    ::valrs::ValidationIssue::missing_field(format!("Missing required field '{}'", #tag))
+                        .at(vec![::valrs::PathSegment::Key(#tag.to_string())]),
+                ]);
+            }
+        };
+    }
+}
+
+/// Generates validation for `{ "tag": "Variant", ...fields }`.
+fn generate_internal_validation(enum_name: &Ident, parsed: &ParsedEnum, tag: &str) -> TokenStream2 {
+    let tag_lookup = generate_tag_lookup(tag);
+    let unknown = parsed.unknown_variant_issue(|_| true, true);
+
+    let arms = parsed.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let name = &variant.name;
+        let validation = match &variant.shape {
+            VariantShape::Unit => {
+                quote! { ::valrs::ValidationResult::success(#enum_name::#ident) }
+            }
+            // The remaining fields live next to the tag in the same object
+            VariantShape::Newtype(_) | VariantShape::Struct(_) => {
+                let content_validation = generate_content_validation(enum_name, variant, Some(tag));
+                quote! {{
+                    let content = __valrs_value;
+                    #content_validation
+                }}
+            }
+            VariantShape::Tuple(_) => unreachable!("rejected while parsing"),
+        };
+        quote! { #name => #validation, }
+    });
+
+    quote! {
+        #tag_lookup
+
+        match tag_value {
+            #(#arms)*
+            other => ::valrs::ValidationResult::failures(vec![
+                #unknown.at(vec![::valrs::PathSegment::Key(#tag.to_string())]),
+            ]),
+        }
+    }
+}
+
+/// Generates validation for `{ "tag": "Variant", "content": content }`.
+fn generate_adjacent_validation(
+    enum_name: &Ident,
+    parsed: &ParsedEnum,
+    tag: &str,
+    content_key: &str,
+) -> TokenStream2 {
+    let tag_lookup = generate_tag_lookup(tag);
+    let unknown = parsed.unknown_variant_issue(|_| true, true);
+
+    let arms = parsed.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let name = &variant.name;
+        let validation = match &variant.shape {
+            VariantShape::Unit => quote! {
+                match __valrs_obj.get(#content_key) {
+                    None | Some(::serde_json::Value::Null) => {
+                        ::valrs::ValidationResult::success(#enum_name::#ident)
+                    }
+                    Some(other) => ::valrs::ValidationResult::failures(vec![
+                        ::valrs::ValidationIssue::invalid_type("Expected null", "null", other)
+                            .at(vec![::valrs::PathSegment::Key(#content_key.to_string())]),
+                    ]),
+                }
+            },
+            _ => {
+                let content_validation = generate_content_validation(enum_name, variant, None);
+                quote! {
+                    match __valrs_obj.get(#content_key) {
+                        Some(content) => {
+                            let result: ::valrs::ValidationResult<Self::Output> = #content_validation;
+                            result.with_path_prefix(::valrs::PathSegment::Key(#content_key.to_string()))
+                        }
+                        None => ::valrs::ValidationResult::failures(vec![
+                            ::valrs::ValidationIssue::missing_field(
+                                format!("Missing required field '{}'", #content_key),
+                            )
+                            .at(vec![::valrs::PathSegment::Key(#content_key.to_string())]),
+                        ]),
+                    }
+                }
+            }
+        };
+        quote! { #name => #validation, }
+    });
+
+    quote! {
+        #tag_lookup
+
+        // The content is nested, so enclosing keys do not apply to it
+        let __valrs_content_ctx = __valrs_ctx.without_enclosing_keys();
+        let __valrs_ctx: &::valrs::ValidationContext = &__valrs_content_ctx;
+
+        match tag_value {
+            #(#arms)*
+            other => ::valrs::ValidationResult::failures(vec![
+                #unknown.at(vec![::valrs::PathSegment::Key(#tag.to_string())]),
+            ]),
+        }
+    }
+}
+
+/// Generates validation that tries each variant's content in declaration order.
+///
+/// If none matches, the issue lists the issues of every variant as its union
+/// errors.
+fn generate_untagged_validation(enum_name: &Ident, parsed: &ParsedEnum) -> TokenStream2 {
+    let attempts = parsed.variants.iter().map(|variant| {
+        let content_validation = generate_content_validation(enum_name, variant, None);
+        quote! {
+            {
+                let result: ::valrs::ValidationResult<Self::Output> = #content_validation;
+                match result {
+                    ::valrs::ValidationResult::Success(_) => return result,
+                    ::valrs::ValidationResult::Failure(__valrs_issues) => union_errors.push(__valrs_issues),
+                }
+            }
+        }
+    });
+    let message = format!("Value does not match any variant of {}", enum_name);
+
+    quote! {
+        let content = __valrs_value;
+        let mut union_errors = Vec::new();
+        #(#attempts)*
+        ::valrs::ValidationResult::failures(vec![
+            ::valrs::ValidationIssue::invalid_union(#message, union_errors),
+        ])
+    }
+}
+
+// =============================================================================
+// StandardJsonSchema
+// =============================================================================
+
+/// Generates the `StandardJsonSchema` implementation for an enum.
+pub(crate) fn derive_standard_json_schema_enum(
+    input: &DeriveInput,
+    data: &DataEnum,
+) -> syn::Result<TokenStream2> {
+    let enum_name = &input.ident;
+    let parsed = parse_enum(input, data, "StandardJsonSchema")?;
+
+    let container_attrs = ContainerAttrs::from_attributes(&input.attrs)?;
+    let metadata_schema =
+        generate_metadata_schema(&container_attrs.metadata, &format_ident!("schema"));
+
+    let methods = SchemaKind::ALL.map(|kind| {
+        let method = kind.method();
+        let schema = generate_enum_schema(&parsed, kind);
+        quote! {
+            fn #method(target: ::valrs::JsonSchemaTarget) -> ::serde_json::Value {
+                use ::serde_json::{json, Value};
+
+                let mut schema: Value = #schema;
+                #metadata_schema
+
+                // Add $schema for root schemas
+                if let Value::Object(ref mut map) = schema {
+                    let uri = target.schema_uri();
+                    if !uri.is_empty() {
+                        map.insert("$schema".to_string(), Value::String(uri.to_string()));
+                    }
+                }
+
+                schema
+            }
+        }
+    });
+
+    let generics = bounded_generics(input, DerivedTrait::StandardJsonSchema)?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::valrs::StandardJsonSchema for #enum_name #ty_generics #where_clause {
+            #(#methods)*
+        }
+    })
+}
+
+/// Generates an expression for the `kind` schema (without `$schema`) of an enum.
+fn generate_enum_schema(parsed: &ParsedEnum, kind: SchemaKind) -> TokenStream2 {
+    if parsed.is_string_set() {
+        let names = parsed.variants.iter().map(|variant| &variant.name);
+        quote! {
+            json!({
+                "type": "string",
+                "enum": [#(#names),*],
+            })
+        }
+    } else {
+        let tagged_schema = generate_tagged_schema(parsed, kind);
+        quote! {{
+            // A string literal schema; OpenAPI 3.0 has no `const`
+            let literal = |name: &str| -> Value {
+                match target {
+                    ::valrs::JsonSchemaTarget::OpenApi30 => {
+                        json!({ "type": "string", "enum": [name] })
+                    }
+                    _ => json!({ "type": "string", "const": name }),
+                }
+            };
+
+            #tagged_schema
+        }}
+    }
+}
+
+/// Generates an expression for the `kind` schema of a variant's content.
+fn generate_content_schema(variant: &ParsedVariant, kind: SchemaKind) -> TokenStream2 {
+    let method = kind.method();
+    let ty_schema = |ty: TokenStream2| {
+        quote! {{
+            let mut content_schema = <#ty as ::valrs::StandardJsonSchema>::#method(target);
+            if let Value::Object(ref mut m) = content_schema {
+                m.remove("$schema");
+            }
+            content_schema
+        }}
+    };
+
+    match &variant.shape {
+        VariantShape::Unit => quote! { json!({ "type": "null" }) },
+        VariantShape::Newtype(ty) => ty_schema(quote! { #ty }),
+        VariantShape::Tuple(types) => ty_schema(quote! { (#(#types,)*) }),
+        VariantShape::Struct(fields) => generate_object_schema(fields, kind),
+    }
+}
+
+/// Generates the `oneOf`/`anyOf` schema for a tagged or untagged enum.
+fn generate_tagged_schema(parsed: &ParsedEnum, kind: SchemaKind) -> TokenStream2 {
+    let variant_schemas: Vec<TokenStream2> = parsed
+        .variants
+        .iter()
+        .map(|variant| {
+            let name = &variant.name;
+            let content_schema = generate_content_schema(variant, kind);
+            let is_unit = matches!(variant.shape, VariantShape::Unit);
+
+            match &parsed.tagging {
+                Tagging::External if is_unit => quote! { literal(#name) },
+                Tagging::External => quote! {
+                    json!({
+                        "type": "object",
+                        "properties": { #name: (#content_schema) },
+                        "required": [#name],
+                        "additionalProperties": false,
+                    })
+                },
+                Tagging::Internal { tag } if is_unit => quote! {
+                    json!({
+                        "type": "object",
+                        "properties": { #tag: literal(#name) },
+                        "required": [#tag],
+                    })
+                },
+                Tagging::Internal { tag } => quote! {{
+                    // Merge the tag into the content's object schema when possible
+                    let mut variant_schema: Value = #content_schema;
+                    let merged = match variant_schema.as_object_mut() {
+                        Some(map) if map.get("properties").is_some_and(Value::is_object) => {
+                            if let Some(Value::Object(props)) = map.get_mut("properties") {
+                                props.insert(#tag.to_string(), literal(#name));
+                            }
+                            let mut required = vec![Value::String(#tag.to_string())];
+                            if let Some(Value::Array(existing)) = map.remove("required") {
+                                required.extend(existing);
+                            }
+                            map.insert("required".to_string(), Value::Array(required));
+                            true
+                        }
+                        _ => false,
+                    };
+                    if merged {
+                        variant_schema
+                    } else {
+                        json!({
+                            "allOf": [
+                                {
+                                    "type": "object",
+                                    "properties": { #tag: literal(#name) },
+                                    "required": [#tag],
+                                },
+                                variant_schema,
+                            ]
+                        })
+                    }
+                }},
+                Tagging::Adjacent { tag, .. } if is_unit => quote! {
+                    json!({
+                        "type": "object",
+                        "properties": { #tag: literal(#name) },
+                        "required": [#tag],
+                    })
+                },
+                Tagging::Adjacent { tag, content } => quote! {
+                    json!({
+                        "type": "object",
+                        "properties": {
+                            #tag: literal(#name),
+                            #content: (#content_schema),
+                        },
+                        "required": [#tag, #content],
+                    })
+                },
+                Tagging::Untagged => content_schema,
+            }
+        })
+        .collect();
+
+    match &parsed.tagging {
+        Tagging::Untagged => quote! {
+            json!({ "anyOf": Value::Array(vec![#(#variant_schemas),*]) })
+        },
+        Tagging::External => quote! {
+            json!({ "oneOf": Value::Array(vec![#(#variant_schemas),*]) })
+        },
+        Tagging::Internal { tag } | Tagging::Adjacent { tag, .. } => quote! {{
+            let mut schema = ::serde_json::Map::new();
+            schema.insert(
+                "oneOf".to_string(),
+                Value::Array(vec![#(#variant_schemas),*]),
+            );
+            if target == ::valrs::JsonSchemaTarget::OpenApi30 {
+                schema.insert(
+                    "discriminator".to_string(),
+                    json!({ "propertyName": #tag }),
+                );
+            }
+            Value::Object(schema)
+        }},
+    }
+}
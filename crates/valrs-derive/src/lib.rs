@@ -2,103 +2,247 @@
 //!
 //! Provides `#[derive(Valrs)]` and `#[derive(StandardJsonSchema)]` for automatic implementation.
 
+mod attrs;
+mod case;
+mod checks;
+mod constraints;
+mod enums;
+mod metadata;
+mod tuple_struct;
+mod unknown;
+
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
-use quote::quote;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
 use syn::{
-    Attribute, Data, DeriveInput, Error, Expr, ExprLit, Field, Fields, Ident, Lit, Type,
-    parse_macro_input,
+    Data, DeriveInput, Error, Field, Fields, Generics, Ident, Type, parse_macro_input, parse_quote,
 };
 
-/// Field-level schema attributes.
-#[derive(Default)]
-struct FieldAttrs {
-    /// Whether the field is optional (can be missing from input).
-    optional: bool,
-    /// Rename the JSON key for this field.
-    rename: Option<String>,
-    /// Minimum string length validation.
-    min_length: Option<usize>,
-    /// Maximum string length validation.
-    max_length: Option<usize>,
+use attrs::{ContainerAttrs, FieldAttrs, FieldDefault, UnknownFields};
+use case::RenameRule;
+
+/// Parsed field information.
+struct ParsedField {
+    ident: Ident,
+    ty: Type,
+    attrs: FieldAttrs,
+    /// The JSON key, after `rename` and `rename_all`.
+    key: String,
 }
 
-impl FieldAttrs {
-    fn from_attributes(attrs: &[Attribute]) -> syn::Result<Self> {
-        let mut field_attrs = FieldAttrs::default();
+impl ParsedField {
+    fn json_key(&self) -> String {
+        self.key.clone()
+    }
 
-        for attr in attrs {
-            if !attr.path().is_ident("schema") {
-                continue;
-            }
+    /// The local the field's validated value is bound to. It is prefixed so
+    /// that a field cannot shadow the other bindings of the generated code,
+    /// such as `__valrs_ctx`.
+    fn local(&self) -> Ident {
+        format_ident!("__valrs_field_{}", self.ident.unraw())
+    }
 
-            attr.parse_nested_meta(|meta| {
-                if meta.path.is_ident("optional") {
-                    field_attrs.optional = true;
-                    Ok(())
-                } else if meta.path.is_ident("rename") {
-                    let value: Expr = meta.value()?.parse()?;
-                    if let Expr::Lit(ExprLit {
-                        lit: Lit::Str(lit_str),
-                        ..
-                    }) = value
-                    {
-                        field_attrs.rename = Some(lit_str.value());
-                        Ok(())
-                    } else {
-                        Err(meta.error("expected string literal for rename"))
-                    }
-                } else if meta.path.is_ident("min_length") {
-                    let value: Expr = meta.value()?.parse()?;
-                    if let Expr::Lit(ExprLit {
-                        lit: Lit::Int(lit_int),
-                        ..
-                    }) = value
-                    {
-                        field_attrs.min_length = Some(lit_int.base10_parse()?);
-                        Ok(())
-                    } else {
-                        Err(meta.error("expected integer literal for min_length"))
-                    }
-                } else if meta.path.is_ident("max_length") {
-                    let value: Expr = meta.value()?.parse()?;
-                    if let Expr::Lit(ExprLit {
-                        lit: Lit::Int(lit_int),
-                        ..
-                    }) = value
-                    {
-                        field_attrs.max_length = Some(lit_int.base10_parse()?);
-                        Ok(())
-                    } else {
-                        Err(meta.error("expected integer literal for max_length"))
-                    }
-                } else {
-                    Err(meta.error("unknown schema attribute"))
-                }
-            })?;
+    /// Whether the field is required in the `kind` schema. A field with a
+    /// default may be missing from input, but always has a value in output.
+    fn is_required(&self, kind: SchemaKind) -> bool {
+        !self.attrs.optional
+            && (kind == SchemaKind::Output || self.attrs.default.is_none())
+            && !self.attrs.skip
+            && !self.attrs.flatten
+    }
+
+    /// Generates a statement shadowing `__valrs_ctx` with the context the field
+    /// is validated with, if it differs: fields with `coerce` are validated in
+    /// coercion mode.
+    fn context(&self) -> Option<TokenStream2> {
+        self.attrs.coerce.then(|| {
+            quote! { let __valrs_ctx = &__valrs_ctx.clone().with_coerce(true); }
+        })
+    }
+
+    /// The kind of schema used for the field in the enclosing `kind` schema:
+    /// fields with `coerce` accept the coerced input.
+    fn schema_kind(&self, kind: SchemaKind) -> SchemaKind {
+        if self.attrs.coerce && kind == SchemaKind::Input {
+            SchemaKind::CoercedInput
+        } else {
+            kind
+        }
+    }
+}
+
+/// Which of a type's schemas is generated.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SchemaKind {
+    /// `json_schema_input`: what `validate` accepts.
+    Input,
+    /// `json_schema_output`: the validated value.
+    Output,
+    /// `json_schema_coerced_input`: what `validate_with` accepts in coercion
+    /// mode.
+    CoercedInput,
+}
+
+impl SchemaKind {
+    /// All kinds, in the order the trait methods are generated.
+    const ALL: [SchemaKind; 3] = [
+        SchemaKind::Input,
+        SchemaKind::Output,
+        SchemaKind::CoercedInput,
+    ];
+
+    /// The `StandardJsonSchema` method returning this schema.
+    fn method(self) -> Ident {
+        match self {
+            SchemaKind::Input => format_ident!("json_schema_input"),
+            SchemaKind::Output => format_ident!("json_schema_output"),
+            SchemaKind::CoercedInput => format_ident!("json_schema_coerced_input"),
         }
+    }
 
-        Ok(field_attrs)
+    /// The `StandardJsonSchema` method returning this schema's properties.
+    fn properties_method(self) -> Ident {
+        match self {
+            SchemaKind::Input => format_ident!("json_schema_input_properties"),
+            SchemaKind::Output => format_ident!("json_schema_output_properties"),
+            SchemaKind::CoercedInput => format_ident!("json_schema_coerced_input_properties"),
+        }
     }
 }
 
-/// Parsed field information.
-struct ParsedField {
-    ident: Ident,
-    ty: Type,
-    attrs: FieldAttrs,
+/// A trait implemented by the derive macros.
+#[derive(Clone, Copy)]
+enum DerivedTrait {
+    Valrs,
+    StandardJsonSchema,
 }
 
-impl ParsedField {
-    fn json_key(&self) -> String {
-        self.attrs
-            .rename
-            .clone()
-            .unwrap_or_else(|| self.ident.to_string())
+/// Returns `input`'s generics with the bounds for a derived impl of `derived`.
+///
+/// Each type parameter `T` gets `T: Trait<Output = T>`, since derived types are
+/// built from the validated output of each field. `#[schema(bound = "...")]`
+/// replaces these automatic bounds with the given where-predicates, and
+/// `#[schema(bound(valrs = "...", schema = "..."))]` does so for each trait
+/// separately.
+fn bounded_generics(input: &DeriveInput, derived: DerivedTrait) -> syn::Result<Generics> {
+    let container_attrs = ContainerAttrs::from_attributes(&input.attrs)?;
+    let mut generics = input.generics.clone();
+
+    let (bound, trait_path) = match derived {
+        DerivedTrait::Valrs => (container_attrs.valrs_bound, quote! { ::valrs::Valrs }),
+        DerivedTrait::StandardJsonSchema => (
+            container_attrs.schema_bound,
+            quote! { ::valrs::StandardJsonSchema },
+        ),
+    };
+    let predicates: Vec<syn::WherePredicate> = match bound {
+        Some(bound) => bound,
+        None => generics
+            .type_params()
+            .map(|param| {
+                let ident = &param.ident;
+                parse_quote!(#ident: #trait_path<Output = #ident>)
+            })
+            .collect(),
+    };
+    generics.make_where_clause().predicates.extend(predicates);
+
+    Ok(generics)
+}
+
+/// Checks that the container attributes apply to the kind of type derived.
+///
+/// Only `#[schema(...)]` is checked: serde accepts some of these on structs.
+fn check_container_attrs(input: &DeriveInput) -> syn::Result<()> {
+    let container_attrs = ContainerAttrs::from_schema_attributes(&input.attrs)?;
+    let enum_only = [
+        ("tag", container_attrs.tag.is_some()),
+        ("content", container_attrs.content.is_some()),
+        ("untagged", container_attrs.untagged),
+    ];
+    let named_struct_only = [
+        ("check", !container_attrs.checks.is_empty()),
+        ("require_one_of", !container_attrs.require_one_of.is_empty()),
+        (
+            "require_together",
+            !container_attrs.require_together.is_empty(),
+        ),
+        ("missing_message", container_attrs.missing_message.is_some()),
+        (
+            "deny_unknown_fields",
+            container_attrs.unknown_fields == UnknownFields::Deny,
+        ),
+        (
+            "passthrough",
+            container_attrs.unknown_fields == UnknownFields::Passthrough,
+        ),
+    ];
+
+    let is_struct = matches!(input.data, Data::Struct(_));
+    let is_named_struct = matches!(
+        &input.data,
+        Data::Struct(data) if matches!(data.fields, Fields::Named(_))
+    );
+
+    if is_struct && let Some((name, _)) = enum_only.iter().find(|(_, present)| *present) {
+        return Err(Error::new_spanned(
+            &input.ident,
+            format!("#[schema({})] is only supported on enums", name),
+        ));
+    }
+    if !is_named_struct
+        && let Some((name, _)) = named_struct_only.iter().find(|(_, present)| *present)
+    {
+        return Err(Error::new_spanned(
+            &input.ident,
+            format!(
+                "#[schema({})] is only supported on structs with named fields",
+                name
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Where issues raised by a field's own constraints are reported, relative to
+/// the value being validated.
+enum IssuePath {
+    /// The value itself, for transparent newtypes.
+    Root,
+    /// An object property, at the key an expression evaluates to: the key
+    /// the value was found under, which may be an alias.
+    Key(TokenStream2),
+    /// An array position, for tuple structs.
+    Index(usize),
+}
+
+impl IssuePath {
+    /// Generates an expression moving `issue`, relative to the value, to this path.
+    fn prefix(&self, issue: TokenStream2) -> TokenStream2 {
+        match self {
+            IssuePath::Root => issue,
+            IssuePath::Key(key) => quote! {
+                #issue.with_path_prefix(::valrs::PathSegment::Key(#key.to_string()))
+            },
+            IssuePath::Index(index) => quote! {
+                #issue.with_path_prefix(::valrs::PathSegment::Index(#index))
+            },
+        }
     }
 }
 
-/// Derives the `Valrs` trait for a struct.
+/// Derives the `Valrs` trait for a struct or an enum.
+///
+/// Tuple structs with a single field are transparent: they validate exactly
+/// like the inner type, plus any field constraints. Tuple structs with several
+/// fields are validated as fixed-length arrays.
+///
+/// Unit-only enums are validated as one of a fixed set of strings, one per
+/// variant. Enums with data follow serde's representations: externally tagged
+/// by default, or internally tagged, adjacently tagged and untagged via
+/// container attributes.
 ///
 /// # Example
 ///
@@ -115,11 +259,103 @@ impl ParsedField {
 ///
 /// # Attributes
 ///
-/// - `#[schema(optional)]` - Field can be missing from input (for `Option<T>`)
+/// - `#[schema(optional)]` - Field can be missing from input (for `Option<T>`);
+///   missing and `null` values both become `None`
+/// - `#[schema(optional, non_nullable)]` - `null` is rejected like any other
+///   invalid value; only a missing field becomes `None`
+/// - `valrs::Maybe<T>` fields are always optional and keep missing (`Absent`),
+///   `null` (`Null`) and present (`Value`) apart
 /// - `#[schema(rename = "fieldName")]` - Use different JSON key
+/// - `#[schema(coerce)]` - Validate the field in coercion mode (see
+///   `ValidationContext::with_coerce`), so that `"42"` is accepted for a number;
+///   the input schema accepts the converted types as well
+/// - `#[schema(default)]` - Use `Default::default()` when the field is missing
+/// - `#[schema(default = "path::to::fn")]` - Call a function when the field is
+///   missing
+/// - `#[schema(default = 8080)]` - Use a number or boolean literal when the
+///   field is missing
+/// - `#[schema(flatten)]` - Read the fields of a nested struct from the same
+///   object; its issues are reported at the flattened keys
 /// - `#[schema(min_length = N)]` - String minimum length validation
 /// - `#[schema(max_length = N)]` - String maximum length validation
-#[proc_macro_derive(Valrs, attributes(schema))]
+/// - `#[schema(format = "...")]` - String format: `email`, `uri`, `uuid`,
+///   `date-time`, `date`, `ipv4`, `ipv6` or `hostname`
+/// - `#[schema(pattern = "...")]` - String must match the regular expression
+/// - `#[schema(minimum = N)]`, `#[schema(maximum = N)]` - Inclusive number bounds
+/// - `#[schema(exclusive_minimum = N)]`, `#[schema(exclusive_maximum = N)]` -
+///   Exclusive number bounds
+/// - `#[schema(multiple_of = N)]` - Number must be a multiple of `N`
+/// - `#[schema(min_items = N)]`, `#[schema(max_items = N)]` - Collection size
+///   bounds, reported at the collection
+/// - `#[schema(unique_items)]` - Collection items must all be different
+///   (compared with `PartialEq`); each repeated item is reported at its index
+/// - `#[schema(items(...))]` - Constraints checked on each item of a
+///   collection, such as `items(max_length = 32)`; issues are reported at the
+///   item's index
+/// - `#[schema(min_length(N, message = "..."))]` - Any of the constraints above
+///   with a custom issue message (`unique_items(message = "...")` for the
+///   flag). It may use `{field}` (the field's key), `{actual}` (the length,
+///   string or number checked) and the constraint's value as `{min}`, `{max}`,
+///   `{format}`, `{pattern}` or `{multiple_of}`; `unique_items` has `{index}`,
+///   the index of the first equal item. Error maps do not replace custom
+///   messages
+/// - `#[schema(missing_message = "...")]` - Custom message when the field is
+///   missing, which may use `{field}`
+/// - `#[schema(validate_with = "path::to::check")]` - Call a custom validator,
+///   a `fn(&T) -> Result<(), String>` or `fn(&T) -> Vec<ValidationIssue>`, on
+///   the validated value (the inner `T` of an optional field). Its issues are
+///   prefixed with the field's key. May be repeated; validators run in order
+/// - `#[schema(validate_with_context = "path::to::check")]` - Like
+///   `validate_with`, for a validator that also takes the
+///   `&ValidationContext`, such as `fn(&T, &ValidationContext) -> Result<(), String>`
+///
+/// On structs:
+///
+/// - `#[schema(rename_all = "...")]` - Case conversion for all field names
+/// - `#[schema(alias = "name")]` - Also accept `name` as the key of a field;
+///   may be repeated. Giving more than one of a field's keys is an issue
+/// - `#[schema(check = "path::to::check")]` - Call a `fn(&Self)` returning
+///   `Result<(), String>` or `Vec<ValidationIssue>` on the constructed struct,
+///   once all fields are valid. Issues keep their paths. May be repeated
+/// - `#[schema(check_with_context = "path::to::check")]` - Like `check`, for a
+///   function that also takes the `&ValidationContext`
+/// - `#[schema(require_one_of("a", "b", ...))]` - At least one of the named
+///   fields must be present. As with JSON Schema's `required`, a field set to
+///   null is present; add `non_nullable` to the field to reject null
+/// - `#[schema(require_together("a", "b", ...))]` - If one of the named fields
+///   is present, all of them must be
+/// - `#[schema(missing_message = "...")]` - Custom message for every missing
+///   field without its own, which may use `{field}`
+/// - `#[schema(implicit_optional)]` - `Option<T>` fields without a default are
+///   optional without `#[schema(optional)]` (also on enums, for variant fields)
+/// - `#[schema(deny_unknown_fields)]` - Report an issue at each key that is not
+///   a field (or alias); by default unknown keys are ignored, unless the
+///   `ValidationContext` is strict
+/// - `#[schema(passthrough)]` - Collect unknown keys into the field marked
+///   `#[schema(extra)]`, typically a `serde_json::Map<String, Value>`
+///
+/// Serde attributes that affect the wire format are honored as well: `rename`,
+/// `rename_all`, `alias`, `default`, `skip`, `skip_deserializing`, `flatten`,
+/// `deny_unknown_fields`, `tag`, `content` and `untagged`. A `#[schema(...)]` attribute overrides the
+/// serde one, and `#[schema(ignore_serde)]` on the container disables this.
+///
+/// On generic types, each type parameter `T` is bounded by
+/// `T: Valrs<Output = T>`. `#[schema(bound = "...")]` on the container replaces
+/// these bounds with the given where-predicates (an empty string adds none),
+/// for this impl and the `StandardJsonSchema` one.
+/// `#[schema(bound(valrs = "...", schema = "..."))]` gives the bounds of each
+/// impl separately; an impl whose bound is not given keeps the automatic ones.
+///
+/// On enums:
+///
+/// - `#[schema(rename_all = "...")]` - Case conversion for all variant names
+///   (`lowercase`, `UPPERCASE`, `PascalCase`, `camelCase`, `snake_case`,
+///   `SCREAMING_SNAKE_CASE`, `kebab-case`, `SCREAMING-KEBAB-CASE`)
+/// - `#[schema(rename = "name")]` - Use a different string for a variant
+/// - `#[schema(tag = "type")]` - Internally tagged: `{ "type": "Variant", ...fields }`
+/// - `#[schema(tag = "t", content = "c")]` - Adjacently tagged: `{ "t": "Variant", "c": data }`
+/// - `#[schema(untagged)]` - Untagged: the first variant whose data validates
+#[proc_macro_derive(Valrs, attributes(schema, serde))]
 pub fn derive_valrs(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
@@ -132,15 +368,14 @@ pub fn derive_valrs(input: TokenStream) -> TokenStream {
 fn derive_valrs_impl(input: DeriveInput) -> syn::Result<TokenStream2> {
     let struct_name = &input.ident;
 
-    // Only support named structs
+    check_container_attrs(&input)?;
+
+    // Tuple structs are handled separately; unit structs are not supported
     let fields = match &input.data {
         Data::Struct(data) => match &data.fields {
             Fields::Named(named) => &named.named,
-            Fields::Unnamed(_) => {
-                return Err(Error::new_spanned(
-                    struct_name,
-                    "Valrs derive does not support tuple structs",
-                ));
+            Fields::Unnamed(unnamed) => {
+                return tuple_struct::derive_valrs_tuple_struct(&input, unnamed);
             }
             Fields::Unit => {
                 return Err(Error::new_spanned(
@@ -149,12 +384,7 @@ fn derive_valrs_impl(input: DeriveInput) -> syn::Result<TokenStream2> {
                 ));
             }
         },
-        Data::Enum(_) => {
-            return Err(Error::new_spanned(
-                struct_name,
-                "Valrs derive does not support enums yet",
-            ));
-        }
+        Data::Enum(data) => return enums::derive_valrs_enum(&input, data),
         Data::Union(_) => {
             return Err(Error::new_spanned(
                 struct_name,
@@ -163,186 +393,444 @@ fn derive_valrs_impl(input: DeriveInput) -> syn::Result<TokenStream2> {
         }
     };
 
-    // Parse all fields
-    let parsed_fields: Vec<ParsedField> =
-        fields.iter().map(parse_field).collect::<syn::Result<_>>()?;
+    let parsed_fields = parse_struct_fields(&input, fields)?;
+    let container_attrs = ContainerAttrs::from_attributes(&input.attrs)?;
+    let presence_rules = checks::parse_presence_rules(&container_attrs, &parsed_fields)?;
 
-    // Generate validation code for each field
-    let field_validations = parsed_fields
-        .iter()
-        .map(generate_field_validation)
-        .collect::<Vec<_>>();
+    let presence_checks = checks::generate_presence_checks(&presence_rules);
+    let unknown_key_checks =
+        unknown::generate_unknown_key_checks(container_attrs.unknown_fields, &parsed_fields, &[]);
+    let body = generate_named_fields_validation(
+        &parsed_fields,
+        quote! { #struct_name },
+        quote! {
+            #presence_checks
+            #unknown_key_checks
+        },
+        checks::generate_struct_checks(&container_attrs),
+    );
 
-    // Generate struct construction
-    let field_names: Vec<_> = parsed_fields.iter().map(|f| &f.ident).collect();
+    let generics = bounded_generics(&input, DerivedTrait::Valrs)?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     let expanded = quote! {
-        impl ::valrs::Valrs for #struct_name {
-            type Input = #struct_name;
-            type Output = #struct_name;
+        impl #impl_generics ::valrs::Valrs for #struct_name #ty_generics #where_clause {
+            type Input = #struct_name #ty_generics;
+            type Output = #struct_name #ty_generics;
 
             fn validate(value: &::serde_json::Value) -> ::valrs::ValidationResult<Self::Output> {
-                let obj = match value.as_object() {
+                Self::validate_with(value, &::valrs::ValidationContext::default())
+            }
+
+            fn validate_with(
+                __valrs_value: &::serde_json::Value,
+                __valrs_ctx: &::valrs::ValidationContext,
+            ) -> ::valrs::ValidationResult<Self::Output> {
+                let __valrs_object = __valrs_value;
+                let __valrs_obj = match __valrs_object.as_object() {
                     Some(o) => o,
-                    None => return ::valrs::ValidationResult::failure("Expected object"),
+                    None => {
+                        return ::valrs::ValidationResult::failures(vec![
+                            ::valrs::ValidationIssue::invalid_type("Expected object", "object", __valrs_value),
+                        ]);
+                    }
                 };
 
-                let mut issues: Vec<::valrs::ValidationIssue> = Vec::new();
+                #body
+            }
+        }
+    };
 
-                #(#field_validations)*
+    Ok(expanded)
+}
 
-                if !issues.is_empty() {
-                    return ::valrs::ValidationResult::Failure(issues);
-                }
+/// Generates an expression validating the object `__valrs_obj` into
+/// `constructor { fields }`.
+///
+/// The expression evaluates to a `ValidationResult` and reports issues for all
+/// fields at once. It is shared by structs and struct-like enum variants.
+/// `__valrs_object`, the `&Value` holding `__valrs_obj`, must also be in scope
+/// for flattened fields.
+/// `object_checks` run after the fields, and `value_checks` run on the
+/// constructed `__valrs_value` once there are no issues; both push to
+/// `__valrs_issues`. The fields are validated without the context's enclosing
+/// keys, which remain in `__valrs_object_ctx`.
+fn generate_named_fields_validation(
+    fields: &[ParsedField],
+    constructor: TokenStream2,
+    object_checks: TokenStream2,
+    value_checks: TokenStream2,
+) -> TokenStream2 {
+    // Generate validation code for each field; fields after the context's
+    // issue limit is reached are not validated
+    let field_validations = fields
+        .iter()
+        .map(|field| {
+            let field_local = field.local();
+            let field_ty = &field.ty;
+            let validation = if field.attrs.extra {
+                unknown::generate_extra_field_validation(field, fields)
+            } else {
+                generate_field_validation(field)
+            };
+            let field_context = field.context();
+            quote! {
+                let #field_local: Option<#field_ty> = if __valrs_ctx.is_done(__valrs_issues.len()) {
+                    None
+                } else {
+                    #field_context
+                    #validation
+                    #field_local
+                };
+            }
+        })
+        .collect::<Vec<_>>();
 
-                ::valrs::ValidationResult::Success(#struct_name {
-                    #(#field_names),*
-                })
+    // Generate struct construction; only reached with a value for every field
+    let field_names: Vec<_> = fields.iter().map(|f| &f.ident).collect();
+    let field_locals: Vec<_> = fields.iter().map(ParsedField::local).collect();
+    let construction = if value_checks.is_empty() {
+        quote! {
+            ::valrs::ValidationResult::Success(#constructor {
+                #(#field_names: #field_locals),*
+            })
+        }
+    } else {
+        quote! {
+            let __valrs_value = #constructor {
+                #(#field_names: #field_locals),*
+            };
+            #value_checks
+            if __valrs_issues.is_empty() {
+                ::valrs::ValidationResult::Success(__valrs_value)
+            } else {
+                __valrs_ctx.truncate(&mut __valrs_issues);
+                ::valrs::ValidationResult::Failure(__valrs_issues)
             }
         }
     };
 
-    Ok(expanded)
+    let object_checks = (!object_checks.is_empty()).then(|| {
+        quote! {
+            if !__valrs_ctx.is_done(__valrs_issues.len()) {
+                #object_checks
+            }
+        }
+    });
+
+    quote! {{
+        let __valrs_object_ctx = __valrs_ctx;
+        let __valrs_fields_ctx = __valrs_ctx.without_enclosing_keys();
+        let __valrs_ctx: &::valrs::ValidationContext = &__valrs_fields_ctx;
+        let mut __valrs_issues: Vec<::valrs::ValidationIssue> = Vec::new();
+
+        #(#field_validations)*
+
+        #object_checks
+
+        match (#(#field_locals,)*) {
+            (#(Some(#field_locals),)*) if __valrs_issues.is_empty() => {
+                #construction
+            }
+            _ => {
+                __valrs_ctx.truncate(&mut __valrs_issues);
+                ::valrs::ValidationResult::Failure(__valrs_issues)
+            }
+        }
+    }}
+}
+
+/// Parses the fields of a named struct, applying the container attributes.
+fn parse_struct_fields<'a>(
+    input: &DeriveInput,
+    fields: impl IntoIterator<Item = &'a Field>,
+) -> syn::Result<Vec<ParsedField>> {
+    let container_attrs = ContainerAttrs::from_attributes(&input.attrs)?;
+
+    let parsed_fields = fields
+        .into_iter()
+        .map(|field| {
+            let mut parsed = parse_field(field, container_attrs.rename_all, &container_attrs)?;
+            if let Some(path) = &container_attrs.default
+                && parsed.attrs.default.is_none()
+            {
+                parsed.attrs.default = Some(FieldDefault::Container(path.clone()));
+            }
+            if parsed.attrs.missing_message.is_none() {
+                parsed.attrs.missing_message = container_attrs.missing_message.clone();
+            }
+            Ok(parsed)
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    unknown::check_unknown_fields(input, &container_attrs, &parsed_fields)?;
+    Ok(parsed_fields)
 }
 
-fn parse_field(field: &Field) -> syn::Result<ParsedField> {
+/// Parses a named field, applying `rename_all` unless the field is renamed.
+///
+/// `Maybe<T>` fields are always optional, and so are `Option<T>` fields without
+/// a default when the container has `implicit_optional`.
+fn parse_field(
+    field: &Field,
+    rename_all: Option<RenameRule>,
+    container_attrs: &ContainerAttrs,
+) -> syn::Result<ParsedField> {
     let ident = field
         .ident
         .clone()
         .ok_or_else(|| Error::new_spanned(field, "expected named field"))?;
 
-    let attrs = FieldAttrs::from_attributes(&field.attrs)?;
+    let mut attrs = FieldAttrs::from_attributes(&field.attrs, container_attrs.serde)?;
+    let is_option = extract_inner_type(&field.ty, "Option").is_some();
+    if extract_inner_type(&field.ty, "Maybe").is_some()
+        || (container_attrs.implicit_optional
+            && is_option
+            && attrs.default.is_none()
+            && !attrs.skip
+            && !attrs.flatten)
+    {
+        attrs.optional = true;
+    }
+    if attrs.non_nullable && !(attrs.optional && is_option) {
+        return Err(Error::new_spanned(
+            field,
+            "non_nullable is only supported on optional Option<T> fields",
+        ));
+    }
+    if attrs.extra {
+        if container_attrs.unknown_fields != UnknownFields::Passthrough {
+            return Err(Error::new_spanned(
+                field,
+                "#[schema(extra)] requires #[schema(passthrough)] on the struct",
+            ));
+        }
+        if attrs.optional
+            || attrs.rename.is_some()
+            || !attrs.aliases.is_empty()
+            || attrs.default.is_some()
+            || attrs.skip
+            || attrs.flatten
+            || attrs.coerce
+            || attrs.has_constraints()
+        {
+            return Err(Error::new_spanned(
+                field,
+                "extra cannot be combined with optional, rename, alias, default, skip, flatten, coerce or constraints",
+            ));
+        }
+    }
+    if attrs.flatten
+        && (attrs.optional
+            || attrs.rename.is_some()
+            || !attrs.aliases.is_empty()
+            || attrs.default.is_some()
+            || attrs.has_constraints())
+    {
+        return Err(Error::new_spanned(
+            field,
+            "flatten cannot be combined with optional, rename, alias, default or constraints",
+        ));
+    }
+
+    let name = ident.unraw().to_string();
+    let key = match (&attrs.rename, rename_all) {
+        (Some(rename), _) => rename.clone(),
+        (None, Some(rule)) => rule.apply_to_field(&name),
+        (None, None) => name,
+    };
 
     Ok(ParsedField {
         ident,
         ty: field.ty.clone(),
         attrs,
+        key,
     })
 }
 
+/// Generates code binding the field's local, an `Option<FieldTy>`, from
+/// `__valrs_obj`.
+///
+/// The local is `Some` when the field validated and `None` when it failed, in
+/// which case the field's issues have been pushed to `__valrs_issues`. This
+/// lets every field be checked without requiring a placeholder value for
+/// failed fields.
 fn generate_field_validation(field: &ParsedField) -> TokenStream2 {
-    let field_ident = &field.ident;
+    let field_local = field.local();
     let field_ty = &field.ty;
     let json_key = field.json_key();
 
-    // Generate additional string length validations if specified
-    let length_validations = generate_length_validations(field, &json_key);
-    let has_length_validations =
-        field.attrs.min_length.is_some() || field.attrs.max_length.is_some();
+    // Skipped fields are never read from input
+    if field.attrs.skip {
+        let default_value = generate_default_value(field);
+        return quote! {
+            let #field_local: Option<#field_ty> = Some(#default_value);
+        };
+    }
 
-    if field.attrs.optional {
-        // For optional fields, missing or null values become None.
-        // We need to extract the inner type from Option<T> to validate it directly
-        // when length validations are present.
-        if let Some(inner_ty) =
-            extract_option_inner_type(field_ty).filter(|_| has_length_validations)
-        {
-            // Validate the inner type directly and wrap in Some
-            quote! {
-                let #field_ident: #field_ty = match obj.get(#json_key) {
-                    Some(::serde_json::Value::Null) | None => None,
-                    Some(v) => {
-                        match <#inner_ty as ::valrs::Valrs>::validate(v) {
-                            ::valrs::ValidationResult::Success(inner_val) => {
-                                // Apply length validations to the inner value
-                                let val = &inner_val;
-                                #length_validations
-                                Some(inner_val)
-                            }
-                            ::valrs::ValidationResult::Failure(errs) => {
-                                for mut err in errs {
-                                    let mut new_path = vec![::valrs::PathSegment::Key(#json_key.to_string())];
-                                    if let Some(existing_path) = err.path.take() {
-                                        new_path.extend(existing_path);
-                                    }
-                                    err.path = Some(new_path);
-                                    issues.push(err);
-                                }
-                                None
-                            }
-                        }
+    // Flattened fields validate the enclosing object as a whole, so its other
+    // keys are not unknown to them even in a strict context
+    if field.attrs.flatten {
+        return quote! {
+            let #field_local: Option<#field_ty> =
+                match <#field_ty as ::valrs::Valrs>::validate_with(
+                    __valrs_object,
+                    &__valrs_ctx.clone().with_strict(false),
+                ) {
+                    ::valrs::ValidationResult::Success(validated_val) => Some(validated_val),
+                    ::valrs::ValidationResult::Failure(errs) => {
+                        __valrs_issues.extend(errs);
+                        None
                     }
                 };
+        };
+    }
+
+    // Look up the field by its key, then by each alias; issues are reported
+    // at the key that was found. Any further key that is present conflicts.
+    let aliases = &field.attrs.aliases;
+    let lookup = if aliases.is_empty() {
+        quote! { __valrs_obj.get(#json_key).map(|v| (#json_key, v)) }
+    } else {
+        quote! {{
+            let present: Vec<&str> = [#json_key, #(#aliases),*]
+                .into_iter()
+                .filter(|key| __valrs_obj.contains_key(*key))
+                .collect();
+            for key in present.iter().skip(1) {
+                __valrs_issues.push(
+                    ::valrs::ValidationIssue::key_conflict(
+                        format!("Key '{}' conflicts with '{}'", key, present[0]),
+                        &[present[0], *key],
+                    )
+                    .at(vec![::valrs::PathSegment::Key(key.to_string())]),
+                );
             }
+            present.first().map(|key| (*key, &__valrs_obj[*key]))
+        }}
+    };
+
+    // Generate constraint checks (string lengths, numeric bounds) if specified
+    let constraint_checks =
+        constraints::generate_constraint_checks(field, &IssuePath::Key(quote! { __valrs_key }));
+    let has_constraints = field.attrs.has_constraints();
+
+    if field.attrs.optional {
+        // For optional fields, missing values become None (Absent for
+        // Maybe<T>), and so do null values (Null for Maybe<T>) unless the field
+        // is non_nullable, in which case null is validated like other values.
+        // The inner type T is validated directly when there are constraints,
+        // so the checks apply to the unwrapped value, and for Maybe<T> and
+        // non_nullable fields.
+        let maybe_inner_ty = extract_inner_type(field_ty, "Maybe");
+        let (absent, null) = if maybe_inner_ty.is_some() {
+            (
+                quote! { ::valrs::Maybe::Absent },
+                quote! { ::valrs::Maybe::Null },
+            )
         } else {
-            // No length validations - use the Option type's validate directly
-            quote! {
-                let #field_ident: #field_ty = match obj.get(#json_key) {
-                    Some(::serde_json::Value::Null) | None => None,
-                    Some(v) => {
-                        match <#field_ty as ::valrs::Valrs>::validate(v) {
-                            ::valrs::ValidationResult::Success(val) => val,
-                            ::valrs::ValidationResult::Failure(errs) => {
-                                for mut err in errs {
-                                    let mut new_path = vec![::valrs::PathSegment::Key(#json_key.to_string())];
-                                    if let Some(existing_path) = err.path.take() {
-                                        new_path.extend(existing_path);
-                                    }
-                                    err.path = Some(new_path);
-                                    issues.push(err);
-                                }
-                                None
-                            }
+            (quote! { None }, quote! { None })
+        };
+        let unwrap_inner = has_constraints || field.attrs.non_nullable;
+        let (validate_ty, field_value) = match maybe_inner_ty {
+            Some(inner_ty) => (inner_ty, quote! { ::valrs::Maybe::Value(validated_val) }),
+            None => match extract_inner_type(field_ty, "Option").filter(|_| unwrap_inner) {
+                Some(inner_ty) => (inner_ty, quote! { Some(validated_val) }),
+                None => (field_ty, quote! { validated_val }),
+            },
+        };
+        let null_arm = (!field.attrs.non_nullable).then(|| {
+            quote! { Some((_, ::serde_json::Value::Null)) => Some(#null), }
+        });
+
+        quote! {
+            let #field_local: Option<#field_ty> = match #lookup {
+                None => Some(#absent),
+                #null_arm
+                Some((__valrs_key, v)) => {
+                    match <#validate_ty as ::valrs::Valrs>::validate_with(v, __valrs_ctx)
+                        .with_path_prefix(::valrs::PathSegment::Key(__valrs_key.to_string()))
+                    {
+                        ::valrs::ValidationResult::Success(validated_val) => {
+                            #constraint_checks
+                            Some(#field_value)
+                        }
+                        ::valrs::ValidationResult::Failure(errs) => {
+                            __valrs_issues.extend(errs);
+                            None
                         }
                     }
-                };
-            }
+                }
+            };
         }
     } else {
-        // For required fields, missing values are an error
-        let length_block = if has_length_validations {
-            quote! {
-                let val = &validated_val;
-                #length_validations
-            }
+        // For required fields, missing values are an error unless there is a default
+        let missing = if field.attrs.default.is_some() {
+            let default_value = generate_default_value(field);
+            quote! { Some(#default_value) }
         } else {
-            quote! {}
+            // A custom message is set after creating the issue, so that error
+            // maps do not replace it
+            let custom_message = field.attrs.missing_message.as_ref().map(|message| {
+                let message = constraints::generate_message(&field.key, message, &[]);
+                quote! { .with_message(#message) }
+            });
+            quote! {{
+                __valrs_issues.push(
+                    ::valrs::ValidationIssue::missing_field(format!("Missing required field '{}'", #json_key))
+                        #custom_message
+                        .at(vec![::valrs::PathSegment::Key(#json_key.to_string())]),
+                );
+                None
+            }}
         };
 
         quote! {
-            let #field_ident: #field_ty = match obj.get(#json_key) {
-                Some(v) => {
-                    match <#field_ty as ::valrs::Valrs>::validate(v) {
+            let #field_local: Option<#field_ty> = match #lookup {
+                Some((__valrs_key, v)) => {
+                    match <#field_ty as ::valrs::Valrs>::validate_with(v, __valrs_ctx)
+                        .with_path_prefix(::valrs::PathSegment::Key(__valrs_key.to_string()))
+                    {
                         ::valrs::ValidationResult::Success(validated_val) => {
-                            #length_block
-                            validated_val
+                            #constraint_checks
+                            Some(validated_val)
                         }
                         ::valrs::ValidationResult::Failure(errs) => {
-                            for mut err in errs {
-                                let mut new_path = vec![::valrs::PathSegment::Key(#json_key.to_string())];
-                                if let Some(existing_path) = err.path.take() {
-                                    new_path.extend(existing_path);
-                                }
-                                err.path = Some(new_path);
-                                issues.push(err);
-                            }
-                            // Use default to allow continuing validation of other fields
-                            // The issues vec will cause failure at the end
-                            <#field_ty as Default>::default()
+                            __valrs_issues.extend(errs);
+                            None
                         }
                     }
                 }
-                None => {
-                    issues.push(::valrs::ValidationIssue::with_path(
-                        format!("Missing required field '{}'", #json_key),
-                        vec![::valrs::PathSegment::Key(#json_key.to_string())],
-                    ));
-                    // Use default to allow continuing validation of other fields
-                    <#field_ty as Default>::default()
-                }
+                None => #missing,
             };
         }
     }
 }
 
-/// Attempts to extract the inner type T from Option<T>.
-/// Returns None if the type is not an Option.
-fn extract_option_inner_type(ty: &Type) -> Option<&Type> {
+/// Generates an expression for the value of a missing or skipped field.
+fn generate_default_value(field: &ParsedField) -> TokenStream2 {
+    match &field.attrs.default {
+        None | Some(FieldDefault::Trait) => quote! { ::core::default::Default::default() },
+        Some(FieldDefault::Path(path)) => quote! { #path() },
+        Some(FieldDefault::Literal(expr)) => quote! { #expr },
+        Some(FieldDefault::Container(path)) => {
+            let field_ident = &field.ident;
+            match path {
+                Some(path) => quote! { #path().#field_ident },
+                None => quote! { <Self as ::core::default::Default>::default().#field_ident },
+            }
+        }
+    }
+}
+
+/// Attempts to extract the inner type T from `wrapper<T>`, such as `Option<T>`.
+/// Returns None if the type is not a `wrapper`.
+fn extract_inner_type<'a>(ty: &'a Type, wrapper: &str) -> Option<&'a Type> {
     let Type::Path(type_path) = ty else {
         return None;
     };
     let segment = type_path.path.segments.last()?;
-    if segment.ident != "Option" {
+    if segment.ident != wrapper {
         return None;
     }
     let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
@@ -354,55 +842,26 @@ fn extract_option_inner_type(ty: &Type) -> Option<&Type> {
     }
 }
 
-fn generate_length_validations(field: &ParsedField, json_key: &str) -> TokenStream2 {
-    let mut validations = Vec::new();
-
-    // For string length validations, `val` is a reference to the validated value.
-    // For required String fields, val is &String.
-    // For optional String fields with length validation, val is &String (inner type).
-
-    if let Some(min_len) = field.attrs.min_length {
-        validations.push(quote! {
-            if val.len() < #min_len {
-                issues.push(::valrs::ValidationIssue::with_path(
-                    format!("String must be at least {} characters, got {}", #min_len, val.len()),
-                    vec![::valrs::PathSegment::Key(#json_key.to_string())],
-                ));
-            }
-        });
-    }
-
-    if let Some(max_len) = field.attrs.max_length {
-        validations.push(quote! {
-            if val.len() > #max_len {
-                issues.push(::valrs::ValidationIssue::with_path(
-                    format!("String must be at most {} characters, got {}", #max_len, val.len()),
-                    vec![::valrs::PathSegment::Key(#json_key.to_string())],
-                ));
-            }
-        });
-    }
-
-    if validations.is_empty() {
-        quote! {}
-    } else {
-        quote! {
-            #(#validations)*
-        }
-    }
-}
-
 // =============================================================================
 // StandardJsonSchema derive macro
 // =============================================================================
 
-/// Derives the `StandardJsonSchema` trait for a struct.
+/// Derives the `StandardJsonSchema` trait for a struct or an enum.
 ///
 /// This macro generates JSON Schema for the struct, including:
 /// - Object schema with `type: "object"`
 /// - Properties for each field
 /// - Required array for non-optional fields
-/// - String constraints (`minLength`, `maxLength`) when specified
+/// - Constraint keywords (`minLength`, `minItems`, ...) when specified
+///
+/// Single-field tuple structs generate the inner type's schema plus field
+/// constraints. Multi-field tuple structs generate a fixed-length array schema
+/// with one schema per position.
+///
+/// Unit-only enums generate `{ "type": "string", "enum": [...] }`. Enums with
+/// data generate a `oneOf` of the variant schemas, each pinning its tag with a
+/// `const` (an `enum` of one value and a `discriminator` object for OpenAPI
+/// 3.0). Untagged enums generate an `anyOf`.
 ///
 /// # Example
 ///
@@ -426,11 +885,62 @@ fn generate_length_validations(field: &ParsedField, json_key: &str) -> TokenStre
 ///
 /// # Attributes
 ///
-/// - `#[schema(optional)]` - Field is not required in the schema
+/// - `#[schema(optional)]` - Field is not required in the schema, and its
+///   schema is the nullable inner type's; with `non_nullable` it is not
+///   nullable. `Maybe<T>` fields and, with `implicit_optional`, `Option<T>`
+///   fields are treated the same way
 /// - `#[schema(rename = "fieldName")]` - Use different property name in schema
+/// - `#[schema(default)]`, `#[schema(default = ...)]` - Field is not required in
+///   the input schema, but is in the output schema. A literal default is also
+///   emitted as the property's `default` in the input schema
 /// - `#[schema(min_length = N)]` - Add `minLength` constraint for strings
 /// - `#[schema(max_length = N)]` - Add `maxLength` constraint for strings
-#[proc_macro_derive(StandardJsonSchema, attributes(schema))]
+/// - `#[schema(format = "...")]`, `#[schema(pattern = "...")]` - Add `format`
+///   and `pattern` keywords for strings
+/// - `#[schema(minimum = N)]`, `#[schema(maximum = N)]`,
+///   `#[schema(exclusive_minimum = N)]`, `#[schema(exclusive_maximum = N)]`,
+///   `#[schema(multiple_of = N)]` - Add the matching number keywords. OpenAPI 3.0
+///   uses `minimum`/`maximum` with a boolean `exclusiveMinimum`/`exclusiveMaximum`
+/// - `#[schema(min_items = N)]`, `#[schema(max_items = N)]`,
+///   `#[schema(unique_items)]` - Add `minItems`, `maxItems` and `uniqueItems`
+/// - `#[schema(items(...))]` - Adds the item constraints' keywords to the
+///   `items` schema
+/// - `#[schema(rename_all = "...")]`, `#[schema(rename = "name")]`,
+///   `#[schema(tag = "...")]`, `#[schema(content = "...")]` and
+///   `#[schema(untagged)]` - Enum representation, as for `Valrs`
+/// - `#[schema(rename_all = "...")]` on structs - Case conversion for all
+///   property names
+/// - `#[schema(alias = "name")]` - Listed in an `x-aliases` vendor extension on
+///   the property
+/// - `#[schema(flatten)]` - Merges the nested struct's `properties` and
+///   `required`, and adds its presence rules to `allOf`. The schema of a
+///   hand-written type is added to `allOf` whole unless it is a plain object
+///   schema
+/// - Doc comments - Become the `description` of the property or container
+///   schema; `#[schema(description = "...")]` overrides them
+/// - `#[schema(title = "...")]` - Adds `title`
+/// - `#[schema(example = ...)]` - A string, number or boolean example; may be
+///   repeated. Emitted as `examples` in JSON Schema, or as the first `example`
+///   in OpenAPI 3.0
+/// - `#[schema(deprecated)]`, `#[schema(read_only)]`, `#[schema(write_only)]` -
+///   Add `deprecated` (except in Draft 07), `readOnly` and `writeOnly`
+///
+/// The annotations apply both to fields and to the container.
+/// - `#[schema(deny_unknown_fields)]` - Adds `unevaluatedProperties: false` in
+///   Draft 2020-12, `additionalProperties: false` otherwise
+/// - `#[schema(passthrough)]` - Adds `additionalProperties: true`; the `extra`
+///   field is not a property
+/// - `#[schema(require_one_of(...))]` - Adds an `anyOf` of `required` keywords
+/// - `#[schema(require_together(...))]` - Adds `dependentRequired`
+///   (`dependencies` in Draft 07, an `anyOf` in OpenAPI 3.0). Several `anyOf`
+///   keywords are combined with `allOf`
+/// - Serde attributes, as for `Valrs`; skipped fields are left out, and fields
+///   with a default are only required in the output schema
+/// - `#[schema(bound = "...")]` - Replaces the automatic
+///   `T: StandardJsonSchema<Output = T>` bounds on type parameters
+/// - `#[schema(bound(schema = "..."))]` - Replaces them for this impl only,
+///   see `Valrs`
+#[proc_macro_derive(StandardJsonSchema, attributes(schema, serde))]
 pub fn derive_standard_json_schema(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
@@ -443,15 +953,14 @@ pub fn derive_standard_json_schema(input: TokenStream) -> TokenStream {
 fn derive_standard_json_schema_impl(input: DeriveInput) -> syn::Result<TokenStream2> {
     let struct_name = &input.ident;
 
-    // Only support named structs
+    check_container_attrs(&input)?;
+
+    // Tuple structs are handled separately; unit structs are not supported
     let fields = match &input.data {
         Data::Struct(data) => match &data.fields {
             Fields::Named(named) => &named.named,
-            Fields::Unnamed(_) => {
-                return Err(Error::new_spanned(
-                    struct_name,
-                    "StandardJsonSchema derive does not support tuple structs",
-                ));
+            Fields::Unnamed(unnamed) => {
+                return tuple_struct::derive_standard_json_schema_tuple_struct(&input, unnamed);
             }
             Fields::Unit => {
                 return Err(Error::new_spanned(
@@ -460,12 +969,7 @@ fn derive_standard_json_schema_impl(input: DeriveInput) -> syn::Result<TokenStre
                 ));
             }
         },
-        Data::Enum(_) => {
-            return Err(Error::new_spanned(
-                struct_name,
-                "StandardJsonSchema derive does not support enums yet",
-            ));
-        }
+        Data::Enum(data) => return enums::derive_standard_json_schema_enum(&input, data),
         Data::Union(_) => {
             return Err(Error::new_spanned(
                 struct_name,
@@ -474,52 +978,48 @@ fn derive_standard_json_schema_impl(input: DeriveInput) -> syn::Result<TokenStre
         }
     };
 
-    // Parse all fields
-    let parsed_fields: Vec<ParsedField> =
-        fields.iter().map(parse_field).collect::<syn::Result<_>>()?;
-
-    // Generate property schema for each field
-    let property_insertions = parsed_fields
-        .iter()
-        .map(generate_property_insertion)
-        .collect::<Vec<_>>();
-
-    // Generate required array entries (non-optional fields)
-    let required_entries: Vec<_> = parsed_fields
-        .iter()
-        .filter(|f| !f.attrs.optional)
-        .map(|f| {
-            let json_key = f.json_key();
-            quote! { required.push(#json_key.to_string()); }
-        })
-        .collect();
+    let parsed_fields = parse_struct_fields(&input, fields)?;
+    let container_attrs = ContainerAttrs::from_attributes(&input.attrs)?;
+    let presence_rules = checks::parse_presence_rules(&container_attrs, &parsed_fields)?;
 
-    let expanded = quote! {
-        impl ::valrs::StandardJsonSchema for #struct_name {
-            fn json_schema_input(target: ::valrs::JsonSchemaTarget) -> ::serde_json::Value {
-                use ::serde_json::{json, Map, Value};
-
-                let mut properties = Map::new();
-                let mut required: Vec<String> = Vec::new();
-
-                #(#property_insertions)*
-                #(#required_entries)*
+    let presence_schema = checks::generate_presence_schema(&presence_rules);
+    // Presence rules are kept as an `allOf` clause when the struct is
+    // flattened. Its unknown-key policy and annotations are not: the enclosing
+    // object has other keys, and its own annotations.
+    let presence_properties = (!presence_rules.is_empty()).then(|| {
+        quote! {
+            let mut schema = ::serde_json::Value::Object(::serde_json::Map::new());
+            #presence_schema
+            object.all_of.push(schema);
+        }
+    });
+    let unknown_keys_schema = unknown::generate_unknown_keys_schema(container_attrs.unknown_fields);
+    let metadata_schema =
+        metadata::generate_metadata_schema(&container_attrs.metadata, &format_ident!("schema"));
+    let methods = SchemaKind::ALL.map(|kind| {
+        let method = kind.method();
+        let properties_method = kind.properties_method();
+        let object_schema = generate_object_schema(&parsed_fields, kind);
+        let object_properties = generate_object_properties(&parsed_fields, kind);
+        let properties_body = match &presence_properties {
+            Some(presence_properties) => quote! {
+                let mut object = #object_properties;
+                #presence_properties
+                Some(object)
+            },
+            None => quote! { Some(#object_properties) },
+        };
+        quote! {
+            fn #method(target: ::valrs::JsonSchemaTarget) -> ::serde_json::Value {
+                use ::serde_json::Value;
 
-                let mut schema = json!({
-                    "type": "object",
-                    "properties": properties,
-                });
+                let mut schema = #object_schema;
+                #presence_schema
+                #unknown_keys_schema
+                #metadata_schema
 
+                // Add $schema for root schemas
                 if let Value::Object(ref mut map) = schema {
-                    // Only add required array if there are required fields
-                    if !required.is_empty() {
-                        map.insert(
-                            "required".to_string(),
-                            Value::Array(required.into_iter().map(Value::String).collect())
-                        );
-                    }
-
-                    // Add $schema for root schemas
                     let uri = target.schema_uri();
                     if !uri.is_empty() {
                         map.insert("$schema".to_string(), Value::String(uri.to_string()));
@@ -529,87 +1029,198 @@ fn derive_standard_json_schema_impl(input: DeriveInput) -> syn::Result<TokenStre
                 schema
             }
 
-            fn json_schema_output(target: ::valrs::JsonSchemaTarget) -> ::serde_json::Value {
-                Self::json_schema_input(target)
+            fn #properties_method(
+                target: ::valrs::JsonSchemaTarget,
+            ) -> Option<::valrs::ObjectProperties> {
+                #properties_body
             }
         }
+    });
+
+    let generics = bounded_generics(&input, DerivedTrait::StandardJsonSchema)?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let expanded = quote! {
+        impl #impl_generics ::valrs::StandardJsonSchema for #struct_name #ty_generics #where_clause {
+            #(#methods)*
+        }
     };
 
     Ok(expanded)
 }
 
-/// Generates code to insert a property schema for a field.
-fn generate_property_insertion(field: &ParsedField) -> TokenStream2 {
-    let field_ty = &field.ty;
-    let json_key = field.json_key();
-    let has_string_constraints =
-        field.attrs.min_length.is_some() || field.attrs.max_length.is_some();
+/// Generates an expression building the `kind` object schema (without
+/// `$schema`) for `fields`.
+///
+/// The expression expects `target` to be in scope and is shared by structs and
+/// struct-like enum variants.
+fn generate_object_schema(fields: &[ParsedField], kind: SchemaKind) -> TokenStream2 {
+    let object_properties = generate_object_properties(fields, kind);
 
-    // For optional fields, get the inner type's schema
-    let inner_ty = if field.attrs.optional {
-        extract_option_inner_type(field_ty)
-    } else {
-        None
-    };
+    quote! {{
+        use ::serde_json::{json, Value};
 
-    // Determine which type to use for the base schema
-    let schema_ty = inner_ty.unwrap_or(field_ty);
+        let ::valrs::ObjectProperties { properties, required, all_of } = #object_properties;
 
-    if has_string_constraints {
-        // Generate string schema with constraints
-        let min_len_code = field
-            .attrs
-            .min_length
-            .map(|min| {
-                quote! {
-                    if let Value::Object(ref mut m) = prop_schema {
-                        m.insert("minLength".to_string(), Value::Number(#min.into()));
-                    }
+        let mut schema = json!({
+            "type": "object",
+            "properties": properties,
+        });
+
+        // Only add required array if there are required fields
+        if let Value::Object(ref mut map) = schema {
+            if !required.is_empty() {
+                map.insert(
+                    "required".to_string(),
+                    Value::Array(required.into_iter().map(Value::String).collect())
+                );
+            }
+            // Schemas of flattened fields that cannot be merged as properties
+            if !all_of.is_empty() {
+                map.insert("allOf".to_string(), Value::Array(all_of));
+            }
+        }
+
+        schema
+    }}
+}
+
+/// Generates an expression building the `ObjectProperties` of the `kind`
+/// object schema for `fields`, without the container's presence rules.
+///
+/// The expression expects `target` to be in scope.
+fn generate_object_properties(fields: &[ParsedField], kind: SchemaKind) -> TokenStream2 {
+    // Generate property schema and required entry for each field
+    let property_insertions = fields
+        .iter()
+        .filter(|f| !f.attrs.skip && !f.attrs.extra)
+        .map(|f| generate_property_insertion(f, kind))
+        .collect::<Vec<_>>();
+
+    // Only flattened fields add to `all_of`
+    let all_of_mut = fields
+        .iter()
+        .any(|f| f.attrs.flatten && !f.attrs.skip)
+        .then(|| quote! { mut });
+
+    quote! {{
+        use ::serde_json::{Map, Value};
+
+        let mut properties = Map::new();
+        let mut required: Vec<String> = Vec::new();
+        let #all_of_mut all_of: Vec<Value> = Vec::new();
+
+        #(#property_insertions)*
+
+        ::valrs::ObjectProperties { properties, required, all_of }
+    }}
+}
+
+/// Generates code to insert a property schema for a field, and to mark it
+/// required unless it may be missing.
+///
+/// A flattened field contributes the properties, required keys and `allOf`
+/// clauses of its own object schema instead. When the field type does not
+/// expose them, its schema is added to `all_of` whole.
+fn generate_property_insertion(field: &ParsedField, kind: SchemaKind) -> TokenStream2 {
+    if field.attrs.flatten {
+        let field_ty = &field.ty;
+        let method = field.schema_kind(kind).method();
+        let properties_method = field.schema_kind(kind).properties_method();
+        return quote! {
+            match <#field_ty as ::valrs::StandardJsonSchema>::#properties_method(target) {
+                Some(flat) => {
+                    properties.extend(flat.properties);
+                    required.extend(flat.required);
+                    all_of.extend(flat.all_of);
                 }
-            })
-            .unwrap_or_else(|| quote! {});
-
-        let max_len_code = field
-            .attrs
-            .max_length
-            .map(|max| {
-                quote! {
-                    if let Value::Object(ref mut m) = prop_schema {
-                        m.insert("maxLength".to_string(), Value::Number(#max.into()));
+                None => {
+                    let mut flat_schema = <#field_ty as ::valrs::StandardJsonSchema>::#method(target);
+                    if let Value::Object(ref mut m) = flat_schema {
+                        m.remove("$schema");
                     }
+                    all_of.push(flat_schema);
                 }
-            })
-            .unwrap_or_else(|| quote! {});
+            }
+        };
+    }
+
+    let json_key = field.json_key();
+    let field_schema = generate_field_schema(field, kind);
+    let required_entry = field.is_required(kind).then(|| {
+        quote! { required.push(#json_key.to_string()); }
+    });
+
+    // Only literal defaults are known without serializing a value
+    let default_entry = match &field.attrs.default {
+        Some(FieldDefault::Literal(expr)) if kind != SchemaKind::Output => Some(quote! {
+            if let Value::Object(ref mut m) = prop_schema {
+                m.insert("default".to_string(), ::serde_json::json!(#expr));
+            }
+        }),
+        _ => None,
+    };
 
+    // Aliases are accepted in input but not part of the schema's vocabulary,
+    // so they are listed in a vendor extension
+    let aliases = &field.attrs.aliases;
+    let aliases_entry = (!aliases.is_empty()).then(|| {
         quote! {
-            {
-                // Get base schema from the type (without $schema field)
-                let mut prop_schema = <#schema_ty as ::valrs::StandardJsonSchema>::json_schema_input(
-                    ::valrs::JsonSchemaTarget::OpenApi30
-                );
-                // Remove $schema if present (shouldn't be for OpenApi30, but be safe)
-                if let Value::Object(ref mut m) = prop_schema {
-                    m.remove("$schema");
-                }
-                #min_len_code
-                #max_len_code
-                properties.insert(#json_key.to_string(), prop_schema);
+            if let Value::Object(ref mut m) = prop_schema {
+                m.insert("x-aliases".to_string(), ::serde_json::json!([#(#aliases),*]));
             }
         }
+    });
+
+    quote! {
+        let mut prop_schema = #field_schema;
+        #default_entry
+        #aliases_entry
+        properties.insert(#json_key.to_string(), prop_schema);
+        #required_entry
+    }
+}
+
+/// Generates an expression for a field's `kind` schema (without `$schema`),
+/// including any constraints from its attributes.
+fn generate_field_schema(field: &ParsedField, kind: SchemaKind) -> TokenStream2 {
+    let field_ty = &field.ty;
+    let method = field.schema_kind(kind).method();
+
+    // Optional fields use the inner type's schema, with the constraints, made
+    // nullable unless the field is non_nullable
+    let inner_ty = if field.attrs.optional {
+        extract_inner_type(field_ty, "Option").or_else(|| extract_inner_type(field_ty, "Maybe"))
     } else {
-        // No constraints, use the type's schema directly
+        None
+    };
+    let nullable_schema = (inner_ty.is_some() && !field.attrs.non_nullable).then(|| {
         quote! {
-            {
-                // Get schema from the type (without $schema field)
-                let mut prop_schema = <#schema_ty as ::valrs::StandardJsonSchema>::json_schema_input(
-                    ::valrs::JsonSchemaTarget::OpenApi30
-                );
-                // Remove $schema if present (shouldn't be for OpenApi30, but be safe)
-                if let Value::Object(ref mut m) = prop_schema {
-                    m.remove("$schema");
-                }
-                properties.insert(#json_key.to_string(), prop_schema);
+            prop_schema = ::valrs::validators::nullable_schema(prop_schema, target);
+            if let Value::Object(ref mut m) = prop_schema {
+                m.remove("$schema");
             }
         }
-    }
+    });
+
+    // Determine which type to use for the base schema
+    let schema_ty = inner_ty.unwrap_or(field_ty);
+
+    // Generate schema constraints and annotations
+    let constraint_schema =
+        constraints::generate_constraint_schema(&field.attrs.constraints, quote! { prop_schema });
+    let metadata_schema =
+        metadata::generate_metadata_schema(&field.attrs.metadata, &format_ident!("prop_schema"));
+
+    quote! {{
+        // Get base schema from the type (without $schema field)
+        let mut prop_schema = <#schema_ty as ::valrs::StandardJsonSchema>::#method(target);
+        if let Value::Object(ref mut m) = prop_schema {
+            m.remove("$schema");
+        }
+        #constraint_schema
+        #nullable_schema
+        #metadata_schema
+        prop_schema
+    }}
 }
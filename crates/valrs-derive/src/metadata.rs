@@ -0,0 +1,76 @@
+//! Code generation for schema annotations: `description` (from doc comments),
+//! `title`, examples, `deprecated`, `readOnly` and `writeOnly`.
+
+use proc_macro2::{Ident, TokenStream as TokenStream2};
+use quote::quote;
+
+use crate::attrs::Metadata;
+
+/// Generates statements adding `metadata` to the schema in the variable
+/// `schema`.
+///
+/// Examples are an `examples` array in JSON Schema, while OpenAPI 3.0 has a
+/// single `example`, for which the first one is used. Draft 07 has no
+/// `deprecated` keyword, so it is left out there. The statements expect
+/// `target` to be in scope.
+pub(crate) fn generate_metadata_schema(metadata: &Metadata, schema: &Ident) -> TokenStream2 {
+    if metadata.is_empty() {
+        return quote! {};
+    }
+
+    let mut keywords = Vec::new();
+    let mut insert = |key: &str, value: TokenStream2| {
+        keywords.push(quote! {
+            m.insert(#key.to_string(), #value);
+        });
+    };
+
+    if let Some(title) = &metadata.title {
+        insert(
+            "title",
+            quote! { ::serde_json::Value::String(#title.to_string()) },
+        );
+    }
+    if let Some(description) = &metadata.description {
+        insert(
+            "description",
+            quote! { ::serde_json::Value::String(#description.to_string()) },
+        );
+    }
+    if metadata.read_only {
+        insert("readOnly", quote! { ::serde_json::Value::Bool(true) });
+    }
+    if metadata.write_only {
+        insert("writeOnly", quote! { ::serde_json::Value::Bool(true) });
+    }
+
+    let examples = &metadata.examples;
+    let examples_insert = examples.first().map(|first| {
+        quote! {
+            match target {
+                ::valrs::JsonSchemaTarget::OpenApi30 => {
+                    m.insert("example".to_string(), ::serde_json::json!(#first));
+                }
+                _ => {
+                    m.insert("examples".to_string(), ::serde_json::json!([#(#examples),*]));
+                }
+            }
+        }
+    });
+
+    let deprecated_insert = metadata.deprecated.then(|| {
+        quote! {
+            if target != ::valrs::JsonSchemaTarget::Draft07 {
+                m.insert("deprecated".to_string(), ::serde_json::Value::Bool(true));
+            }
+        }
+    });
+
+    quote! {
+        if let ::serde_json::Value::Object(ref mut m) = #schema {
+            #(#keywords)*
+            #examples_insert
+            #deprecated_insert
+        }
+    }
+}
@@ -0,0 +1,249 @@
+//! Code generation for tuple structs.
+//!
+//! - A tuple struct with one field is a transparent newtype: `struct Id(u64)`
+//!   validates exactly like `u64`, plus any constraints on the field.
+//! - A tuple struct with several fields is a fixed-length JSON array, like the
+//!   tuple of its field types, with issues reported at index paths.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{DeriveInput, Error, Field, FieldsUnnamed};
+
+use crate::attrs::{ContainerAttrs, FieldAttrs};
+use crate::constraints::generate_constraint_checks;
+use crate::metadata::generate_metadata_schema;
+use crate::{
+    DerivedTrait, IssuePath, ParsedField, SchemaKind, bounded_generics, generate_field_schema,
+};
+
+/// Parses the field at `index` of a tuple struct.
+///
+/// The field is bound to a local named `field<index>` in generated code.
+fn parse_tuple_field(index: usize, field: &Field, serde: bool) -> syn::Result<ParsedField> {
+    let attrs = FieldAttrs::from_attributes(&field.attrs, serde)?;
+
+    let unsupported = [
+        ("rename", attrs.rename.is_some()),
+        ("optional", attrs.optional),
+        ("non_nullable", attrs.non_nullable),
+        ("alias", !attrs.aliases.is_empty()),
+        ("default", attrs.default.is_some()),
+        ("skip", attrs.skip),
+        ("flatten", attrs.flatten),
+        ("missing_message", attrs.missing_message.is_some()),
+    ];
+    if let Some((name, _)) = unsupported.iter().find(|(_, present)| *present) {
+        return Err(Error::new_spanned(
+            field,
+            format!("`{}` is not supported on tuple struct fields", name),
+        ));
+    }
+
+    Ok(ParsedField {
+        ident: format_ident!("field{}", index),
+        ty: field.ty.clone(),
+        attrs,
+        key: index.to_string(),
+    })
+}
+
+fn parse_tuple_fields(
+    input: &DeriveInput,
+    fields: &FieldsUnnamed,
+) -> syn::Result<Vec<ParsedField>> {
+    let container_attrs = ContainerAttrs::from_attributes(&input.attrs)?;
+
+    fields
+        .unnamed
+        .iter()
+        .enumerate()
+        .map(|(index, field)| parse_tuple_field(index, field, container_attrs.serde))
+        .collect()
+}
+
+pub(crate) fn derive_valrs_tuple_struct(
+    input: &DeriveInput,
+    fields: &FieldsUnnamed,
+) -> syn::Result<TokenStream2> {
+    let struct_name = &input.ident;
+    let parsed_fields = parse_tuple_fields(input, fields)?;
+
+    let body = match parsed_fields.as_slice() {
+        [] => {
+            return Err(Error::new_spanned(
+                struct_name,
+                "Valrs derive does not support tuple structs without fields",
+            ));
+        }
+        [field] => generate_newtype_validation(struct_name, field),
+        _ => generate_tuple_validation(struct_name, &parsed_fields),
+    };
+
+    let generics = bounded_generics(input, DerivedTrait::Valrs)?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::valrs::Valrs for #struct_name #ty_generics #where_clause {
+            type Input = #struct_name #ty_generics;
+            type Output = #struct_name #ty_generics;
+
+            fn validate(value: &::serde_json::Value) -> ::valrs::ValidationResult<Self::Output> {
+                Self::validate_with(value, &::valrs::ValidationContext::default())
+            }
+
+            fn validate_with(
+                __valrs_value: &::serde_json::Value,
+                __valrs_ctx: &::valrs::ValidationContext,
+            ) -> ::valrs::ValidationResult<Self::Output> {
+                #body
+            }
+        }
+    })
+}
+
+/// Generates validation for a newtype, which validates `__valrs_value` as the
+/// inner type.
+fn generate_newtype_validation(struct_name: &syn::Ident, field: &ParsedField) -> TokenStream2 {
+    let field_ty = &field.ty;
+    let constraint_checks = generate_constraint_checks(field, &IssuePath::Root);
+    let field_context = field.context();
+
+    quote! {
+        #field_context
+        match <#field_ty as ::valrs::Valrs>::validate_with(__valrs_value, __valrs_ctx) {
+            ::valrs::ValidationResult::Success(validated_val) => {
+                let mut __valrs_issues: Vec<::valrs::ValidationIssue> = Vec::new();
+                #constraint_checks
+                if __valrs_issues.is_empty() {
+                    ::valrs::ValidationResult::Success(#struct_name(validated_val))
+                } else {
+                    __valrs_ctx.truncate(&mut __valrs_issues);
+                    ::valrs::ValidationResult::Failure(__valrs_issues)
+                }
+            }
+            ::valrs::ValidationResult::Failure(__valrs_issues) => ::valrs::ValidationResult::Failure(__valrs_issues),
+        }
+    }
+}
+
+/// Generates validation for a multi-field tuple struct as a fixed-length array.
+///
+/// Like named structs, every position is checked so that all issues are
+/// reported at once, unless the context's issue limit is reached first.
+fn generate_tuple_validation(struct_name: &syn::Ident, fields: &[ParsedField]) -> TokenStream2 {
+    let len = fields.len();
+
+    let field_validations = fields.iter().enumerate().map(|(index, field)| {
+        let field_local = field.local();
+        let field_ty = &field.ty;
+        let constraint_checks = generate_constraint_checks(field, &IssuePath::Index(index));
+        let field_context = field.context();
+
+        quote! {
+            let #field_local: Option<#field_ty> = if __valrs_ctx.is_done(__valrs_issues.len()) {
+                None
+            } else {
+                #field_context
+                match <#field_ty as ::valrs::Valrs>::validate_with(&items[#index], __valrs_ctx)
+                    .with_path_prefix(::valrs::PathSegment::Index(#index))
+                {
+                    ::valrs::ValidationResult::Success(validated_val) => {
+                        #constraint_checks
+                        Some(validated_val)
+                    }
+                    ::valrs::ValidationResult::Failure(errs) => {
+                        __valrs_issues.extend(errs);
+                        None
+                    }
+                }
+            };
+        }
+    });
+
+    let field_locals: Vec<_> = fields.iter().map(ParsedField::local).collect();
+
+    quote! {
+        let items = match ::valrs::validators::expect_array_of_len(__valrs_value, #len) {
+            Ok(items) => items,
+            Err(__valrs_issues) => return ::valrs::ValidationResult::failures(__valrs_issues),
+        };
+
+        let mut __valrs_issues: Vec<::valrs::ValidationIssue> = Vec::new();
+
+        #(#field_validations)*
+
+        match (#(#field_locals,)*) {
+            (#(Some(#field_locals),)*) if __valrs_issues.is_empty() => {
+                ::valrs::ValidationResult::Success(#struct_name(#(#field_locals),*))
+            }
+            _ => {
+                __valrs_ctx.truncate(&mut __valrs_issues);
+                ::valrs::ValidationResult::Failure(__valrs_issues)
+            }
+        }
+    }
+}
+
+pub(crate) fn derive_standard_json_schema_tuple_struct(
+    input: &DeriveInput,
+    fields: &FieldsUnnamed,
+) -> syn::Result<TokenStream2> {
+    let struct_name = &input.ident;
+    let parsed_fields = parse_tuple_fields(input, fields)?;
+
+    if parsed_fields.is_empty() {
+        return Err(Error::new_spanned(
+            struct_name,
+            "StandardJsonSchema derive does not support tuple structs without fields",
+        ));
+    }
+
+    let container_attrs = ContainerAttrs::from_attributes(&input.attrs)?;
+    let metadata_schema =
+        generate_metadata_schema(&container_attrs.metadata, &format_ident!("schema"));
+
+    let methods = SchemaKind::ALL.map(|kind| {
+        let method = kind.method();
+        let schema = generate_tuple_struct_schema(&parsed_fields, kind);
+        quote! {
+            fn #method(target: ::valrs::JsonSchemaTarget) -> ::serde_json::Value {
+                use ::serde_json::Value;
+
+                let mut schema = #schema;
+                #metadata_schema
+
+                // Add $schema for root schemas
+                if let Value::Object(ref mut map) = schema {
+                    let uri = target.schema_uri();
+                    if !uri.is_empty() {
+                        map.insert("$schema".to_string(), Value::String(uri.to_string()));
+                    }
+                }
+
+                schema
+            }
+        }
+    });
+
+    let generics = bounded_generics(input, DerivedTrait::StandardJsonSchema)?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::valrs::StandardJsonSchema for #struct_name #ty_generics #where_clause {
+            #(#methods)*
+        }
+    })
+}
+
+/// Generates an expression for the `kind` schema of a tuple struct.
+fn generate_tuple_struct_schema(parsed_fields: &[ParsedField], kind: SchemaKind) -> TokenStream2 {
+    match parsed_fields {
+        [field] => generate_field_schema(field, kind),
+        _ => {
+            let field_schemas = parsed_fields.iter().map(|f| generate_field_schema(f, kind));
+            quote! {
+                ::valrs::validators::tuple_schema(vec![#(#field_schemas),*], target)
+            }
+        }
+    }
+}
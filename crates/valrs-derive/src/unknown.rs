@@ -0,0 +1,164 @@
+//! Code generation for the unknown-key policies of structs: unknown keys are
+//! ignored by default, reported with `deny_unknown_fields`, or collected into
+//! the `#[schema(extra)]` field with `passthrough`.
+//!
+//! The policy is also expressed in the struct's JSON Schema.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{DeriveInput, Error};
+
+use crate::ParsedField;
+use crate::attrs::{ContainerAttrs, UnknownFields};
+
+/// Checks that the struct's fields fit its unknown-key policy.
+///
+/// Flattened fields read keys that are only known at runtime, so they cannot
+/// be told apart from unknown keys. A `passthrough` struct needs exactly one
+/// `extra` field.
+pub(crate) fn check_unknown_fields(
+    input: &DeriveInput,
+    container_attrs: &ContainerAttrs,
+    fields: &[ParsedField],
+) -> syn::Result<()> {
+    if container_attrs.unknown_fields == UnknownFields::Strip {
+        return Ok(());
+    }
+
+    if let Some(field) = fields.iter().find(|f| f.attrs.flatten) {
+        return Err(Error::new_spanned(
+            &field.ident,
+            "flatten cannot be combined with deny_unknown_fields or passthrough",
+        ));
+    }
+
+    if container_attrs.unknown_fields == UnknownFields::Passthrough {
+        let mut extra = fields.iter().filter(|f| f.attrs.extra);
+        if extra.next().is_none() {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "#[schema(passthrough)] requires a #[schema(extra)] field",
+            ));
+        }
+        if let Some(field) = extra.next() {
+            return Err(Error::new_spanned(
+                &field.ident,
+                "only one field can be #[schema(extra)]",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Generates an expression for the keys read by `fields`, followed by
+/// `extra_keys`, as a `&[&str]`.
+fn generate_known_keys(fields: &[ParsedField], extra_keys: &[&str]) -> TokenStream2 {
+    let keys = fields
+        .iter()
+        .filter(|f| !f.attrs.skip && !f.attrs.extra)
+        .flat_map(|f| {
+            std::iter::once(f.key.as_str()).chain(f.attrs.aliases.iter().map(String::as_str))
+        })
+        .chain(extra_keys.iter().copied());
+    quote! {
+        {
+            let known: &[&str] = &[#(#keys),*];
+            known
+        }
+    }
+}
+
+/// Generates statements pushing an issue to `__valrs_issues` for each key of
+/// `__valrs_obj` that is not a field, under `deny_unknown_fields`. By default
+/// this only happens when the context is strict; `passthrough` structs never
+/// report unknown keys.
+///
+/// `extra_keys` are known besides the fields, such as the tag of an internally
+/// tagged enum.
+pub(crate) fn generate_unknown_key_checks(
+    policy: UnknownFields,
+    fields: &[ParsedField],
+    extra_keys: &[&str],
+) -> TokenStream2 {
+    let known_keys = generate_known_keys(fields, extra_keys);
+    let checks = quote! {
+        let known_keys = #known_keys;
+        for key in __valrs_obj.keys() {
+            if !known_keys.contains(&key.as_str()) && !__valrs_object_ctx.is_enclosing_key(key) {
+                __valrs_issues.push(
+                    ::valrs::ValidationIssue::unknown_key(format!("Unrecognized key '{}'", key), key)
+                        .at(vec![::valrs::PathSegment::Key(key.clone())]),
+                );
+            }
+        }
+    };
+
+    match policy {
+        UnknownFields::Deny => checks,
+        // Keys read by flattened fields are only known at runtime
+        UnknownFields::Strip if fields.iter().any(|f| f.attrs.flatten) => quote! {},
+        UnknownFields::Strip => quote! {
+            if __valrs_ctx.strict() {
+                #checks
+            }
+        },
+        UnknownFields::Passthrough => quote! {},
+    }
+}
+
+/// Generates code binding the `extra` field to the entries of `__valrs_obj`
+/// that are not other fields or enclosing keys. The field type can be any map collected from
+/// `(String, Value)` pairs, such as `serde_json::Map<String, Value>`.
+pub(crate) fn generate_extra_field_validation(
+    field: &ParsedField,
+    fields: &[ParsedField],
+) -> TokenStream2 {
+    let field_local = field.local();
+    let field_ty = &field.ty;
+    let known_keys = generate_known_keys(fields, &[]);
+
+    quote! {
+        let #field_local: Option<#field_ty> = {
+            let known_keys = #known_keys;
+            Some(
+                __valrs_obj.iter()
+                    .filter(|(key, _)| {
+                        !known_keys.contains(&key.as_str())
+                            && !__valrs_object_ctx.is_enclosing_key(key)
+                    })
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect(),
+            )
+        };
+    }
+}
+
+/// Generates statements adding the unknown-key policy to the object schema in
+/// `schema`.
+///
+/// `deny_unknown_fields` becomes `unevaluatedProperties: false` in Draft
+/// 2020-12, which also sees properties from `allOf`, and `additionalProperties:
+/// false` otherwise. `passthrough` becomes `additionalProperties: true`. The
+/// statements expect `target` to be in scope.
+pub(crate) fn generate_unknown_keys_schema(policy: UnknownFields) -> TokenStream2 {
+    let insert = match policy {
+        UnknownFields::Strip => return quote! {},
+        UnknownFields::Deny => quote! {
+            let keyword = match target {
+                ::valrs::JsonSchemaTarget::Draft202012 => "unevaluatedProperties",
+                _ => "additionalProperties",
+            };
+            map.insert(keyword.to_string(), Value::Bool(false));
+        },
+        UnknownFields::Passthrough => quote! {
+            map.insert("additionalProperties".to_string(), Value::Bool(true));
+        },
+    };
+
+    quote! {
+        if let Value::Object(ref mut map) = schema {
+            #insert
+        }
+    }
+}
@@ -0,0 +1,213 @@
+//! Tests for struct-level checks and field presence rules.
+
+use serde_json::json;
+use valrs::{IssueCode, JsonSchemaTarget, PathSegment, StandardJsonSchema, ValidationIssue, Valrs};
+use valrs_derive::{StandardJsonSchema, Valrs};
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+#[schema(check = "Booking::check_dates", check = "Booking::check_guests")]
+struct Booking {
+    start_day: u32,
+    end_day: u32,
+    guests: Vec<String>,
+}
+
+impl Booking {
+    fn check_dates(&self) -> Result<(), String> {
+        if self.end_day > self.start_day {
+            Ok(())
+        } else {
+            Err("end_day must be after start_day".to_string())
+        }
+    }
+
+    fn check_guests(&self) -> Vec<ValidationIssue> {
+        self.guests
+            .iter()
+            .enumerate()
+            .filter(|(_, guest)| guest.is_empty())
+            .map(|(i, _)| {
+                ValidationIssue::with_path(
+                    "Guest name is empty",
+                    vec![PathSegment::Key("guests".into()), PathSegment::Index(i)],
+                )
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+#[schema(
+    rename_all = "camelCase",
+    require_one_of("email", "phone"),
+    require_together("street", "postal_code")
+)]
+struct Contact {
+    #[schema(optional)]
+    email: Option<String>,
+    #[schema(optional, alias = "tel")]
+    phone: Option<String>,
+    #[schema(optional)]
+    street: Option<String>,
+    #[schema(optional)]
+    postal_code: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+#[schema(require_one_of("email", "phone"))]
+struct StrictContact {
+    #[schema(optional, non_nullable)]
+    email: Option<String>,
+    #[schema(optional, non_nullable)]
+    phone: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+#[schema(require_one_of("a", "b"), require_one_of("b", "c"))]
+struct Pairs {
+    #[schema(optional)]
+    a: Option<u8>,
+    #[schema(optional)]
+    b: Option<u8>,
+    #[schema(optional)]
+    c: Option<u8>,
+}
+
+#[test]
+fn test_checks_run_on_valid_struct() {
+    let result = Booking::validate(&json!({ "start_day": 1, "end_day": 3, "guests": ["Ada"] }));
+    assert!(result.is_success());
+
+    let result =
+        Booking::validate(&json!({ "start_day": 3, "end_day": 1, "guests": ["", "Ada", ""] }));
+    let issues = result.issues();
+    assert_eq!(issues.len(), 3);
+    assert_eq!(issues[0].message, "end_day must be after start_day");
+    assert!(issues[0].path.is_none());
+    assert_eq!(issues[1].message, "Guest name is empty");
+    assert_eq!(
+        issues[2].path,
+        Some(vec![
+            PathSegment::Key("guests".into()),
+            PathSegment::Index(2)
+        ])
+    );
+}
+
+#[test]
+fn test_checks_skipped_when_fields_fail() {
+    let result = Booking::validate(&json!({ "start_day": 3, "end_day": "1", "guests": [""] }));
+    assert_eq!(result.issues().len(), 1);
+    assert_eq!(
+        result.issues()[0].path,
+        Some(vec![PathSegment::Key("end_day".into())])
+    );
+}
+
+#[test]
+fn test_require_one_of() {
+    assert!(Contact::validate(&json!({ "email": "ada@example.com" })).is_success());
+    assert!(Contact::validate(&json!({ "tel": "555-0100" })).is_success());
+
+    // A key set to null is present, as for the schema's `required`
+    assert!(Contact::validate(&json!({ "email": null })).is_success());
+
+    let result = Contact::validate(&json!({}));
+    assert_eq!(result.issues().len(), 1);
+    assert_eq!(
+        result.issues()[0].message,
+        "At least one of 'email', 'phone' is required"
+    );
+    assert_eq!(result.issues()[0].path, Some(vec![]));
+    assert_eq!(result.issues()[0].code, IssueCode::MissingField);
+    assert_eq!(
+        result.issues()[0].params.keys,
+        Some(vec!["email".to_string(), "phone".to_string()])
+    );
+
+    // non_nullable fields reject null instead
+    let result = StrictContact::validate(&json!({ "email": null }));
+    assert_eq!(result.issues().len(), 1);
+    assert_eq!(result.issues()[0].code, IssueCode::InvalidType);
+    assert_eq!(
+        StrictContact::validate(&json!({ "phone": "555-0100" })).ok(),
+        Some(StrictContact {
+            email: None,
+            phone: Some("555-0100".to_string()),
+        })
+    );
+}
+
+#[test]
+fn test_require_together() {
+    let valid = json!({ "phone": "555-0100", "street": "Main St", "postalCode": "12345" });
+    assert!(Contact::validate(&valid).is_success());
+
+    let result = Contact::validate(&json!({ "phone": "555-0100", "postalCode": "12345" }));
+    assert_eq!(result.issues().len(), 1);
+    assert_eq!(
+        result.issues()[0].message,
+        "Field 'street' is required when 'postalCode' is present"
+    );
+    assert_eq!(result.issues()[0].code, IssueCode::MissingField);
+    assert_eq!(
+        result.issues()[0].path,
+        Some(vec![PathSegment::Key("street".into())])
+    );
+
+    // A key set to null is present
+    let result = Contact::validate(&json!({ "phone": "555-0100", "postalCode": null }));
+    assert_eq!(
+        result.issues()[0].path,
+        Some(vec![PathSegment::Key("street".into())])
+    );
+}
+
+#[test]
+fn test_presence_schema() {
+    let schema = Contact::json_schema_input(JsonSchemaTarget::Draft202012);
+    assert_eq!(
+        schema["anyOf"],
+        json!([{ "required": ["email"] }, { "required": ["phone"] }])
+    );
+    assert_eq!(
+        schema["dependentRequired"],
+        json!({ "postalCode": ["street"], "street": ["postalCode"] })
+    );
+
+    let schema = Contact::json_schema_input(JsonSchemaTarget::Draft07);
+    assert_eq!(schema["dependencies"]["street"], json!(["postalCode"]));
+    assert_eq!(schema.get("dependentRequired"), None);
+}
+
+#[test]
+fn test_presence_schema_openapi() {
+    let schema = Contact::json_schema_input(JsonSchemaTarget::OpenApi30);
+    assert_eq!(schema.get("dependentRequired"), None);
+    assert_eq!(
+        schema["allOf"],
+        json!([
+            { "anyOf": [{ "required": ["email"] }, { "required": ["phone"] }] },
+            {
+                "anyOf": [
+                    { "required": ["street", "postalCode"] },
+                    { "not": { "anyOf": [{ "required": ["street"] }, { "required": ["postalCode"] }] } }
+                ]
+            }
+        ])
+    );
+}
+
+#[test]
+fn test_several_rules_combined() {
+    let result = Pairs::validate(&json!({ "a": 1 }));
+    assert_eq!(
+        result.issues()[0].message,
+        "At least one of 'b', 'c' is required"
+    );
+    assert!(Pairs::validate(&json!({ "b": 1 })).is_success());
+
+    let schema = Pairs::json_schema_input(JsonSchemaTarget::Draft202012);
+    assert_eq!(schema["allOf"].as_array().unwrap().len(), 2);
+    assert_eq!(schema.get("anyOf"), None);
+}
@@ -0,0 +1,129 @@
+//! Tests for coercion mode: per call with `ValidationContext::with_coerce`, and
+//! per field with `#[schema(coerce)]`.
+
+use serde_json::json;
+use valrs::{
+    IssueCode, JsonSchemaTarget, PathSegment, StandardJsonSchema, ValidationContext, Valrs,
+};
+use valrs_derive::{StandardJsonSchema, Valrs};
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct Query {
+    #[schema(coerce, maximum = 100)]
+    limit: u32,
+    #[schema(coerce, optional)]
+    verbose: Option<bool>,
+    #[schema(coerce)]
+    tags: Vec<String>,
+    offset: u32,
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct Port(#[schema(coerce)] u16);
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct Settings {
+    port: u16,
+    debug: bool,
+}
+
+#[test]
+fn test_coerce_field_converts_strings() {
+    let result = Query::validate(&json!({
+        "limit": "20",
+        "verbose": "1",
+        "tags": [1, "two"],
+        "offset": 5
+    }));
+    assert_eq!(
+        result.ok(),
+        Some(Query {
+            limit: 20,
+            verbose: Some(true),
+            tags: vec!["1".to_string(), "two".to_string()],
+            offset: 5,
+        })
+    );
+    assert_eq!(Port::validate(&json!("8080")).ok(), Some(Port(8080)));
+}
+
+#[test]
+fn test_coerce_field_checks_converted_values() {
+    let result = Query::validate(&json!({ "limit": "200", "tags": [], "offset": "5" }));
+    let issues = result.issues();
+    assert_eq!(issues.len(), 2);
+    assert_eq!(
+        issues[0].message,
+        "Number must be less than or equal to 100, got 200"
+    );
+    assert_eq!(
+        issues[0].path,
+        Some(vec![PathSegment::Key("limit".to_string())])
+    );
+
+    // Only fields with `coerce` convert their input
+    assert_eq!(issues[1].code, IssueCode::InvalidType);
+    assert_eq!(
+        issues[1].path,
+        Some(vec![PathSegment::Key("offset".to_string())])
+    );
+
+    let result = Port::validate(&json!("http"));
+    assert_eq!(
+        result.issues()[0].message,
+        "Expected non-negative integer, could not convert 'http'"
+    );
+    assert_eq!(
+        Port::validate(&json!("70000")).issues()[0].code,
+        IssueCode::TooBig
+    );
+}
+
+#[test]
+fn test_coerce_context_applies_to_all_fields() {
+    let value = json!({ "port": "8080", "debug": "false" });
+    assert_eq!(Settings::validate(&value).issues().len(), 2);
+
+    let ctx = ValidationContext::new().with_coerce(true);
+    assert_eq!(
+        Settings::validate_with(&value, &ctx).ok(),
+        Some(Settings {
+            port: 8080,
+            debug: false,
+        })
+    );
+}
+
+#[test]
+fn test_coerce_field_json_schema() {
+    let schema = Query::json_schema_input(JsonSchemaTarget::OpenApi30);
+    assert_eq!(
+        schema["properties"]["limit"],
+        json!({
+            "anyOf": [
+                { "type": "integer" },
+                { "type": "string", "pattern": "^-?(0|[1-9][0-9]*)$" }
+            ],
+            "maximum": 100
+        })
+    );
+    assert_eq!(
+        schema["properties"]["tags"]["items"],
+        json!({ "anyOf": [{ "type": "string" }, { "type": "number" }] })
+    );
+    assert_eq!(schema["properties"]["offset"], json!({ "type": "integer" }));
+
+    let output = Query::json_schema_output(JsonSchemaTarget::OpenApi30);
+    assert_eq!(output["properties"]["limit"]["type"], "integer");
+
+    let coerced = Settings::json_schema_coerced_input(JsonSchemaTarget::OpenApi30);
+    assert_eq!(
+        coerced["properties"]["debug"],
+        json!({
+            "anyOf": [
+                { "type": "boolean" },
+                { "type": "string", "enum": ["true", "false", "1", "0"] }
+            ]
+        })
+    );
+}
@@ -0,0 +1,144 @@
+//! Tests for collection constraint attributes.
+
+use serde_json::json;
+use valrs::{IssueCode, JsonSchemaTarget, PathSegment, StandardJsonSchema, Valrs};
+use valrs_derive::{StandardJsonSchema, Valrs};
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct Post {
+    #[schema(
+        min_items = 1,
+        max_items = 3,
+        unique_items,
+        items(min_length = 1, max_length = 8, pattern = "^[a-z]+$")
+    )]
+    tags: Vec<String>,
+    #[schema(optional, items(minimum = 1, maximum = 5))]
+    ratings: Option<Vec<u8>>,
+    #[schema(items(items(exclusive_maximum = 10)), max_items = 2)]
+    grid: Vec<Vec<i32>>,
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct Ids(#[schema(unique_items(message = "Id repeats index {index}"))] Vec<u32>);
+
+fn key(name: &str) -> PathSegment {
+    PathSegment::Key(name.into())
+}
+
+#[test]
+fn test_valid_collections() {
+    let result = Post::validate(&json!({
+        "tags": ["rust", "web"],
+        "ratings": [5, 1],
+        "grid": [[1, 9], []]
+    }));
+    assert_eq!(
+        result.ok(),
+        Some(Post {
+            tags: vec!["rust".into(), "web".into()],
+            ratings: Some(vec![5, 1]),
+            grid: vec![vec![1, 9], vec![]],
+        })
+    );
+}
+
+#[test]
+fn test_size_issues_point_at_the_array() {
+    let result = Post::validate(&json!({ "tags": [], "grid": [[], [], []] }));
+    let issues = result.issues();
+    assert_eq!(issues.len(), 2);
+    assert_eq!(
+        issues[0].message,
+        "Array must contain at least 1 items, got 0"
+    );
+    assert_eq!(issues[0].path, Some(vec![key("tags")]));
+    assert_eq!(
+        issues[1].message,
+        "Array must contain at most 2 items, got 3"
+    );
+    assert_eq!(issues[1].path, Some(vec![key("grid")]));
+}
+
+#[test]
+fn test_item_issues_point_at_the_index() {
+    let result = Post::validate(&json!({
+        "tags": ["rust", "Web", "rust"],
+        "ratings": [3, 0],
+        "grid": [[1], [2, 10]]
+    }));
+    let issues: Vec<_> = result
+        .issues()
+        .iter()
+        .map(|i| (i.message.as_str(), i.path.clone().unwrap()))
+        .collect();
+    assert_eq!(
+        issues,
+        [
+            (
+                "Duplicate item, same as index 0",
+                vec![key("tags"), PathSegment::Index(2)]
+            ),
+            (
+                "String does not match pattern",
+                vec![key("tags"), PathSegment::Index(1)]
+            ),
+            (
+                "Number must be greater than or equal to 1, got 0",
+                vec![key("ratings"), PathSegment::Index(1)]
+            ),
+            (
+                "Number must be less than 10, got 10",
+                vec![key("grid"), PathSegment::Index(1), PathSegment::Index(1)]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_newtype_unique_items_message() {
+    let result = Ids::validate(&json!([1, 2, 1]));
+    assert_eq!(result.issues()[0].message, "Id repeats index 0");
+    assert_eq!(result.issues()[0].path, Some(vec![PathSegment::Index(2)]));
+    assert_eq!(result.issues()[0].code, IssueCode::NotUnique);
+    assert_eq!(result.issues()[0].params.index, Some(0));
+    assert_eq!(Ids::validate(&json!([1, 2])).ok(), Some(Ids(vec![1, 2])));
+}
+
+#[test]
+fn test_collection_schema() {
+    let schema = Post::json_schema_input(JsonSchemaTarget::Draft202012);
+    assert_eq!(
+        schema["properties"]["tags"],
+        json!({
+            "type": "array",
+            "minItems": 1,
+            "maxItems": 3,
+            "uniqueItems": true,
+            "items": {
+                "type": "string",
+                "minLength": 1,
+                "maxLength": 8,
+                "pattern": "^[a-z]+$"
+            }
+        })
+    );
+    assert_eq!(
+        schema["properties"]["ratings"]["anyOf"][0]["items"]["maximum"],
+        5
+    );
+    assert_eq!(
+        schema["properties"]["grid"]["items"]["items"]["exclusiveMaximum"],
+        10
+    );
+
+    let schema = Post::json_schema_input(JsonSchemaTarget::OpenApi30);
+    assert_eq!(
+        schema["properties"]["grid"]["items"]["items"]["exclusiveMaximum"],
+        true
+    );
+    assert_eq!(
+        Ids::json_schema_input(JsonSchemaTarget::Draft07)["uniqueItems"],
+        true
+    );
+}
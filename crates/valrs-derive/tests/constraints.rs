@@ -0,0 +1,211 @@
+//! Tests for numeric constraint attributes.
+
+use serde_json::json;
+use valrs::{JsonSchemaTarget, PathSegment, StandardJsonSchema, Valrs};
+use valrs_derive::{StandardJsonSchema, Valrs};
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct Settings {
+    #[schema(minimum = 0, maximum = 100)]
+    volume: u8,
+    #[schema(exclusive_minimum = 0.0, maximum = 1.5)]
+    ratio: f64,
+    #[schema(multiple_of = 5)]
+    step: i32,
+    #[schema(minimum = -10, exclusive_maximum = 10)]
+    offset: i64,
+    #[schema(optional, minimum = 1)]
+    retries: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct Percent(#[schema(minimum = 0, maximum = 100)] f64);
+
+/// Bounds next to 2^53, which rounds them as a float.
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct Ids {
+    #[schema(minimum = 9_007_199_254_740_993)]
+    first: i64,
+    #[schema(maximum = 18_446_744_073_709_551_614, multiple_of = 2)]
+    last: u64,
+}
+
+fn valid() -> serde_json::Value {
+    json!({ "volume": 50, "ratio": 0.5, "step": 15, "offset": -10 })
+}
+
+#[test]
+fn test_valid_numbers() {
+    let result = Settings::validate(&valid());
+    assert_eq!(
+        result.ok(),
+        Some(Settings {
+            volume: 50,
+            ratio: 0.5,
+            step: 15,
+            offset: -10,
+            retries: None,
+        })
+    );
+}
+
+#[test]
+fn test_bound_messages() {
+    let cases = [
+        (
+            "volume",
+            json!(101),
+            "Number must be less than or equal to 100, got 101",
+        ),
+        ("ratio", json!(0), "Number must be greater than 0, got 0"),
+        (
+            "ratio",
+            json!(2.5),
+            "Number must be less than or equal to 1.5, got 2.5",
+        ),
+        ("step", json!(12), "Number must be a multiple of 5, got 12"),
+        (
+            "offset",
+            json!(-11),
+            "Number must be greater than or equal to -10, got -11",
+        ),
+        ("offset", json!(10), "Number must be less than 10, got 10"),
+        (
+            "retries",
+            json!(0),
+            "Number must be greater than or equal to 1, got 0",
+        ),
+    ];
+
+    for (key, value, message) in cases {
+        let mut input = valid();
+        input[key] = value;
+        let result = Settings::validate(&input);
+        assert_eq!(result.issues().len(), 1, "{}", key);
+        assert_eq!(result.issues()[0].message, message);
+        assert_eq!(
+            result.issues()[0].path,
+            Some(vec![PathSegment::Key(key.into())])
+        );
+    }
+}
+
+#[test]
+fn test_newtype_bounds() {
+    assert_eq!(Percent::validate(&json!(12.5)).ok(), Some(Percent(12.5)));
+
+    let result = Percent::validate(&json!(-1));
+    assert_eq!(
+        result.issues()[0].message,
+        "Number must be greater than or equal to 0, got -1"
+    );
+    assert!(result.issues()[0].path.is_none());
+}
+
+#[test]
+fn test_large_integer_bounds() {
+    let input = json!({ "first": 9_007_199_254_740_993i64, "last": 18_446_744_073_709_551_614u64 });
+    assert!(Ids::validate(&input).is_success());
+
+    let input = json!({ "first": 9_007_199_254_740_992i64, "last": u64::MAX });
+    let result = Ids::validate(&input);
+    let issues = result.issues();
+    assert_eq!(issues.len(), 3);
+    assert_eq!(
+        issues[0].message,
+        "Number must be greater than or equal to 9007199254740993, got 9007199254740992"
+    );
+    assert_eq!(
+        issues[0].params.minimum,
+        Some(9_007_199_254_740_993i64.into())
+    );
+    assert_eq!(
+        issues[1].params.maximum,
+        Some(18_446_744_073_709_551_614u64.into())
+    );
+    assert_eq!(
+        issues[2].message,
+        "Number must be a multiple of 2, got 18446744073709551615"
+    );
+
+    let schema = Ids::json_schema_input(JsonSchemaTarget::Draft202012);
+    assert_eq!(
+        schema["properties"]["first"]["minimum"],
+        json!(9_007_199_254_740_993i64)
+    );
+    assert_eq!(
+        schema["properties"]["last"]["maximum"],
+        json!(18_446_744_073_709_551_614u64)
+    );
+}
+
+#[test]
+fn test_json_schema_keywords() {
+    let schema = Settings::json_schema_input(JsonSchemaTarget::Draft202012);
+    let properties = &schema["properties"];
+    assert_eq!(
+        properties["volume"],
+        json!({ "type": "integer", "minimum": 0, "maximum": 100 })
+    );
+    assert_eq!(
+        properties["ratio"],
+        json!({ "type": "number", "exclusiveMinimum": 0.0, "maximum": 1.5 })
+    );
+    assert_eq!(properties["step"]["multipleOf"], 5);
+    assert_eq!(properties["offset"]["minimum"], -10);
+    assert_eq!(properties["offset"]["exclusiveMaximum"], 10);
+    assert_eq!(properties["retries"]["anyOf"][0]["minimum"], 1);
+    assert_eq!(properties["retries"]["anyOf"][1], json!({ "type": "null" }));
+}
+
+#[test]
+fn test_openapi_exclusive_bounds() {
+    let schema = Settings::json_schema_input(JsonSchemaTarget::OpenApi30);
+    let properties = &schema["properties"];
+    assert_eq!(
+        properties["ratio"],
+        json!({
+            "type": "number",
+            "minimum": 0.0,
+            "exclusiveMinimum": true,
+            "maximum": 1.5
+        })
+    );
+    assert_eq!(properties["offset"]["maximum"], 10);
+    assert_eq!(properties["offset"]["exclusiveMaximum"], true);
+    assert_eq!(properties["volume"].get("exclusiveMinimum"), None);
+}
+
+#[test]
+fn test_exclusive_bounds_inside_wrappers() {
+    let targets = [
+        JsonSchemaTarget::Draft07,
+        JsonSchemaTarget::Draft202012,
+        JsonSchemaTarget::OpenApi30,
+    ];
+    for target in targets {
+        // The nullable wrapper is an anyOf in JSON Schema
+        let unwrap = |schema: &serde_json::Value| match target {
+            JsonSchemaTarget::OpenApi30 => schema.clone(),
+            _ => schema["anyOf"][0].clone(),
+        };
+        let schemas = [
+            unwrap(&<Option<Settings>>::json_schema_input(target)),
+            unwrap(&<valrs::Maybe<Settings>>::json_schema_input(target)),
+            unwrap(&<Vec<Option<Settings>>>::json_schema_input(target)["items"]),
+        ];
+        for schema in schemas {
+            let ratio = &schema["properties"]["ratio"];
+            let offset = &schema["properties"]["offset"];
+            if target == JsonSchemaTarget::OpenApi30 {
+                assert_eq!(ratio["minimum"], 0.0);
+                assert_eq!(ratio["exclusiveMinimum"], true);
+                assert_eq!(offset["exclusiveMaximum"], true);
+            } else {
+                assert_eq!(ratio["exclusiveMinimum"], 0.0);
+                assert_eq!(ratio.get("minimum"), None);
+                assert_eq!(offset["exclusiveMaximum"], 10);
+            }
+        }
+    }
+}
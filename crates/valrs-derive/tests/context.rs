@@ -0,0 +1,232 @@
+//! Tests for validating derived types with a `ValidationContext`.
+
+use serde_json::json;
+use valrs::{PathSegment, ValidationContext, Valrs};
+use valrs_derive::Valrs;
+
+/// The tenant of the current request, passed to custom rules.
+struct Tenant {
+    prefix: &'static str,
+}
+
+mod rules {
+    use valrs::ValidationContext;
+
+    use super::{Order, Tenant};
+
+    pub fn tenant_sku(sku: &str, ctx: &ValidationContext) -> Result<(), String> {
+        match ctx.extension::<Tenant>() {
+            Some(tenant) if !sku.starts_with(tenant.prefix) => {
+                Err(format!("SKU must start with '{}'", tenant.prefix))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn quantity_limit(order: &Order, ctx: &ValidationContext) -> Result<(), String> {
+        if ctx.strict() && order.quantity > 10 {
+            Err("At most 10 items per order".to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Valrs)]
+#[schema(check_with_context = "rules::quantity_limit")]
+struct Order {
+    #[schema(validate_with_context = "rules::tenant_sku")]
+    sku: String,
+    quantity: u32,
+    #[schema(optional)]
+    note: Option<String>,
+}
+
+/// A hand-written type implementing only `validate`.
+#[derive(Debug, PartialEq)]
+struct Even(u32);
+
+impl Valrs for Even {
+    type Input = u32;
+    type Output = Even;
+
+    fn validate(value: &serde_json::Value) -> valrs::ValidationResult<Self::Output> {
+        match value.as_u64() {
+            Some(n) if n % 2 == 0 => valrs::ValidationResult::success(Even(n as u32)),
+            _ => valrs::ValidationResult::failure("Expected even number"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Valrs)]
+struct Pair {
+    left: Even,
+    right: Even,
+}
+
+#[derive(Debug, PartialEq, Valrs)]
+#[schema(tag = "kind")]
+enum Item {
+    Product(Order),
+    Voucher { code: String },
+}
+
+/// Fields named like the bindings of the generated `validate_with`.
+#[derive(Debug, PartialEq, Valrs)]
+#[schema(check = "Bindings::check")]
+struct Bindings {
+    ctx: String,
+    #[schema(minimum = 0)]
+    issues: i32,
+    #[schema(optional)]
+    obj: Option<bool>,
+    #[schema(coerce)]
+    value: u32,
+}
+
+impl Bindings {
+    fn check(&self) -> Result<(), String> {
+        if self.ctx.is_empty() {
+            Err("ctx must not be empty".to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Valrs)]
+struct Batch {
+    orders: Vec<Order>,
+}
+
+#[test]
+fn test_default_context_reports_every_issue() {
+    let result = Order::validate(&json!({ "sku": 1, "quantity": -1, "note": 2 }));
+    assert_eq!(result.issues().len(), 3);
+}
+
+#[test]
+fn test_abort_early_stops_at_first_issue() {
+    let ctx = ValidationContext::new().with_abort_early(true);
+    let result = Order::validate_with(&json!({ "sku": 1, "quantity": -1, "note": 2 }), &ctx);
+    let issues = result.issues();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(
+        issues[0].path,
+        Some(vec![PathSegment::Key("sku".to_string())])
+    );
+
+    let batch = json!({ "orders": [{ "sku": 1, "quantity": 1 }, { "sku": 2, "quantity": 2 }] });
+    assert_eq!(Batch::validate_with(&batch, &ctx).issues().len(), 1);
+    assert_eq!(Batch::validate(&batch).issues().len(), 2);
+}
+
+#[test]
+fn test_max_issues_caps_issue_count() {
+    let ctx = ValidationContext::new().with_max_issues(2);
+    let orders: Vec<_> = (0..5).map(|_| json!({ "sku": 1, "quantity": 1 })).collect();
+    let result = Batch::validate_with(&json!({ "orders": orders }), &ctx);
+    let issues = result.issues();
+    assert_eq!(issues.len(), 2);
+    assert_eq!(
+        issues[1].path,
+        Some(vec![
+            PathSegment::Key("orders".to_string()),
+            PathSegment::Index(1),
+            PathSegment::Key("sku".to_string()),
+        ])
+    );
+}
+
+#[test]
+fn test_strict_context_rejects_unknown_keys() {
+    let value = json!({ "sku": "A-1", "quantity": 1, "coupon": "FREE" });
+    assert!(Order::validate(&value).is_success());
+
+    let ctx = ValidationContext::new().with_strict(true);
+    let result = Order::validate_with(&value, &ctx);
+    let issues = result.issues();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].message, "Unrecognized key 'coupon'");
+}
+
+#[test]
+fn test_custom_rules_read_the_context() {
+    let value = json!({ "sku": "B-1", "quantity": 20 });
+    assert!(Order::validate(&value).is_success());
+
+    let ctx = ValidationContext::new()
+        .with_strict(true)
+        .with_extension(Tenant { prefix: "A-" });
+    let result = Order::validate_with(&value, &ctx);
+    let issues = result.issues();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].message, "SKU must start with 'A-'");
+
+    let result = Order::validate_with(&json!({ "sku": "A-1", "quantity": 20 }), &ctx);
+    assert_eq!(result.issues()[0].message, "At most 10 items per order");
+    assert_eq!(result.issues()[0].path, None);
+}
+
+#[test]
+fn test_validate_only_impls_accept_a_context() {
+    let value = json!({ "left": 1, "right": 3 });
+    assert_eq!(
+        Even::validate_with(&json!(4), &ValidationContext::new()).ok(),
+        Some(Even(4))
+    );
+    assert_eq!(Pair::validate(&value).issues().len(), 2);
+
+    let ctx = ValidationContext::new().with_abort_early(true);
+    assert_eq!(Pair::validate_with(&value, &ctx).issues().len(), 1);
+}
+
+#[test]
+fn test_strict_context_applies_to_enum_variants() {
+    let ctx = ValidationContext::new().with_strict(true);
+    let voucher = json!({ "kind": "Voucher", "code": "FREE" });
+    assert_eq!(
+        Item::validate_with(&voucher, &ctx).ok(),
+        Some(Item::Voucher {
+            code: "FREE".to_string()
+        })
+    );
+
+    let value = json!({ "kind": "Voucher", "code": "FREE", "amount": 5 });
+    assert!(Item::validate(&value).is_success());
+    let result = Item::validate_with(&value, &ctx);
+    assert_eq!(result.issues().len(), 1);
+    assert_eq!(result.issues()[0].message, "Unrecognized key 'amount'");
+
+    // The tag of an internally tagged newtype variant is not unknown
+    let product = json!({ "kind": "Product", "sku": "A-1", "quantity": 1 });
+    assert!(Item::validate_with(&product, &ctx).is_success());
+    let product = json!({ "kind": "Product", "sku": "A-1", "quantity": 1, "coupon": "X" });
+    assert_eq!(
+        Item::validate_with(&product, &ctx).issues()[0].path,
+        Some(vec![PathSegment::Key("coupon".to_string())])
+    );
+}
+
+#[test]
+fn test_fields_named_like_generated_bindings() {
+    let value = json!({ "ctx": "a", "issues": 1, "obj": true, "value": "2" });
+    assert_eq!(
+        Bindings::validate(&value).ok(),
+        Some(Bindings {
+            ctx: "a".to_string(),
+            issues: 1,
+            obj: Some(true),
+            value: 2,
+        })
+    );
+
+    let result = Bindings::validate(&json!({ "ctx": "", "issues": -1, "value": 1 }));
+    assert_eq!(result.issues().len(), 1);
+    let result = Bindings::validate(&json!({ "ctx": "", "issues": 1, "value": 1 }));
+    assert_eq!(result.issues()[0].message, "ctx must not be empty");
+
+    let ctx = ValidationContext::new().with_abort_early(true);
+    let result = Bindings::validate_with(&json!({ "ctx": 1, "issues": -1, "obj": 2 }), &ctx);
+    assert_eq!(result.issues().len(), 1);
+}
@@ -0,0 +1,125 @@
+//! Tests for field defaults and the input/output schemas they produce.
+
+use serde_json::json;
+use valrs::{JsonSchemaTarget, StandardJsonSchema, Valrs};
+use valrs_derive::{StandardJsonSchema, Valrs};
+
+fn default_host() -> String {
+    "localhost".to_string()
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct Server {
+    #[schema(default = "default_host")]
+    host: String,
+    #[schema(default = 8080)]
+    port: u16,
+    #[schema(default = -1.5)]
+    offset: f64,
+    #[schema(default = true)]
+    secure: bool,
+    #[schema(default)]
+    tags: Vec<String>,
+    name: String,
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct Cluster {
+    primary: Server,
+    #[schema(optional)]
+    backup: Option<Server>,
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+enum Event {
+    Restart {
+        #[schema(default = 3)]
+        attempts: u8,
+    },
+}
+
+#[test]
+fn test_missing_fields_use_defaults() {
+    let result = Server::validate(&json!({ "name": "api" }));
+    assert_eq!(
+        result.ok(),
+        Some(Server {
+            host: "localhost".into(),
+            port: 8080,
+            offset: -1.5,
+            secure: true,
+            tags: vec![],
+            name: "api".into(),
+        })
+    );
+
+    let result = Server::validate(&json!({ "name": "api", "port": 443, "tags": ["a"] }));
+    let server = result.ok().unwrap();
+    assert_eq!(server.port, 443);
+    assert_eq!(server.tags, vec!["a".to_string()]);
+}
+
+#[test]
+fn test_present_fields_are_still_validated() {
+    let result = Server::validate(&json!({ "name": "api", "port": "443" }));
+    assert!(result.is_failure());
+
+    let result = Server::validate(&json!({}));
+    assert_eq!(result.issues().len(), 1);
+    assert_eq!(result.issues()[0].message, "Missing required field 'name'");
+}
+
+#[test]
+fn test_input_schema_defaults() {
+    let schema = Server::json_schema_input(JsonSchemaTarget::Draft202012);
+    assert_eq!(schema["required"], json!(["name"]));
+
+    let properties = &schema["properties"];
+    assert_eq!(properties["port"]["default"], 8080);
+    assert_eq!(properties["offset"]["default"], -1.5);
+    assert_eq!(properties["secure"]["default"], true);
+    assert_eq!(properties["host"].get("default"), None);
+    assert_eq!(properties["tags"].get("default"), None);
+}
+
+#[test]
+fn test_output_schema_requires_defaulted_fields() {
+    let schema = Server::json_schema_output(JsonSchemaTarget::Draft202012);
+    assert_eq!(
+        schema["required"],
+        json!(["host", "port", "offset", "secure", "tags", "name"])
+    );
+    assert_eq!(schema["properties"]["port"].get("default"), None);
+}
+
+#[test]
+fn test_output_schema_is_nested() {
+    let input = Cluster::json_schema_input(JsonSchemaTarget::OpenApi30);
+    let output = Cluster::json_schema_output(JsonSchemaTarget::OpenApi30);
+    assert_eq!(input["properties"]["primary"]["required"], json!(["name"]));
+    assert_eq!(
+        output["properties"]["primary"]["required"]
+            .as_array()
+            .unwrap()
+            .len(),
+        6
+    );
+    assert_eq!(
+        output["properties"]["backup"]["required"]
+            .as_array()
+            .unwrap()
+            .len(),
+        6
+    );
+    assert_eq!(output["required"], json!(["primary"]));
+}
+
+#[test]
+fn test_enum_variant_defaults() {
+    let result = Event::validate(&json!({ "Restart": {} }));
+    assert_eq!(result.ok(), Some(Event::Restart { attempts: 3 }));
+
+    let input = Event::json_schema_input(JsonSchemaTarget::Draft202012);
+    let output = Event::json_schema_output(JsonSchemaTarget::Draft202012);
+    assert_ne!(input, output);
+}
@@ -0,0 +1,394 @@
+//! Tests for deriving `Valrs` and `StandardJsonSchema` on enums.
+
+use serde_json::json;
+use valrs::{IssueCode, JsonSchemaTarget, PathSegment, StandardJsonSchema, Valrs};
+use valrs_derive::{StandardJsonSchema, Valrs};
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+enum Status {
+    Active,
+    Suspended,
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+#[schema(rename_all = "snake_case")]
+enum Plan {
+    FreeTier,
+    #[schema(rename = "pro")]
+    Professional,
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+enum Placeholder {
+    #[schema(rename = "{x}")]
+    Braced,
+}
+
+#[derive(Debug, Valrs, StandardJsonSchema)]
+struct Account {
+    status: Status,
+}
+
+#[test]
+fn test_unit_enum_validation() {
+    assert_eq!(
+        Status::validate(&json!("Active")).ok(),
+        Some(Status::Active)
+    );
+    assert_eq!(
+        Status::validate(&json!("Suspended")).ok(),
+        Some(Status::Suspended)
+    );
+    assert!(Status::validate(&json!("active")).is_failure());
+    assert!(Status::validate(&json!(0)).is_failure());
+}
+
+#[test]
+fn test_unit_enum_error_message() {
+    let result = Status::validate(&json!("Deleted"));
+    assert_eq!(
+        result.issues()[0].message,
+        r#"Expected one of "Active", "Suspended", got "Deleted""#
+    );
+
+    let result = Status::validate(&json!(null));
+    assert_eq!(result.issues()[0].message, "Expected string");
+
+    // Names are not part of the message template
+    assert_eq!(
+        Placeholder::validate(&json!("{x}")).ok(),
+        Some(Placeholder::Braced)
+    );
+    let result = Placeholder::validate(&json!("x"));
+    assert_eq!(
+        result.issues()[0].message,
+        r#"Expected one of "{x}", got "x""#
+    );
+}
+
+#[test]
+fn test_unit_enum_renames() {
+    assert_eq!(
+        Plan::validate(&json!("free_tier")).ok(),
+        Some(Plan::FreeTier)
+    );
+    assert_eq!(Plan::validate(&json!("pro")).ok(), Some(Plan::Professional));
+    assert!(Plan::validate(&json!("FreeTier")).is_failure());
+    assert!(Plan::validate(&json!("professional")).is_failure());
+}
+
+#[test]
+fn test_unit_enum_json_schema() {
+    let schema = Plan::json_schema_input(JsonSchemaTarget::Draft202012);
+    assert_eq!(schema["type"], "string");
+    assert_eq!(schema["enum"], json!(["free_tier", "pro"]));
+    assert_eq!(
+        schema["$schema"],
+        "https://json-schema.org/draft/2020-12/schema"
+    );
+
+    let schema = Status::json_schema_input(JsonSchemaTarget::OpenApi30);
+    assert_eq!(schema["enum"], json!(["Active", "Suspended"]));
+    assert!(schema.get("$schema").is_none());
+}
+
+#[test]
+fn test_unit_enum_as_struct_field() {
+    let result = Account::validate(&json!({ "status": "Suspended" }));
+    assert_eq!(result.ok().map(|a| a.status), Some(Status::Suspended));
+
+    let schema = Account::json_schema_input(JsonSchemaTarget::OpenApi30);
+    assert_eq!(
+        schema["properties"]["status"]["enum"],
+        json!(["Active", "Suspended"])
+    );
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Line(Point, Point),
+    Rect { width: u32, height: u32 },
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+#[schema(tag = "type", rename_all = "snake_case")]
+enum Event {
+    Ping,
+    Moved(Point),
+    UserCreated {
+        name: String,
+        #[schema(optional)]
+        email: Option<String>,
+    },
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+#[schema(tag = "t", content = "c")]
+enum Message {
+    Quit,
+    Text(String),
+    Pair(i32, i32),
+    Move { x: i32, y: i32 },
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+#[schema(untagged)]
+enum Scalar {
+    Nothing,
+    Number(i64),
+    Word(String),
+    Coords { lat: f64, lng: f64 },
+}
+
+#[test]
+fn test_externally_tagged_validation() {
+    assert_eq!(Shape::validate(&json!("Empty")).ok(), Some(Shape::Empty));
+    assert_eq!(
+        Shape::validate(&json!({ "Circle": 1.5 })).ok(),
+        Some(Shape::Circle(1.5))
+    );
+    assert_eq!(
+        Shape::validate(&json!({ "Line": [{ "x": 0, "y": 0 }, { "x": 1, "y": 2 }] })).ok(),
+        Some(Shape::Line(Point { x: 0, y: 0 }, Point { x: 1, y: 2 }))
+    );
+    assert_eq!(
+        Shape::validate(&json!({ "Rect": { "width": 3, "height": 4 } })).ok(),
+        Some(Shape::Rect {
+            width: 3,
+            height: 4
+        })
+    );
+    assert!(Shape::validate(&json!("Circle")).is_failure());
+    assert!(Shape::validate(&json!({ "Circle": 1, "Empty": null })).is_failure());
+}
+
+#[test]
+fn test_externally_tagged_issue_paths() {
+    let result = Shape::validate(&json!({ "Rect": { "width": "3" } }));
+    let issues = result.issues();
+    assert_eq!(issues.len(), 2);
+    assert_eq!(
+        issues[0].path,
+        Some(vec![
+            PathSegment::Key("Rect".into()),
+            PathSegment::Key("width".into())
+        ])
+    );
+    assert_eq!(
+        issues[1].path,
+        Some(vec![
+            PathSegment::Key("Rect".into()),
+            PathSegment::Key("height".into())
+        ])
+    );
+
+    let result = Shape::validate(&json!({ "Line": [{ "x": 0, "y": 0 }, { "x": 1 }] }));
+    assert_eq!(
+        result.issues()[0].path,
+        Some(vec![
+            PathSegment::Key("Line".into()),
+            PathSegment::Index(1),
+            PathSegment::Key("y".into())
+        ])
+    );
+}
+
+#[test]
+fn test_internally_tagged_validation() {
+    assert_eq!(
+        Event::validate(&json!({ "type": "ping" })).ok(),
+        Some(Event::Ping)
+    );
+    assert_eq!(
+        Event::validate(&json!({ "type": "moved", "x": 1, "y": 2 })).ok(),
+        Some(Event::Moved(Point { x: 1, y: 2 }))
+    );
+    assert_eq!(
+        Event::validate(&json!({ "type": "user_created", "name": "Ada" })).ok(),
+        Some(Event::UserCreated {
+            name: "Ada".into(),
+            email: None
+        })
+    );
+}
+
+#[test]
+fn test_internally_tagged_reports_only_matching_variant() {
+    let result = Event::validate(&json!({ "type": "user_created", "name": 7 }));
+    let issues = result.issues();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].path, Some(vec![PathSegment::Key("name".into())]));
+
+    let result = Event::validate(&json!({ "type": "deleted" }));
+    assert_eq!(result.issues().len(), 1);
+    assert_eq!(
+        result.issues()[0].path,
+        Some(vec![PathSegment::Key("type".into())])
+    );
+
+    let result = Event::validate(&json!({ "name": "Ada" }));
+    assert_eq!(result.issues()[0].message, "Missing required field 'type'");
+}
+
+#[test]
+fn test_adjacently_tagged_validation() {
+    assert_eq!(
+        Message::validate(&json!({ "t": "Quit" })).ok(),
+        Some(Message::Quit)
+    );
+    assert_eq!(
+        Message::validate(&json!({ "t": "Text", "c": "hi" })).ok(),
+        Some(Message::Text("hi".into()))
+    );
+    assert_eq!(
+        Message::validate(&json!({ "t": "Pair", "c": [1, 2] })).ok(),
+        Some(Message::Pair(1, 2))
+    );
+    assert_eq!(
+        Message::validate(&json!({ "t": "Move", "c": { "x": 1, "y": 2 } })).ok(),
+        Some(Message::Move { x: 1, y: 2 })
+    );
+
+    let result = Message::validate(&json!({ "t": "Move", "c": { "x": 1 } }));
+    assert_eq!(
+        result.issues()[0].path,
+        Some(vec![
+            PathSegment::Key("c".into()),
+            PathSegment::Key("y".into())
+        ])
+    );
+
+    let result = Message::validate(&json!({ "t": "Text" }));
+    assert_eq!(result.issues()[0].message, "Missing required field 'c'");
+}
+
+#[test]
+fn test_untagged_validation() {
+    assert_eq!(Scalar::validate(&json!(null)).ok(), Some(Scalar::Nothing));
+    assert_eq!(Scalar::validate(&json!(5)).ok(), Some(Scalar::Number(5)));
+    assert_eq!(
+        Scalar::validate(&json!("five")).ok(),
+        Some(Scalar::Word("five".into()))
+    );
+    assert_eq!(
+        Scalar::validate(&json!({ "lat": 1.0, "lng": 2.0 })).ok(),
+        Some(Scalar::Coords { lat: 1.0, lng: 2.0 })
+    );
+
+    let result = Scalar::validate(&json!([1]));
+    assert_eq!(
+        result.issues()[0].message,
+        "Value does not match any variant of Scalar"
+    );
+}
+
+#[derive(Debug, PartialEq, Valrs)]
+struct Reading {
+    scalar: Scalar,
+}
+
+#[test]
+fn test_untagged_union_errors() {
+    let result = Reading::validate(&json!({ "scalar": { "lat": "1", "lng": 2.0 } }));
+    let issue = &result.issues()[0];
+    assert_eq!(issue.code, IssueCode::InvalidUnion);
+    assert_eq!(issue.path, Some(vec![PathSegment::Key("scalar".into())]));
+
+    // One list per variant, in order, with paths from the root
+    let union_errors = issue.params.union_errors.as_ref().unwrap();
+    assert_eq!(union_errors.len(), 4);
+    assert_eq!(union_errors[0][0].message, "Expected null");
+    assert_eq!(union_errors[1][0].code, IssueCode::InvalidType);
+    assert_eq!(union_errors[3].len(), 1);
+    assert_eq!(union_errors[3][0].message, "Expected number");
+    assert_eq!(
+        union_errors[3][0].path,
+        Some(vec![
+            PathSegment::Key("scalar".into()),
+            PathSegment::Key("lat".into())
+        ])
+    );
+
+    assert_eq!(
+        Reading::validate(&json!({ "scalar": 1 })).ok(),
+        Some(Reading {
+            scalar: Scalar::Number(1)
+        })
+    );
+}
+
+#[test]
+fn test_externally_tagged_json_schema() {
+    let schema = Shape::json_schema_input(JsonSchemaTarget::Draft202012);
+    let one_of = schema["oneOf"].as_array().unwrap();
+    assert_eq!(one_of.len(), 4);
+    assert_eq!(one_of[0], json!({ "type": "string", "const": "Empty" }));
+    assert_eq!(one_of[1]["properties"]["Circle"]["type"], "number");
+    assert_eq!(one_of[1]["required"], json!(["Circle"]));
+    assert_eq!(one_of[1]["additionalProperties"], false);
+    assert_eq!(
+        one_of[2]["properties"]["Line"]["prefixItems"][0]["type"],
+        "object"
+    );
+    assert_eq!(
+        one_of[3]["properties"]["Rect"]["required"],
+        json!(["width", "height"])
+    );
+    assert_eq!(
+        schema["$schema"],
+        "https://json-schema.org/draft/2020-12/schema"
+    );
+
+    let schema = Shape::json_schema_input(JsonSchemaTarget::OpenApi30);
+    assert_eq!(
+        schema["oneOf"][0],
+        json!({ "type": "string", "enum": ["Empty"] })
+    );
+}
+
+#[test]
+fn test_internally_tagged_json_schema() {
+    let schema = Event::json_schema_input(JsonSchemaTarget::Draft07);
+    let one_of = schema["oneOf"].as_array().unwrap();
+    assert_eq!(one_of[0]["properties"]["type"]["const"], "ping");
+    assert_eq!(one_of[1]["properties"]["type"]["const"], "moved");
+    assert_eq!(one_of[1]["properties"]["x"]["type"], "integer");
+    assert_eq!(one_of[1]["required"], json!(["type", "x", "y"]));
+    assert_eq!(one_of[2]["required"], json!(["type", "name"]));
+    assert!(schema.get("discriminator").is_none());
+
+    let schema = Event::json_schema_input(JsonSchemaTarget::OpenApi30);
+    assert_eq!(schema["discriminator"], json!({ "propertyName": "type" }));
+    assert_eq!(
+        schema["oneOf"][0]["properties"]["type"]["enum"],
+        json!(["ping"])
+    );
+}
+
+#[test]
+fn test_adjacently_tagged_json_schema() {
+    let schema = Message::json_schema_input(JsonSchemaTarget::Draft202012);
+    let one_of = schema["oneOf"].as_array().unwrap();
+    assert_eq!(one_of[0]["required"], json!(["t"]));
+    assert_eq!(one_of[1]["properties"]["c"]["type"], "string");
+    assert_eq!(one_of[1]["required"], json!(["t", "c"]));
+    assert_eq!(one_of[2]["properties"]["c"]["type"], "array");
+}
+
+#[test]
+fn test_untagged_json_schema() {
+    let schema = Scalar::json_schema_input(JsonSchemaTarget::Draft202012);
+    let any_of = schema["anyOf"].as_array().unwrap();
+    assert_eq!(any_of[0]["type"], "null");
+    assert_eq!(any_of[1]["type"], "integer");
+    assert_eq!(any_of[2]["type"], "string");
+    assert_eq!(any_of[3]["type"], "object");
+}
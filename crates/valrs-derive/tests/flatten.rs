@@ -0,0 +1,226 @@
+//! Tests for flattened struct fields.
+
+use serde_json::json;
+use valrs::{JsonSchemaTarget, ObjectProperties, PathSegment, StandardJsonSchema, Valrs};
+use valrs_derive::{StandardJsonSchema, Valrs};
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct Pagination {
+    #[schema(minimum = 1)]
+    page: u32,
+    #[schema(default = 20)]
+    per_page: u32,
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+#[schema(rename_all = "camelCase")]
+struct AuditFields {
+    created_by: String,
+    #[schema(optional)]
+    updated_by: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct ListUsers {
+    query: String,
+    #[schema(flatten)]
+    pagination: Pagination,
+    #[schema(flatten)]
+    audit: AuditFields,
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+#[schema(require_one_of("email", "phone"))]
+struct ContactInfo {
+    #[schema(optional)]
+    email: Option<String>,
+    #[schema(optional)]
+    phone: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct Customer {
+    name: String,
+    #[schema(flatten)]
+    contact: ContactInfo,
+}
+
+/// Sort order of a listing.
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+#[schema(title = "Sorting", deny_unknown_fields)]
+struct Sorting {
+    /// The key to sort by.
+    sort_by: String,
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct Listing {
+    name: String,
+    #[schema(flatten)]
+    sorting: Sorting,
+}
+
+/// A hand-written type whose schema is not a plain object schema.
+#[derive(Debug, PartialEq)]
+struct Coordinates {
+    lat: f64,
+}
+
+impl Valrs for Coordinates {
+    type Input = Coordinates;
+    type Output = Coordinates;
+
+    fn validate(value: &serde_json::Value) -> valrs::ValidationResult<Self::Output> {
+        f64::validate(&value["lat"]).map(|lat| Coordinates { lat })
+    }
+}
+
+impl StandardJsonSchema for Coordinates {
+    fn json_schema_input(_target: JsonSchemaTarget) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": { "lat": { "type": "number" } },
+            "minProperties": 1
+        })
+    }
+
+    fn json_schema_output(target: JsonSchemaTarget) -> serde_json::Value {
+        Self::json_schema_input(target)
+    }
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct Place {
+    name: String,
+    #[schema(flatten)]
+    coordinates: Coordinates,
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct Page {
+    #[schema(flatten)]
+    list: ListUsers,
+}
+
+#[test]
+fn test_flattened_fields_are_read_from_same_object() {
+    let result = ListUsers::validate(&json!({
+        "query": "ada",
+        "page": 2,
+        "createdBy": "admin"
+    }));
+    assert_eq!(
+        result.ok(),
+        Some(ListUsers {
+            query: "ada".into(),
+            pagination: Pagination {
+                page: 2,
+                per_page: 20,
+            },
+            audit: AuditFields {
+                created_by: "admin".into(),
+                updated_by: None,
+            },
+        })
+    );
+}
+
+#[test]
+fn test_issues_at_flattened_keys() {
+    let result = ListUsers::validate(&json!({ "query": "ada", "page": 0 }));
+    let issues = result.issues();
+    assert_eq!(issues.len(), 2);
+    assert_eq!(issues[0].path, Some(vec![PathSegment::Key("page".into())]));
+    assert_eq!(
+        issues[1].path,
+        Some(vec![PathSegment::Key("createdBy".into())])
+    );
+    assert_eq!(issues[1].message, "Missing required field 'createdBy'");
+}
+
+#[test]
+fn test_schema_merges_properties() {
+    let schema = ListUsers::json_schema_input(JsonSchemaTarget::Draft202012);
+    let keys: Vec<_> = schema["properties"].as_object().unwrap().keys().collect();
+    assert_eq!(
+        keys,
+        ["createdBy", "page", "per_page", "query", "updatedBy"]
+    );
+    assert_eq!(schema["required"], json!(["query", "page", "createdBy"]));
+    assert_eq!(schema["properties"]["per_page"]["default"], 20);
+    assert_eq!(schema.get("allOf"), None);
+
+    let schema = ListUsers::json_schema_output(JsonSchemaTarget::Draft202012);
+    assert_eq!(
+        schema["required"],
+        json!(["query", "page", "per_page", "createdBy"])
+    );
+}
+
+#[test]
+fn test_nested_flatten() {
+    assert!(Page::validate(&json!({ "query": "", "page": 1, "createdBy": "x" })).is_success());
+
+    let properties = Page::json_schema_input_properties(JsonSchemaTarget::OpenApi30).unwrap();
+    assert_eq!(properties.properties.len(), 5);
+    assert_eq!(properties.required, ["query", "page", "createdBy"]);
+}
+
+#[test]
+fn test_schema_merges_properties_despite_annotations() {
+    let schema = Listing::json_schema_input(JsonSchemaTarget::Draft202012);
+    assert_eq!(
+        schema["properties"]["sort_by"],
+        json!({ "type": "string", "description": "The key to sort by." })
+    );
+    assert_eq!(schema["required"], json!(["name", "sort_by"]));
+    assert_eq!(schema.get("allOf"), None);
+    assert_eq!(schema.get("description"), None);
+    assert_eq!(schema.get("unevaluatedProperties"), None);
+}
+
+#[test]
+fn test_presence_rules_are_kept() {
+    let properties =
+        ContactInfo::json_schema_input_properties(JsonSchemaTarget::Draft202012).unwrap();
+    assert_eq!(properties.properties.len(), 2);
+    assert_eq!(
+        properties.all_of,
+        [json!({ "anyOf": [{ "required": ["email"] }, { "required": ["phone"] }] })]
+    );
+
+    let result = Customer::validate(&json!({ "name": "Ada" }));
+    assert_eq!(
+        result.issues()[0].message,
+        "At least one of 'email', 'phone' is required"
+    );
+
+    let schema = Customer::json_schema_input(JsonSchemaTarget::Draft202012);
+    assert_eq!(schema["required"], json!(["name"]));
+    assert_eq!(schema["properties"].as_object().unwrap().len(), 3);
+    assert_eq!(schema["allOf"], json!([properties.all_of[0]]));
+
+    // Flattened again, the rule is passed on
+    let properties = Customer::json_schema_input_properties(JsonSchemaTarget::Draft202012).unwrap();
+    assert_eq!(properties.all_of.len(), 1);
+}
+
+#[test]
+fn test_all_of_fallback() {
+    assert_eq!(
+        Coordinates::json_schema_input_properties(JsonSchemaTarget::Draft202012),
+        None
+    );
+
+    let schema = Place::json_schema_input(JsonSchemaTarget::Draft202012);
+    assert_eq!(schema["required"], json!(["name"]));
+    assert_eq!(schema["properties"].as_object().unwrap().len(), 1);
+    let all_of = schema["allOf"].as_array().unwrap();
+    assert_eq!(all_of.len(), 1);
+    assert_eq!(all_of[0]["minProperties"], 1);
+
+    // Its allOf is passed on when flattened again
+    let properties: Option<ObjectProperties> =
+        Place::json_schema_input_properties(JsonSchemaTarget::Draft202012);
+    assert_eq!(properties.unwrap().all_of, [all_of[0].clone()]);
+}
@@ -0,0 +1,104 @@
+//! Tests for string format and pattern attributes.
+
+use serde_json::json;
+use valrs::{JsonSchemaTarget, PathSegment, StandardJsonSchema, Valrs};
+use valrs_derive::{StandardJsonSchema, Valrs};
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct Signup {
+    #[schema(format = "email", max_length = 254)]
+    email: String,
+    #[schema(format = "uri")]
+    homepage: String,
+    #[schema(format = "uuid")]
+    invite: String,
+    #[schema(pattern = "^[A-Z]{3}$")]
+    currency: String,
+    #[schema(optional, format = "date")]
+    birthday: Option<String>,
+    #[schema(optional, format = "date-time")]
+    joined: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct Host(#[schema(format = "hostname")] String);
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct Peer(
+    #[schema(format = "ipv4")] String,
+    #[schema(format = "ipv6")] String,
+);
+
+fn valid() -> serde_json::Value {
+    json!({
+        "email": "ada@example.com",
+        "homepage": "https://example.com",
+        "invite": "123e4567-e89b-42d3-a456-426614174000",
+        "currency": "EUR",
+        "birthday": "1815-12-10",
+        "joined": "1833-06-05T18:00:00Z"
+    })
+}
+
+#[test]
+fn test_valid_formats() {
+    assert!(Signup::validate(&valid()).is_success());
+    assert!(Host::validate(&json!("api.example.com")).is_success());
+    assert!(Peer::validate(&json!(["10.0.0.1", "fe80::1"])).is_success());
+}
+
+#[test]
+fn test_format_messages() {
+    let cases = [
+        ("email", "ada@", "Invalid email address"),
+        ("homepage", "example.com", "Invalid URL"),
+        ("invite", "not-a-uuid", "Invalid UUID"),
+        ("currency", "eur", "String does not match pattern"),
+        ("birthday", "10/12/1815", "Invalid date format"),
+        ("joined", "1833-06-05", "Invalid datetime format"),
+    ];
+
+    for (key, value, message) in cases {
+        let mut input = valid();
+        input[key] = json!(value);
+        let result = Signup::validate(&input);
+        assert_eq!(result.issues().len(), 1, "{}", key);
+        assert_eq!(result.issues()[0].message, message);
+        assert_eq!(
+            result.issues()[0].path,
+            Some(vec![PathSegment::Key(key.into())])
+        );
+    }
+}
+
+#[test]
+fn test_tuple_struct_formats() {
+    let result = Host::validate(&json!("-bad-.example.com"));
+    assert_eq!(result.issues()[0].message, "Invalid hostname");
+    assert!(result.issues()[0].path.is_none());
+
+    let result = Peer::validate(&json!(["fe80::1", "10.0.0.1"]));
+    let issues = result.issues();
+    assert_eq!(issues[0].message, "Invalid IPv4 address");
+    assert_eq!(issues[0].path, Some(vec![PathSegment::Index(0)]));
+    assert_eq!(issues[1].message, "Invalid IPv6 address");
+    assert_eq!(issues[1].path, Some(vec![PathSegment::Index(1)]));
+}
+
+#[test]
+fn test_json_schema_keywords() {
+    let schema = Signup::json_schema_input(JsonSchemaTarget::Draft202012);
+    let properties = &schema["properties"];
+    assert_eq!(
+        properties["email"],
+        json!({ "type": "string", "format": "email", "maxLength": 254 })
+    );
+    assert_eq!(properties["homepage"]["format"], "uri");
+    assert_eq!(properties["invite"]["format"], "uuid");
+    assert_eq!(properties["currency"]["pattern"], "^[A-Z]{3}$");
+    assert_eq!(properties["birthday"]["anyOf"][0]["format"], "date");
+    assert_eq!(properties["joined"]["anyOf"][0]["format"], "date-time");
+
+    let schema = Host::json_schema_input(JsonSchemaTarget::OpenApi30);
+    assert_eq!(schema["format"], "hostname");
+}
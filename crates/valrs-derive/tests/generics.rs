@@ -0,0 +1,271 @@
+//! Tests for deriving `Valrs` and `StandardJsonSchema` on generic types.
+
+use std::marker::PhantomData;
+
+use serde_json::json;
+use valrs::{JsonSchemaTarget, PathSegment, StandardJsonSchema, Valrs};
+use valrs_derive::{StandardJsonSchema, Valrs};
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct Page<T> {
+    items: Vec<T>,
+    #[schema(optional)]
+    next: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct Tag {
+    name: String,
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct Pair<A, B>(A, B)
+where
+    A: Clone;
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+#[schema(tag = "status", rename_all = "lowercase")]
+enum Response<T, E> {
+    Ok { data: T },
+    Error { error: E },
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct Borrowed<'a, T>
+where
+    T: Clone,
+{
+    value: T,
+    #[serde(skip)]
+    marker: PhantomData<&'a ()>,
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct Row<T, const N: usize> {
+    cells: [T; N],
+}
+
+/// A marker type that never appears in JSON.
+#[derive(Debug, PartialEq)]
+struct Meters;
+
+/// A marker type without a `UnitName`.
+#[derive(Debug, PartialEq)]
+struct Feet;
+
+/// Names a unit in schemas.
+trait UnitName {
+    const NAME: &'static str;
+}
+
+impl UnitName for Meters {
+    const NAME: &'static str = "meters";
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+#[schema(bound = "")]
+struct Length<U> {
+    value: f64,
+    #[schema(optional)]
+    unit: Option<Unit<U>>,
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+#[schema(bound(valrs = "", schema = "U: UnitName"))]
+struct Measured<U> {
+    amount: Amount<U>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Unit<U>(PhantomData<U>);
+
+impl<U> Valrs for Unit<U> {
+    type Input = String;
+    type Output = Unit<U>;
+
+    fn validate(value: &serde_json::Value) -> valrs::ValidationResult<Self::Output> {
+        String::validate(value).map(|_| Unit(PhantomData))
+    }
+}
+
+impl<U> StandardJsonSchema for Unit<U> {
+    fn json_schema_input(target: JsonSchemaTarget) -> serde_json::Value {
+        String::json_schema_input(target)
+    }
+
+    fn json_schema_output(target: JsonSchemaTarget) -> serde_json::Value {
+        String::json_schema_output(target)
+    }
+}
+
+/// A number with a unit, which only its schema names.
+#[derive(Debug, PartialEq)]
+struct Amount<U>(f64, PhantomData<U>);
+
+impl<U> Valrs for Amount<U> {
+    type Input = f64;
+    type Output = Amount<U>;
+
+    fn validate(value: &serde_json::Value) -> valrs::ValidationResult<Self::Output> {
+        f64::validate(value).map(|n| Amount(n, PhantomData))
+    }
+}
+
+impl<U: UnitName> StandardJsonSchema for Amount<U> {
+    fn json_schema_input(target: JsonSchemaTarget) -> serde_json::Value {
+        let mut schema = f64::json_schema_input(target);
+        schema["description"] = json!(U::NAME);
+        schema
+    }
+
+    fn json_schema_output(target: JsonSchemaTarget) -> serde_json::Value {
+        Self::json_schema_input(target)
+    }
+}
+
+#[test]
+fn test_generic_struct_validation() {
+    let result = Page::<Tag>::validate(&json!({
+        "items": [{ "name": "rust" }, { "name": "json" }],
+        "next": "cursor"
+    }));
+    assert_eq!(
+        result.ok(),
+        Some(Page {
+            items: vec![
+                Tag {
+                    name: "rust".into()
+                },
+                Tag {
+                    name: "json".into()
+                }
+            ],
+            next: Some("cursor".into()),
+        })
+    );
+
+    let result = Page::<Tag>::validate(&json!({ "items": [{ "name": "rust" }, {}] }));
+    assert_eq!(
+        result.issues()[0].path,
+        Some(vec![
+            PathSegment::Key("items".into()),
+            PathSegment::Index(1),
+            PathSegment::Key("name".into()),
+        ])
+    );
+}
+
+#[test]
+fn test_generic_struct_schema() {
+    let schema = Page::<i32>::json_schema_input(JsonSchemaTarget::Draft202012);
+    assert_eq!(
+        schema["properties"]["items"],
+        json!({ "type": "array", "items": { "type": "integer" } })
+    );
+    assert_eq!(schema["required"], json!(["items"]));
+}
+
+#[test]
+fn test_generic_tuple_struct() {
+    assert_eq!(
+        Pair::<String, bool>::validate(&json!(["a", true])).ok(),
+        Some(Pair("a".into(), true))
+    );
+
+    let schema = Pair::<String, bool>::json_schema_input(JsonSchemaTarget::Draft07);
+    assert_eq!(
+        schema["items"],
+        json!([{ "type": "string" }, { "type": "boolean" }])
+    );
+}
+
+#[test]
+fn test_generic_enum() {
+    type R = Response<Tag, String>;
+
+    assert_eq!(
+        R::validate(&json!({ "status": "ok", "data": { "name": "rust" } })).ok(),
+        Some(Response::Ok {
+            data: Tag {
+                name: "rust".into()
+            }
+        })
+    );
+    assert_eq!(
+        R::validate(&json!({ "status": "error", "error": "boom" })).ok(),
+        Some(Response::Error {
+            error: "boom".into()
+        })
+    );
+
+    let schema = R::json_schema_input(JsonSchemaTarget::Draft202012);
+    assert_eq!(
+        schema["oneOf"][1]["properties"]["error"],
+        json!({ "type": "string" })
+    );
+}
+
+#[test]
+fn test_lifetime_struct() {
+    assert_eq!(
+        Borrowed::<u8>::validate(&json!({ "value": 7 })).ok(),
+        Some(Borrowed {
+            value: 7,
+            marker: PhantomData,
+        })
+    );
+
+    let schema = Borrowed::<u8>::json_schema_input(JsonSchemaTarget::Draft202012);
+    assert_eq!(schema["required"], json!(["value"]));
+}
+
+#[test]
+fn test_const_generic_array_field() {
+    assert_eq!(
+        Row::<bool, 2>::validate(&json!({ "cells": [true, false] })).ok(),
+        Some(Row {
+            cells: [true, false]
+        })
+    );
+
+    let result = Row::<bool, 2>::validate(&json!({ "cells": [true] }));
+    assert_eq!(
+        result.issues()[0].path,
+        Some(vec![PathSegment::Key("cells".into())])
+    );
+
+    let schema = Row::<String, 3>::json_schema_input(JsonSchemaTarget::Draft202012);
+    assert_eq!(schema["properties"]["cells"]["minItems"], 3);
+    assert_eq!(schema["properties"]["cells"]["maxItems"], 3);
+}
+
+#[test]
+fn test_bound_override() {
+    // `Meters` implements neither trait; the empty bound drops `U: Valrs`.
+    let result = Length::<Meters>::validate(&json!({ "value": 1.5, "unit": "m" }));
+    assert_eq!(result.ok().map(|l| l.value), Some(1.5));
+
+    let schema = Length::<Meters>::json_schema_input(JsonSchemaTarget::Draft202012);
+    assert_eq!(
+        schema["properties"]["unit"],
+        json!({ "anyOf": [{ "type": "string" }, { "type": "null" }] })
+    );
+}
+
+#[test]
+fn test_bound_per_trait() {
+    // Validation needs no bound, so `Feet` works without a `UnitName`.
+    let result = Measured::<Feet>::validate(&json!({ "amount": 2.5 }));
+    assert_eq!(
+        result.ok(),
+        Some(Measured {
+            amount: Amount(2.5, PhantomData)
+        })
+    );
+
+    let schema = Measured::<Meters>::json_schema_input(JsonSchemaTarget::Draft202012);
+    assert_eq!(
+        schema["properties"]["amount"],
+        json!({ "type": "number", "description": "meters" })
+    );
+}
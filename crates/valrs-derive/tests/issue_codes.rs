@@ -0,0 +1,193 @@
+//! Tests for the codes and params of issues raised by derived types.
+
+use serde_json::json;
+use valrs::{IssueCode, SizeType, ValidationIssue, Valrs};
+use valrs_derive::Valrs;
+
+#[derive(Debug, Valrs)]
+#[schema(deny_unknown_fields)]
+struct Signup {
+    #[schema(min_length = 3, max_length = 20)]
+    username: String,
+    #[schema(format = "email")]
+    email: String,
+    #[schema(pattern = "^[0-9]+$")]
+    pin: String,
+    #[schema(minimum = 18, exclusive_maximum = 150)]
+    age: u32,
+    #[schema(multiple_of = 0.5)]
+    rating: f64,
+    #[schema(min_items = 1)]
+    tags: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Valrs)]
+enum Plan {
+    Free,
+    Pro,
+}
+
+#[derive(Debug, PartialEq, Valrs)]
+#[schema(tag = "type")]
+enum Shape {
+    Circle { radius: f64 },
+    Square { side: f64 },
+}
+
+#[derive(Debug, PartialEq, Valrs)]
+struct Point(i32, i32);
+
+fn issue_for<'a>(issues: &'a [ValidationIssue], key: &str) -> &'a ValidationIssue {
+    issues
+        .iter()
+        .find(|issue| {
+            issue
+                .path
+                .as_ref()
+                .is_some_and(|path| path[0] == key.into())
+        })
+        .unwrap_or_else(|| panic!("no issue for {}", key))
+}
+
+#[test]
+fn test_valid_input() {
+    let signup = Signup::validate(&json!({
+        "username": "ada",
+        "email": "ada@example.com",
+        "pin": "1234",
+        "age": 36,
+        "rating": 4.5,
+        "tags": ["admin"]
+    }))
+    .ok()
+    .unwrap();
+    assert_eq!(signup.username, "ada");
+    assert_eq!(signup.email, "ada@example.com");
+    assert_eq!(signup.pin, "1234");
+    assert_eq!(signup.age, 36);
+    assert_eq!(signup.rating, 4.5);
+    assert_eq!(signup.tags, vec!["admin"]);
+}
+
+#[test]
+fn test_constraint_issue_codes() {
+    let result = Signup::validate(&json!({
+        "username": "ab",
+        "email": "not-an-email",
+        "pin": "12a4",
+        "age": 150,
+        "rating": 4.2,
+        "tags": [],
+        "admin": true
+    }));
+    let issues = result.issues();
+
+    let username = issue_for(issues, "username");
+    assert_eq!(username.code, IssueCode::TooSmall);
+    assert_eq!(username.params.size_type, Some(SizeType::String));
+    assert_eq!(username.params.minimum, Some(3.into()));
+    assert_eq!(username.params.inclusive, Some(true));
+
+    let email = issue_for(issues, "email");
+    assert_eq!(email.code, IssueCode::InvalidFormat);
+    assert_eq!(email.params.validation.as_deref(), Some("email"));
+
+    let pin = issue_for(issues, "pin");
+    assert_eq!(pin.code, IssueCode::InvalidFormat);
+    assert_eq!(pin.params.validation.as_deref(), Some("regex"));
+
+    let age = issue_for(issues, "age");
+    assert_eq!(age.code, IssueCode::TooBig);
+    assert_eq!(age.params.maximum, Some(150.into()));
+    assert_eq!(age.params.inclusive, Some(false));
+
+    let rating = issue_for(issues, "rating");
+    assert_eq!(rating.code, IssueCode::NotMultipleOf);
+    assert_eq!(rating.params.multiple_of, serde_json::Number::from_f64(0.5));
+
+    let tags = issue_for(issues, "tags");
+    assert_eq!(tags.code, IssueCode::TooSmall);
+    assert_eq!(tags.params.size_type, Some(SizeType::Array));
+
+    let admin = issue_for(issues, "admin");
+    assert_eq!(admin.code, IssueCode::UnknownKey);
+    assert_eq!(admin.params.keys, Some(vec!["admin".to_string()]));
+}
+
+#[test]
+fn test_missing_and_invalid_type_issue_codes() {
+    let result = Signup::validate(&json!({ "username": 42 }));
+    let issues = result.issues();
+
+    let username = issue_for(issues, "username");
+    assert_eq!(username.code, IssueCode::InvalidType);
+    assert_eq!(username.params.expected.as_deref(), Some("string"));
+    assert_eq!(username.params.received.as_deref(), Some("number"));
+    assert_eq!(issue_for(issues, "email").code, IssueCode::MissingField);
+
+    let result = Signup::validate(&json!([]));
+    assert_eq!(result.issues()[0].code, IssueCode::InvalidType);
+    assert_eq!(result.issues()[0].params.received.as_deref(), Some("array"));
+}
+
+#[test]
+fn test_enum_issue_codes() {
+    assert_eq!(Plan::validate(&json!("Pro")).ok(), Some(Plan::Pro));
+    let result = Plan::validate(&json!("Enterprise"));
+    let issue = &result.issues()[0];
+    assert_eq!(issue.code, IssueCode::InvalidEnumValue);
+    assert_eq!(
+        issue.params.options,
+        Some(vec!["Free".to_string(), "Pro".to_string()])
+    );
+    assert_eq!(issue.params.received.as_deref(), Some("Enterprise"));
+
+    assert_eq!(
+        Shape::validate(&json!({ "type": "Square", "side": 2.0 })).ok(),
+        Some(Shape::Square { side: 2.0 })
+    );
+    assert!(matches!(
+        Shape::validate(&json!({ "type": "Circle", "radius": 1.0 })).ok(),
+        Some(Shape::Circle { .. })
+    ));
+    let result = Shape::validate(&json!({ "type": "Triangle" }));
+    let issue = &result.issues()[0];
+    assert_eq!(issue.code, IssueCode::InvalidUnionDiscriminator);
+    assert_eq!(issue.path, Some(vec!["type".into()]));
+
+    let result = Shape::validate(&json!({ "radius": 1.0 }));
+    assert_eq!(result.issues()[0].code, IssueCode::MissingField);
+}
+
+#[test]
+fn test_tuple_struct_length_issue() {
+    assert_eq!(Point::validate(&json!([1, 2])).ok(), Some(Point(1, 2)));
+    let result = Point::validate(&json!([1]));
+    let issue = &result.issues()[0];
+    assert_eq!(issue.code, IssueCode::TooSmall);
+    assert_eq!(issue.params.size_type, Some(SizeType::Array));
+    assert_eq!(issue.params.exact, Some(true));
+}
+
+#[test]
+fn test_issue_serialization() {
+    let result = Signup::validate(&json!({
+        "username": "ab",
+        "email": "ada@example.com",
+        "pin": "1",
+        "age": 20,
+        "rating": 1,
+        "tags": ["a"]
+    }));
+    assert_eq!(
+        serde_json::to_value(result.issues()).unwrap(),
+        json!([{
+            "code": "too_small",
+            "message": "String must be at least 3 characters, got 2",
+            "path": ["username"],
+            "type": "string",
+            "minimum": 3,
+            "inclusive": true
+        }])
+    );
+}
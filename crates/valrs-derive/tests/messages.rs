@@ -0,0 +1,156 @@
+//! Tests for custom constraint and missing field messages.
+
+use serde_json::json;
+use std::sync::Arc;
+
+use valrs::{ErrorMap, MessageTable, PathSegment, ValidationContext, Valrs};
+use valrs_derive::Valrs;
+
+#[derive(Debug, Valrs)]
+#[schema(missing_message = "Please fill in {field}")]
+struct SignUp {
+    #[schema(min_length(3, message = "{field} needs {min}+ characters, got {actual}"))]
+    username: String,
+    #[schema(format("email", message = "'{actual}' is not a valid {format}"))]
+    email: String,
+    #[schema(
+        minimum(13, message = "You must be at least {min}"),
+        maximum = 130,
+        missing_message = "Tell us your age"
+    )]
+    age: u32,
+    #[schema(pattern("^[A-Z]{2}$", message = "Use a {{two letter}} code"), optional)]
+    country: Option<String>,
+}
+
+#[derive(Debug, Valrs)]
+struct Order {
+    #[schema(multiple_of(5, message = "Order in packs of {multiple_of}"))]
+    quantity: u32,
+    #[schema(max_length(4, message = "Too long",))]
+    code: String,
+}
+
+#[derive(Debug, Valrs)]
+struct Discount(#[schema(exclusive_maximum(100, message = "Must be under {max}%"))] f64);
+
+#[test]
+fn test_custom_constraint_messages() {
+    let result = SignUp::validate(&json!({
+        "username": "al",
+        "email": "nope",
+        "age": 9,
+        "country": "usa"
+    }));
+    let messages: Vec<_> = result.issues().iter().map(|i| i.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        [
+            "username needs 3+ characters, got 2",
+            "'nope' is not a valid email",
+            "You must be at least 13",
+            "Use a {two letter} code",
+        ]
+    );
+    assert_eq!(
+        result.issues()[2].path,
+        Some(vec![PathSegment::Key("age".into())])
+    );
+}
+
+#[test]
+fn test_constraints_without_message_keep_defaults() {
+    let result = SignUp::validate(&json!({
+        "username": "ada",
+        "email": "ada@example.com",
+        "age": 200
+    }));
+    assert_eq!(
+        result.issues()[0].message,
+        "Number must be less than or equal to 130, got 200"
+    );
+}
+
+#[test]
+fn test_valid_input() {
+    let user = SignUp::validate(&json!({
+        "username": "ada",
+        "email": "ada@example.com",
+        "age": 36,
+        "country": "GB"
+    }))
+    .ok()
+    .unwrap();
+    assert_eq!(
+        (user.username, user.email, user.age, user.country),
+        (
+            "ada".into(),
+            "ada@example.com".into(),
+            36,
+            Some("GB".into())
+        )
+    );
+}
+
+#[test]
+fn test_missing_messages() {
+    let result = SignUp::validate(&json!({ "username": "ada" }));
+    let messages: Vec<_> = result.issues().iter().map(|i| i.message.as_str()).collect();
+    assert_eq!(messages, ["Please fill in email", "Tell us your age"]);
+    assert_eq!(
+        result.issues()[0].path,
+        Some(vec![PathSegment::Key("email".into())])
+    );
+
+    let result = Order::validate(&json!({}));
+    assert_eq!(
+        result.issues()[0].message,
+        "Missing required field 'quantity'"
+    );
+}
+
+#[test]
+fn test_messages_on_numbers_and_newtypes() {
+    let result = Order::validate(&json!({ "quantity": 7, "code": "ABCDE" }));
+    let messages: Vec<_> = result.issues().iter().map(|i| i.message.as_str()).collect();
+    assert_eq!(messages, ["Order in packs of 5", "Too long"]);
+
+    let result = Discount::validate(&json!(100));
+    assert_eq!(result.issues()[0].message, "Must be under 100%");
+    assert_eq!(Discount::validate(&json!(99.5)).ok().unwrap().0, 99.5);
+
+    let order = Order::validate(&json!({ "quantity": 10, "code": "A1" }))
+        .ok()
+        .unwrap();
+    assert_eq!((order.quantity, order.code), (10, "A1".into()));
+}
+
+#[test]
+fn test_custom_messages_are_kept_by_error_maps() {
+    let error_map: Arc<dyn ErrorMap> = Arc::new(MessageTable::english());
+    let result = SignUp::validate_with_error_map(
+        &json!({ "username": "al", "email": "ada@example.com", "age": 200 }),
+        &ValidationContext::default(),
+        error_map,
+    );
+    let messages: Vec<&str> = result
+        .issues()
+        .iter()
+        .map(|issue| issue.message.as_str())
+        .collect();
+    assert_eq!(
+        messages,
+        vec![
+            "username needs 3+ characters, got 2",
+            "Number must be less than or equal to 130",
+        ]
+    );
+
+    let error_map: Arc<dyn ErrorMap> = Arc::new(MessageTable::english());
+    let result = Order::validate_with_error_map(
+        &json!({ "quantity": 5 }),
+        &ValidationContext::default(),
+        error_map,
+    );
+    assert_eq!(result.issues()[0].message, "Required");
+}
@@ -0,0 +1,122 @@
+//! Tests for schema annotations: doc comments, titles, examples and flags.
+
+use serde_json::json;
+use valrs::{JsonSchemaTarget, StandardJsonSchema, Valrs};
+use valrs_derive::{StandardJsonSchema, Valrs};
+
+/// A registered user.
+///
+/// Returned by the users endpoints.
+#[derive(Debug, Valrs, StandardJsonSchema)]
+#[schema(title = "User", example = "see fields")]
+struct User {
+    /// Unique identifier, assigned by the server.
+    #[schema(read_only, example = 42)]
+    id: u64,
+    /// The user's login name.
+    #[schema(title = "Username", example = "ada", example = "grace")]
+    name: String,
+    /// Ignored in favor of the explicit description.
+    #[schema(description = "Plain-text password", write_only)]
+    password: String,
+    #[schema(deprecated, optional, example = -1.5)]
+    score: Option<f64>,
+}
+
+/// An HTTP port.
+#[derive(Debug, Valrs, StandardJsonSchema)]
+struct Port(#[schema(example = 8080, minimum = 1)] u16);
+
+/// A traffic light color.
+#[derive(Debug, Valrs, StandardJsonSchema)]
+#[schema(deprecated)]
+enum Light {
+    Red,
+    Green,
+}
+
+#[test]
+fn test_annotations_do_not_affect_validation() {
+    let user = User::validate(&json!({ "id": 1, "name": "ada", "password": "x" }))
+        .ok()
+        .unwrap();
+    assert_eq!(
+        (user.id, user.name, user.password),
+        (1, "ada".into(), "x".into())
+    );
+    assert_eq!(user.score, None);
+    assert_eq!(Port::validate(&json!(80)).ok().unwrap().0, 80);
+}
+
+#[test]
+fn test_doc_comments_become_descriptions() {
+    let schema = User::json_schema_input(JsonSchemaTarget::Draft202012);
+    assert_eq!(
+        schema["description"],
+        "A registered user.\n\nReturned by the users endpoints."
+    );
+    assert_eq!(
+        schema["properties"]["id"]["description"],
+        "Unique identifier, assigned by the server."
+    );
+    assert_eq!(
+        schema["properties"]["password"]["description"],
+        "Plain-text password"
+    );
+    assert_eq!(schema["properties"]["score"].get("description"), None);
+
+    let schema = Light::json_schema_output(JsonSchemaTarget::Draft07);
+    assert_eq!(schema["description"], "A traffic light color.");
+}
+
+#[test]
+fn test_json_schema_annotations() {
+    let schema = User::json_schema_input(JsonSchemaTarget::Draft202012);
+    assert_eq!(schema["title"], "User");
+    assert_eq!(schema["examples"], json!(["see fields"]));
+
+    let properties = &schema["properties"];
+    assert_eq!(properties["id"]["readOnly"], true);
+    assert_eq!(properties["id"]["examples"], json!([42]));
+    assert_eq!(properties["name"]["title"], "Username");
+    assert_eq!(properties["name"]["examples"], json!(["ada", "grace"]));
+    assert_eq!(properties["password"]["writeOnly"], true);
+    assert_eq!(properties["score"]["deprecated"], true);
+    assert_eq!(properties["score"]["examples"], json!([-1.5]));
+}
+
+#[test]
+fn test_openapi_annotations() {
+    let schema = User::json_schema_input(JsonSchemaTarget::OpenApi30);
+    let properties = &schema["properties"];
+    assert_eq!(properties["name"]["example"], "ada");
+    assert_eq!(properties["name"].get("examples"), None);
+    assert_eq!(properties["score"]["deprecated"], true);
+    assert_eq!(properties["id"]["readOnly"], true);
+
+    let schema = Light::json_schema_input(JsonSchemaTarget::OpenApi30);
+    assert_eq!(schema["deprecated"], true);
+}
+
+#[test]
+fn test_draft07_has_no_deprecated() {
+    let schema = User::json_schema_input(JsonSchemaTarget::Draft07);
+    assert_eq!(schema["properties"]["score"].get("deprecated"), None);
+    assert_eq!(schema["properties"]["score"]["examples"], json!([-1.5]));
+    assert_eq!(schema["properties"]["password"]["writeOnly"], true);
+}
+
+#[test]
+fn test_newtype_annotations() {
+    let schema = Port::json_schema_input(JsonSchemaTarget::Draft202012);
+    assert_eq!(
+        schema,
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "integer",
+            "minimum": 1,
+            "description": "An HTTP port.",
+            "examples": [8080]
+        })
+    );
+}
@@ -0,0 +1,108 @@
+//! Tests for missing, null and present fields: `Maybe<T>`, `non_nullable` and
+//! `implicit_optional`.
+
+use serde_json::json;
+use valrs::{JsonSchemaTarget, Maybe, PathSegment, StandardJsonSchema, Valrs};
+use valrs_derive::{StandardJsonSchema, Valrs};
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct UpdateUser {
+    #[schema(min_length = 1)]
+    name: Maybe<String>,
+    nickname: Maybe<String>,
+    #[schema(optional, non_nullable)]
+    age: Option<u32>,
+    #[schema(optional)]
+    bio: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+#[schema(implicit_optional)]
+struct Filter {
+    query: String,
+    #[schema(maximum = 100)]
+    limit: Option<u32>,
+    #[schema(default)]
+    cursor: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+#[schema(implicit_optional)]
+enum Command {
+    Move { x: i32, y: Option<i32> },
+}
+
+#[test]
+fn test_maybe_distinguishes_missing_and_null() {
+    let result = UpdateUser::validate(&json!({ "nickname": null, "bio": null }));
+    assert_eq!(
+        result.ok(),
+        Some(UpdateUser {
+            name: Maybe::Absent,
+            nickname: Maybe::Null,
+            age: None,
+            bio: None,
+        })
+    );
+
+    let result = UpdateUser::validate(&json!({ "name": "Ada", "age": 36 }));
+    let update = result.ok().unwrap();
+    assert_eq!(update.name, Maybe::Value("Ada".into()));
+    assert_eq!(update.age, Some(36));
+
+    let result = UpdateUser::validate(&json!({ "name": "" }));
+    assert_eq!(
+        result.issues()[0].message,
+        "String must be at least 1 characters, got 0"
+    );
+}
+
+#[test]
+fn test_non_nullable_rejects_null() {
+    let result = UpdateUser::validate(&json!({ "age": null }));
+    let issues = result.issues();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].path, Some(vec![PathSegment::Key("age".into())]));
+}
+
+#[test]
+fn test_implicit_optional() {
+    let result = Filter::validate(&json!({ "query": "rust" }));
+    assert_eq!(
+        result.ok(),
+        Some(Filter {
+            query: "rust".into(),
+            limit: None,
+            cursor: None,
+        })
+    );
+    assert!(Filter::validate(&json!({ "query": "rust", "limit": 101 })).is_failure());
+    assert!(Filter::validate(&json!({})).is_failure());
+
+    let result = Command::validate(&json!({ "Move": { "x": 1 } }));
+    assert_eq!(result.ok(), Some(Command::Move { x: 1, y: None }));
+}
+
+#[test]
+fn test_schema_separates_required_and_nullable() {
+    let schema = UpdateUser::json_schema_input(JsonSchemaTarget::Draft202012);
+    assert_eq!(schema.get("required"), None);
+    let properties = &schema["properties"];
+    assert_eq!(
+        properties["name"],
+        json!({ "anyOf": [{ "type": "string", "minLength": 1 }, { "type": "null" }] })
+    );
+    assert_eq!(properties["age"], json!({ "type": "integer" }));
+
+    let schema = UpdateUser::json_schema_output(JsonSchemaTarget::OpenApi30);
+    assert_eq!(schema.get("required"), None);
+    assert_eq!(
+        schema["properties"]["bio"],
+        json!({ "type": "string", "nullable": true })
+    );
+
+    let schema = Filter::json_schema_input(JsonSchemaTarget::OpenApi30);
+    assert_eq!(schema["required"], json!(["query"]));
+    assert_eq!(schema["properties"]["limit"]["nullable"], true);
+    assert_eq!(schema["properties"]["limit"]["maximum"], 100);
+}
@@ -0,0 +1,140 @@
+//! Tests for `rename_all` on structs and field aliases.
+
+use serde_json::json;
+use valrs::{IssueCode, JsonSchemaTarget, PathSegment, StandardJsonSchema, Valrs};
+use valrs_derive::{StandardJsonSchema, Valrs};
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+#[schema(rename_all = "camelCase")]
+struct Profile {
+    display_name: String,
+    #[schema(alias = "user_email", alias = "mail")]
+    email_address: String,
+    #[schema(rename = "TZ")]
+    time_zone: String,
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+#[schema(rename_all = "SCREAMING_SNAKE_CASE")]
+struct Env {
+    database_url: String,
+    #[schema(optional)]
+    log_level: Option<String>,
+}
+
+fn not_admin(name: &str) -> Result<(), String> {
+    if name == "admin" {
+        Err("Reserved name".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Valrs)]
+struct Account {
+    #[schema(alias = "old", min_length = 3, validate_with = "not_admin")]
+    name: String,
+    #[schema(optional, alias = "years", minimum = 0)]
+    age: Option<i32>,
+}
+
+#[test]
+fn test_rename_all() {
+    let result = Profile::validate(&json!({
+        "displayName": "Ada",
+        "emailAddress": "ada@example.com",
+        "TZ": "UTC"
+    }));
+    assert!(result.is_success());
+
+    let result = Env::validate(&json!({ "DATABASE_URL": "postgres://", "LOG_LEVEL": "info" }));
+    assert_eq!(
+        result.ok().and_then(|env| env.log_level),
+        Some("info".into())
+    );
+
+    let result = Env::validate(&json!({ "database_url": "postgres://" }));
+    assert_eq!(
+        result.issues()[0].message,
+        "Missing required field 'DATABASE_URL'"
+    );
+}
+
+#[test]
+fn test_alias_accepted() {
+    for key in ["emailAddress", "user_email", "mail"] {
+        let mut input = json!({ "displayName": "Ada", "TZ": "UTC" });
+        input[key] = json!("ada@example.com");
+        let result = Profile::validate(&input);
+        assert_eq!(
+            result.ok().map(|p| p.email_address),
+            Some("ada@example.com".into()),
+            "key: {}",
+            key
+        );
+    }
+}
+
+#[test]
+fn test_alias_constraint_paths() {
+    let paths = |input| {
+        Account::validate(&input)
+            .issues()
+            .iter()
+            .map(|issue| issue.path.clone().unwrap())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        paths(json!({ "old": "ab", "years": -1 })),
+        [
+            vec![PathSegment::Key("old".into())],
+            vec![PathSegment::Key("years".into())],
+        ]
+    );
+    assert_eq!(
+        paths(json!({ "old": "admin" })),
+        [vec![PathSegment::Key("old".into())]]
+    );
+    assert_eq!(
+        paths(json!({ "name": "ab" })),
+        [vec![PathSegment::Key("name".into())]]
+    );
+}
+
+#[test]
+fn test_alias_conflict() {
+    let result = Profile::validate(&json!({
+        "displayName": "Ada",
+        "TZ": "UTC",
+        "emailAddress": "ada@example.com",
+        "mail": "ada@example.org"
+    }));
+    assert_eq!(result.issues().len(), 1);
+    assert_eq!(
+        result.issues()[0].message,
+        "Key 'mail' conflicts with 'emailAddress'"
+    );
+    assert_eq!(result.issues()[0].code, IssueCode::KeyConflict);
+    assert_eq!(
+        result.issues()[0].params.keys,
+        Some(vec!["emailAddress".to_string(), "mail".to_string()])
+    );
+    assert_eq!(
+        result.issues()[0].path,
+        Some(vec![PathSegment::Key("mail".into())])
+    );
+}
+
+#[test]
+fn test_schema_uses_canonical_keys() {
+    let schema = Profile::json_schema_input(JsonSchemaTarget::Draft202012);
+    assert_eq!(
+        schema["required"],
+        json!(["displayName", "emailAddress", "TZ"])
+    );
+    assert_eq!(
+        schema["properties"]["emailAddress"],
+        json!({ "type": "string", "x-aliases": ["user_email", "mail"] })
+    );
+    assert!(schema["properties"].get("mail").is_none());
+}
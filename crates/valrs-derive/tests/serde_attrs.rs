@@ -0,0 +1,234 @@
+//! Tests that the derives follow serde attributes, so that validation and
+//! schemas match what serde deserializes.
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+use valrs::{JsonSchemaTarget, PathSegment, StandardJsonSchema, Valrs};
+use valrs_derive::{StandardJsonSchema, Valrs};
+
+fn default_limit() -> u32 {
+    20
+}
+
+#[derive(Debug, PartialEq, Deserialize, Valrs, StandardJsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct Query {
+    search_term: String,
+    #[serde(rename = "page")]
+    page_number: u32,
+    #[serde(default = "default_limit")]
+    limit: u32,
+    #[serde(default)]
+    include_archived: bool,
+    #[serde(alias = "sort", alias = "order")]
+    sort_by: String,
+    #[serde(skip)]
+    cache_hits: u32,
+    #[serde(flatten)]
+    owner: Owner,
+}
+
+#[derive(Debug, PartialEq, Deserialize, Valrs, StandardJsonSchema)]
+struct Owner {
+    owner_id: u64,
+}
+
+#[derive(Debug, Default, PartialEq, Deserialize, Valrs, StandardJsonSchema)]
+#[serde(default)]
+struct Settings {
+    theme: String,
+    font_size: u8,
+}
+
+#[derive(Debug, PartialEq, Deserialize, Valrs, StandardJsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Notification {
+    #[serde(rename_all = "camelCase")]
+    NewMessage { sender_name: String },
+    #[serde(rename = "ping")]
+    Heartbeat,
+}
+
+#[derive(Debug, PartialEq, Deserialize, Valrs, StandardJsonSchema)]
+#[serde(deny_unknown_fields)]
+struct Strict {
+    a: u32,
+}
+
+#[derive(Debug, PartialEq, Deserialize, Valrs, StandardJsonSchema)]
+struct Holder {
+    inner: Strict,
+}
+
+#[derive(Debug, PartialEq, Deserialize, Valrs, StandardJsonSchema)]
+#[serde(tag = "type")]
+enum Wrapped {
+    A(Strict),
+    B(Holder),
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct Precedence {
+    #[serde(rename = "serdeName")]
+    #[schema(rename = "schemaName")]
+    renamed: bool,
+    first_name: String,
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+#[schema(ignore_serde)]
+#[serde(rename_all = "camelCase")]
+struct IgnoresSerde {
+    first_name: String,
+}
+
+/// Asserts that serde and valrs agree on `input`.
+fn assert_consistent<T>(input: Value)
+where
+    T: for<'de> Deserialize<'de> + Valrs<Output = T> + PartialEq + std::fmt::Debug,
+{
+    let deserialized = serde_json::from_value::<T>(input.clone()).ok();
+    let validated = T::validate(&input).ok();
+    assert_eq!(deserialized, validated, "input: {}", input);
+}
+
+#[test]
+fn test_struct_matches_serde() {
+    let inputs = [
+        json!({ "searchTerm": "rust", "page": 2, "sortBy": "date", "owner_id": 7 }),
+        json!({
+            "searchTerm": "rust",
+            "page": 2,
+            "limit": 5,
+            "includeArchived": true,
+            "order": "date",
+            "cacheHits": 10,
+            "owner_id": 7
+        }),
+        json!({ "search_term": "rust", "page": 2, "sortBy": "date", "owner_id": 7 }),
+        json!({ "searchTerm": "rust", "pageNumber": 2, "sortBy": "date", "owner_id": 7 }),
+        json!({ "searchTerm": "rust", "page": 2, "sortBy": "date" }),
+    ];
+    for input in inputs {
+        assert_consistent::<Query>(input);
+    }
+}
+
+#[test]
+fn test_defaults_and_skip() {
+    let result = Query::validate(&json!({
+        "searchTerm": "rust",
+        "page": 1,
+        "sort": "name",
+        "cacheHits": 99,
+        "owner_id": 1
+    }));
+    let query = result.ok().unwrap();
+    assert_eq!(query.limit, 20);
+    assert!(!query.include_archived);
+    assert_eq!(query.sort_by, "name");
+    assert_eq!(query.cache_hits, 0);
+}
+
+#[test]
+fn test_alias_issue_path() {
+    let result = Query::validate(&json!({
+        "searchTerm": "rust",
+        "page": 1,
+        "order": 3,
+        "owner_id": "x"
+    }));
+    let paths: Vec<_> = result.issues().iter().map(|i| i.path.clone()).collect();
+    assert_eq!(
+        paths,
+        vec![
+            Some(vec![PathSegment::Key("order".into())]),
+            Some(vec![PathSegment::Key("owner_id".into())]),
+        ]
+    );
+}
+
+#[test]
+fn test_container_default_matches_serde() {
+    assert_consistent::<Settings>(json!({}));
+    assert_consistent::<Settings>(json!({ "theme": "dark" }));
+    assert_consistent::<Settings>(json!({ "font_size": "large" }));
+}
+
+#[test]
+fn test_enum_matches_serde() {
+    assert_consistent::<Notification>(json!({ "kind": "new_message", "senderName": "Ada" }));
+    assert_consistent::<Notification>(json!({ "kind": "new_message", "sender_name": "Ada" }));
+    assert_consistent::<Notification>(json!({ "kind": "ping" }));
+    assert_consistent::<Notification>(json!({ "kind": "heartbeat" }));
+}
+
+#[test]
+fn test_tag_is_known_to_denying_newtype_content() {
+    assert_consistent::<Wrapped>(json!({ "type": "A", "a": 1 }));
+    assert_consistent::<Wrapped>(json!({ "type": "A", "a": 1, "b": 2 }));
+    assert_eq!(
+        Wrapped::validate(&json!({ "type": "A", "a": 1 })).ok(),
+        Some(Wrapped::A(Strict { a: 1 }))
+    );
+
+    // The tag is only known in the tagged object, not in nested ones
+    assert_consistent::<Wrapped>(json!({ "type": "B", "inner": { "a": 1 } }));
+    assert_consistent::<Wrapped>(json!({ "type": "B", "inner": { "a": 1, "type": "B" } }));
+}
+
+#[test]
+fn test_schema_attributes_take_precedence() {
+    let result = Precedence::validate(&json!({ "schemaName": true, "firstName": "Ada" }));
+    assert!(result.is_success());
+
+    let result = Precedence::validate(&json!({ "serdeName": true, "firstName": "Ada" }));
+    assert!(result.is_failure());
+}
+
+#[test]
+fn test_ignore_serde() {
+    assert!(IgnoresSerde::validate(&json!({ "first_name": "Ada" })).is_success());
+    assert!(IgnoresSerde::validate(&json!({ "firstName": "Ada" })).is_failure());
+}
+
+#[test]
+fn test_struct_schema() {
+    let schema = Query::json_schema_input(JsonSchemaTarget::Draft202012);
+    let properties = schema["properties"].as_object().unwrap();
+    let mut keys: Vec<_> = properties.keys().map(String::as_str).collect();
+    keys.sort_unstable();
+    assert_eq!(
+        keys,
+        [
+            "includeArchived",
+            "limit",
+            "owner_id",
+            "page",
+            "searchTerm",
+            "sortBy"
+        ]
+    );
+    assert_eq!(
+        schema["required"],
+        json!(["searchTerm", "page", "sortBy", "owner_id"])
+    );
+
+    let schema = Settings::json_schema_input(JsonSchemaTarget::Draft202012);
+    assert!(schema.get("required").is_none());
+}
+
+#[test]
+fn test_enum_schema() {
+    let schema = Notification::json_schema_input(JsonSchemaTarget::Draft202012);
+    assert_eq!(
+        schema["oneOf"][0]["properties"]["kind"],
+        json!({ "type": "string", "const": "new_message" })
+    );
+    assert!(schema["oneOf"][0]["properties"].get("senderName").is_some());
+    assert_eq!(
+        schema["oneOf"][1]["properties"]["kind"],
+        json!({ "type": "string", "const": "ping" })
+    );
+}
@@ -0,0 +1,75 @@
+//! Tests for deriving `Valrs` and `StandardJsonSchema` on structs.
+
+use serde_json::json;
+use valrs::validators::NonEmptyString;
+use valrs::{PathSegment, Valrs};
+use valrs_derive::Valrs;
+
+/// A field type with no sensible default.
+#[derive(Debug, PartialEq)]
+struct Percent(u8);
+
+impl Valrs for Percent {
+    type Input = u8;
+    type Output = Percent;
+
+    fn validate(value: &serde_json::Value) -> valrs::ValidationResult<Self::Output> {
+        u8::validate(value).map(Percent)
+    }
+}
+
+#[derive(Debug, PartialEq, Valrs)]
+enum Role {
+    Admin,
+    Member,
+}
+
+#[derive(Debug, PartialEq, Valrs)]
+struct Member {
+    name: NonEmptyString,
+    role: Role,
+    progress: Percent,
+    #[schema(optional)]
+    nickname: Option<NonEmptyString>,
+}
+
+#[test]
+fn test_fields_without_default() {
+    let result = Member::validate(&json!({
+        "name": "Ada",
+        "role": "Admin",
+        "progress": 40
+    }));
+    assert_eq!(
+        result.ok(),
+        Some(Member {
+            name: NonEmptyString("Ada".into()),
+            role: Role::Admin,
+            progress: Percent(40),
+            nickname: None,
+        })
+    );
+}
+
+#[test]
+fn test_all_field_issues_reported() {
+    let result = Member::validate(&json!({
+        "name": "",
+        "role": "Owner",
+        "nickname": ""
+    }));
+    let paths: Vec<_> = result
+        .issues()
+        .iter()
+        .map(|issue| issue.path.clone().unwrap())
+        .collect();
+    assert_eq!(
+        paths,
+        vec![
+            vec![PathSegment::Key("name".into())],
+            vec![PathSegment::Key("role".into())],
+            vec![PathSegment::Key("progress".into())],
+            vec![PathSegment::Key("nickname".into())],
+        ]
+    );
+}
@@ -0,0 +1,136 @@
+//! Tests for deriving `Valrs` and `StandardJsonSchema` on tuple structs.
+
+use serde_json::json;
+use valrs::{JsonSchemaTarget, PathSegment, StandardJsonSchema, Valrs};
+use valrs_derive::{StandardJsonSchema, Valrs};
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct UserId(u64);
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct Email(#[schema(max_length = 254)] String);
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct Point(f64, f64);
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct Labelled(#[schema(min_length = 1)] String, UserId);
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct Contact {
+    id: UserId,
+    email: Email,
+}
+
+#[test]
+fn test_newtype_is_transparent() {
+    assert_eq!(UserId::validate(&json!(42)).ok(), Some(UserId(42)));
+
+    let result = UserId::validate(&json!("42"));
+    assert!(result.is_failure());
+    assert!(result.issues()[0].path.is_none());
+}
+
+#[test]
+fn test_newtype_field_constraints() {
+    assert_eq!(
+        Email::validate(&json!("ada@example.com")).ok(),
+        Some(Email("ada@example.com".into()))
+    );
+
+    let long = "a".repeat(255);
+    let result = Email::validate(&json!(long));
+    assert_eq!(result.issues().len(), 1);
+    assert_eq!(
+        result.issues()[0].message,
+        "String must be at most 254 characters, got 255"
+    );
+    assert!(result.issues()[0].path.is_none());
+}
+
+#[test]
+fn test_newtype_in_struct_paths() {
+    let result = Contact::validate(&json!({ "id": 1, "email": "a".repeat(300) }));
+    assert_eq!(result.issues().len(), 1);
+    assert_eq!(
+        result.issues()[0].path,
+        Some(vec![PathSegment::Key("email".into())])
+    );
+}
+
+#[test]
+fn test_tuple_struct_validation() {
+    assert_eq!(
+        Point::validate(&json!([1.5, -2])).ok(),
+        Some(Point(1.5, -2.0))
+    );
+
+    let result = Point::validate(&json!([1.5]));
+    assert_eq!(
+        result.issues()[0].message,
+        "Expected array of length 2, got 1"
+    );
+
+    let result = Point::validate(&json!({ "x": 1.5, "y": 2 }));
+    assert_eq!(result.issues()[0].message, "Expected array");
+}
+
+#[test]
+fn test_tuple_struct_index_paths() {
+    let result = Labelled::validate(&json!(["", "7"]));
+    let issues = result.issues();
+    assert_eq!(issues.len(), 2);
+    assert_eq!(issues[0].path, Some(vec![PathSegment::Index(0)]));
+    assert_eq!(
+        issues[0].message,
+        "String must be at least 1 characters, got 0"
+    );
+    assert_eq!(issues[1].path, Some(vec![PathSegment::Index(1)]));
+
+    assert_eq!(
+        Labelled::validate(&json!(["a", 7])).ok(),
+        Some(Labelled("a".into(), UserId(7)))
+    );
+}
+
+#[test]
+fn test_newtype_schema() {
+    let schema = UserId::json_schema_input(JsonSchemaTarget::Draft202012);
+    assert_eq!(schema["type"], "integer");
+    assert_eq!(
+        schema["$schema"],
+        "https://json-schema.org/draft/2020-12/schema"
+    );
+
+    let schema = Email::json_schema_input(JsonSchemaTarget::Draft07);
+    assert_eq!(schema["type"], "string");
+    assert_eq!(schema["maxLength"], 254);
+}
+
+#[test]
+fn test_newtype_schema_nested() {
+    let schema = Contact::json_schema_input(JsonSchemaTarget::Draft202012);
+    assert_eq!(
+        schema["properties"]["email"],
+        json!({ "type": "string", "maxLength": 254 })
+    );
+}
+
+#[test]
+fn test_tuple_struct_schema() {
+    let schema = Labelled::json_schema_input(JsonSchemaTarget::Draft202012);
+    assert_eq!(
+        schema["prefixItems"],
+        json!([
+            { "type": "string", "minLength": 1 },
+            { "type": "integer" }
+        ])
+    );
+    assert_eq!(schema["items"], false);
+    assert_eq!(schema["minItems"], 2);
+    assert_eq!(schema["maxItems"], 2);
+
+    let schema = Point::json_schema_input(JsonSchemaTarget::OpenApi30);
+    assert_eq!(schema["items"], json!({ "type": "number" }));
+    assert_eq!(schema["minItems"], 2);
+}
@@ -0,0 +1,107 @@
+//! Tests for unknown-key policies: strip, deny_unknown_fields and passthrough.
+
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+use valrs::{JsonSchemaTarget, PathSegment, StandardJsonSchema, Valrs};
+use valrs_derive::{StandardJsonSchema, Valrs};
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+struct Stripped {
+    name: String,
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+#[schema(deny_unknown_fields, require_one_of("email", "phone"))]
+struct Strict {
+    #[schema(alias = "userName")]
+    name: String,
+    #[schema(optional)]
+    email: Option<String>,
+    #[schema(optional)]
+    phone: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Deserialize, Valrs, StandardJsonSchema)]
+#[serde(deny_unknown_fields)]
+struct SerdeStrict {
+    id: u32,
+}
+
+#[derive(Debug, PartialEq, Valrs, StandardJsonSchema)]
+#[schema(passthrough, rename_all = "camelCase")]
+struct Event {
+    event_type: String,
+    #[schema(extra)]
+    properties: Map<String, Value>,
+}
+
+#[test]
+fn test_unknown_keys_are_stripped_by_default() {
+    let result = Stripped::validate(&json!({ "name": "Ada", "role": "admin" }));
+    assert_eq!(result.ok(), Some(Stripped { name: "Ada".into() }));
+
+    let schema = Stripped::json_schema_input(JsonSchemaTarget::Draft07);
+    assert_eq!(schema.get("additionalProperties"), None);
+}
+
+#[test]
+fn test_deny_unknown_fields() {
+    let result = Strict::validate(&json!({ "userName": "Ada", "email": "a@b.c" }));
+    assert_eq!(result.ok().map(|s| s.name), Some("Ada".into()));
+
+    let result = Strict::validate(&json!({ "name": "Ada", "phone": "1", "role": 1, "x": 2 }));
+    let issues = result.issues();
+    assert_eq!(issues.len(), 2);
+    assert_eq!(issues[0].message, "Unrecognized key 'role'");
+    assert_eq!(issues[0].path, Some(vec![PathSegment::Key("role".into())]));
+    assert_eq!(issues[1].path, Some(vec![PathSegment::Key("x".into())]));
+
+    let result = SerdeStrict::validate(&json!({ "id": 1, "extra": true }));
+    assert_eq!(result.issues()[0].message, "Unrecognized key 'extra'");
+    let serde_result: Result<SerdeStrict, _> =
+        serde_json::from_value(json!({ "id": 1, "extra": true }));
+    assert!(serde_result.is_err());
+}
+
+#[test]
+fn test_deny_unknown_fields_schema() {
+    let schema = Strict::json_schema_input(JsonSchemaTarget::Draft202012);
+    assert_eq!(schema["unevaluatedProperties"], false);
+    assert_eq!(schema.get("additionalProperties"), None);
+
+    for target in [JsonSchemaTarget::Draft07, JsonSchemaTarget::OpenApi30] {
+        let schema = Strict::json_schema_output(target);
+        assert_eq!(schema["additionalProperties"], false);
+        assert_eq!(schema.get("unevaluatedProperties"), None);
+    }
+    assert_eq!(
+        SerdeStrict::json_schema_input(JsonSchemaTarget::Draft07)["additionalProperties"],
+        false
+    );
+}
+
+#[test]
+fn test_passthrough_collects_unknown_keys() {
+    let result = Event::validate(&json!({
+        "eventType": "click",
+        "x": 10,
+        "target": { "id": "button" }
+    }));
+    let event = result.ok().unwrap();
+    assert_eq!(event.event_type, "click");
+    assert_eq!(
+        Value::Object(event.properties),
+        json!({ "x": 10, "target": { "id": "button" } })
+    );
+
+    let result = Event::validate(&json!({ "eventType": "load" }));
+    assert!(result.ok().unwrap().properties.is_empty());
+}
+
+#[test]
+fn test_passthrough_schema() {
+    let schema = Event::json_schema_input(JsonSchemaTarget::Draft202012);
+    assert_eq!(schema["additionalProperties"], true);
+    assert_eq!(schema["required"], json!(["eventType"]));
+    assert_eq!(schema["properties"].as_object().unwrap().len(), 1);
+}
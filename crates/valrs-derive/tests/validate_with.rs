@@ -0,0 +1,129 @@
+//! Tests for custom field validator functions.
+
+use serde_json::json;
+use valrs::{PathSegment, ValidationIssue, Valrs};
+use valrs_derive::Valrs;
+
+mod rules {
+    use valrs::{PathSegment, ValidationIssue};
+
+    pub fn check_sku(sku: &str) -> Result<(), String> {
+        if sku.starts_with("SKU-") {
+            Ok(())
+        } else {
+            Err(format!("Unknown SKU '{}'", sku))
+        }
+    }
+
+    pub fn not_reserved(sku: &str) -> Result<(), String> {
+        if sku == "SKU-0" {
+            Err("SKU is reserved".to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn unique(tags: &[String]) -> Vec<ValidationIssue> {
+        tags.iter()
+            .enumerate()
+            .filter(|(i, tag)| tags[..*i].contains(tag))
+            .map(|(i, _)| ValidationIssue::with_path("Duplicate tag", vec![PathSegment::Index(i)]))
+            .collect()
+    }
+
+    pub fn even(n: &u32) -> Result<(), String> {
+        if n.is_multiple_of(2) {
+            Ok(())
+        } else {
+            Err("Expected an even number".to_string())
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Valrs)]
+struct Item {
+    #[schema(
+        validate_with = "rules::check_sku",
+        validate_with = "rules::not_reserved",
+        min_length = 5
+    )]
+    sku: String,
+    #[schema(validate_with = "rules::unique")]
+    tags: Vec<String>,
+    #[schema(optional, rename = "packSize", validate_with = "rules::even")]
+    pack_size: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, Valrs)]
+struct Even(#[schema(validate_with = "rules::even")] u32);
+
+#[test]
+fn test_validators_pass() {
+    let result = Item::validate(&json!({ "sku": "SKU-1", "tags": ["a", "b"], "packSize": 6 }));
+    assert_eq!(
+        result.ok(),
+        Some(Item {
+            sku: "SKU-1".into(),
+            tags: vec!["a".into(), "b".into()],
+            pack_size: Some(6),
+        })
+    );
+}
+
+#[test]
+fn test_validators_run_in_order() {
+    let result = Item::validate(&json!({ "sku": "X", "tags": [] }));
+    let messages: Vec<_> = result.issues().iter().map(|i| &i.message).collect();
+    assert_eq!(
+        messages,
+        [
+            "String must be at least 5 characters, got 1",
+            "Unknown SKU 'X'"
+        ]
+    );
+
+    let result = Item::validate(&json!({ "sku": "SKU-0", "tags": [] }));
+    assert_eq!(result.issues().len(), 1);
+    assert_eq!(result.issues()[0].message, "SKU is reserved");
+    assert_eq!(
+        result.issues()[0].path,
+        Some(vec![PathSegment::Key("sku".into())])
+    );
+}
+
+#[test]
+fn test_issue_paths_are_prefixed() {
+    let result = Item::validate(&json!({
+        "sku": "SKU-1",
+        "tags": ["a", "b", "a"],
+        "packSize": 3
+    }));
+    let issues: Vec<&ValidationIssue> = result.issues().iter().collect();
+    assert_eq!(issues.len(), 2);
+    assert_eq!(issues[0].message, "Duplicate tag");
+    assert_eq!(
+        issues[0].path,
+        Some(vec![PathSegment::Key("tags".into()), PathSegment::Index(2)])
+    );
+    assert_eq!(issues[1].message, "Expected an even number");
+    assert_eq!(
+        issues[1].path,
+        Some(vec![PathSegment::Key("packSize".into())])
+    );
+}
+
+#[test]
+fn test_validators_skipped_when_type_fails() {
+    let result = Item::validate(&json!({ "sku": 1, "tags": [] }));
+    assert_eq!(result.issues().len(), 1);
+    assert_eq!(result.issues()[0].message, "Expected string");
+}
+
+#[test]
+fn test_newtype_validator() {
+    assert_eq!(Even::validate(&json!(4)).ok(), Some(Even(4)));
+
+    let result = Even::validate(&json!(3));
+    assert_eq!(result.issues()[0].message, "Expected an even number");
+    assert!(result.issues()[0].path.is_none());
+}
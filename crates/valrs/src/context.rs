@@ -0,0 +1,230 @@
+//! Validation options and user data, passed through validation.
+
+use std::any::{Any, TypeId};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::ValidationIssue;
+
+/// Options for a validation call, and data for custom validators.
+///
+/// `Valrs::validate_with` takes a context, which every built-in and derived
+/// implementation passes on to the values it contains; `Valrs::validate` uses
+/// the default context.
+///
+/// # Example
+///
+/// ```rust
+/// use serde_json::json;
+/// use valrs::{ValidationContext, Valrs};
+///
+/// struct Tenant(String);
+///
+/// let ctx = ValidationContext::new()
+///     .with_abort_early(true)
+///     .with_extension(Tenant("acme".to_string()));
+///
+/// let result = Vec::<u8>::validate_with(&json!([1, "two", "three"]), &ctx);
+/// assert_eq!(result.issues().len(), 1);
+/// assert_eq!(ctx.extension::<Tenant>().unwrap().0, "acme");
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct ValidationContext {
+    abort_early: bool,
+    max_issues: Option<usize>,
+    strict: bool,
+    coerce: bool,
+    enclosing_keys: Vec<String>,
+    extensions: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+impl ValidationContext {
+    /// Creates a context with the default options and no extensions.
+    pub fn new() -> Self {
+        ValidationContext::default()
+    }
+
+    /// Stops validation at the first issue.
+    pub fn with_abort_early(mut self, abort_early: bool) -> Self {
+        self.abort_early = abort_early;
+        self
+    }
+
+    /// Stops validation once `max_issues` issues were found, and reports at
+    /// most that many. At least one issue is always reported.
+    pub fn with_max_issues(mut self, max_issues: usize) -> Self {
+        self.max_issues = Some(max_issues);
+        self
+    }
+
+    /// Rejects unknown keys in all derived structs and struct variants of
+    /// derived enums, as if they had `deny_unknown_fields`. Structs with
+    /// `passthrough` still collect them, and structs and variants with
+    /// flattened fields are not checked.
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Converts input of another JSON type where the expected type has an
+    /// unambiguous reading of it: strings such as `"42"` to numbers, `"true"`,
+    /// `"false"`, `"1"` and `"0"` to booleans, and numbers to strings.
+    ///
+    /// Converted numbers are checked like any other, so `"300"` is still out of
+    /// range for `u8`.
+    pub fn with_coerce(mut self, coerce: bool) -> Self {
+        self.coerce = coerce;
+        self
+    }
+
+    /// Marks `key` of the next object validated as read by an enclosing type,
+    /// so that derived structs do not report it as unknown or collect it into
+    /// their `extra` field.
+    ///
+    /// Internally tagged enums use this for the tag, which the content of
+    /// newtype variants also holds. Derived structs clear these keys before
+    /// validating their fields, which are other objects.
+    pub fn with_enclosing_key(mut self, key: impl Into<String>) -> Self {
+        self.enclosing_keys.push(key.into());
+        self
+    }
+
+    /// Adds a value for custom validators to read with `extension`,
+    /// replacing any previous value of the same type.
+    pub fn with_extension<T: Send + Sync + 'static>(mut self, value: T) -> Self {
+        self.insert_extension(value);
+        self
+    }
+
+    /// Adds a value for custom validators to read with `extension`,
+    /// replacing any previous value of the same type.
+    pub fn insert_extension<T: Send + Sync + 'static>(&mut self, value: T) {
+        self.extensions.insert(TypeId::of::<T>(), Arc::new(value));
+    }
+
+    /// Returns whether validation stops at the first issue.
+    pub fn abort_early(&self) -> bool {
+        self.abort_early
+    }
+
+    /// Returns the maximum number of issues to report, if any.
+    pub fn max_issues(&self) -> Option<usize> {
+        self.max_issues
+    }
+
+    /// Returns whether unknown keys are rejected in all derived structs and
+    /// struct variants.
+    pub fn strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Returns whether input of another JSON type is converted.
+    pub fn coerce(&self) -> bool {
+        self.coerce
+    }
+
+    /// Returns whether `key` was marked with `with_enclosing_key`.
+    pub fn is_enclosing_key(&self, key: &str) -> bool {
+        self.enclosing_keys.iter().any(|k| k == key)
+    }
+
+    /// Returns this context without the keys marked with
+    /// `with_enclosing_key`, for validating values nested in the object they
+    /// belong to.
+    pub fn without_enclosing_keys(&self) -> Cow<'_, ValidationContext> {
+        if self.enclosing_keys.is_empty() {
+            Cow::Borrowed(self)
+        } else {
+            let mut ctx = self.clone();
+            ctx.enclosing_keys.clear();
+            Cow::Owned(ctx)
+        }
+    }
+
+    /// Returns the extension of type `T`, if any.
+    pub fn extension<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.extensions
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref())
+    }
+
+    /// Returns the number of issues after which validation stops, if any.
+    pub fn issue_limit(&self) -> Option<usize> {
+        if self.abort_early {
+            Some(1)
+        } else {
+            self.max_issues.map(|max| max.max(1))
+        }
+    }
+
+    /// Returns whether validation should stop once `issue_count` issues were
+    /// found.
+    ///
+    /// Implementations validating several values check this between values.
+    pub fn is_done(&self, issue_count: usize) -> bool {
+        self.issue_limit().is_some_and(|limit| issue_count >= limit)
+    }
+
+    /// Drops the issues beyond the issue limit.
+    pub fn truncate(&self, issues: &mut Vec<ValidationIssue>) {
+        if let Some(limit) = self.issue_limit() {
+            issues.truncate(limit);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issue_limit() {
+        let ctx = ValidationContext::new();
+        assert_eq!(ctx.issue_limit(), None);
+        assert!(!ctx.is_done(100));
+
+        let ctx = ValidationContext::new().with_max_issues(3);
+        assert!(!ctx.is_done(2));
+        assert!(ctx.is_done(3));
+
+        assert_eq!(
+            ValidationContext::new().with_max_issues(0).issue_limit(),
+            Some(1)
+        );
+
+        // abort_early wins over max_issues
+        let ctx = ctx.with_abort_early(true);
+        assert_eq!(ctx.issue_limit(), Some(1));
+        let mut issues = vec![ValidationIssue::new("a"), ValidationIssue::new("b")];
+        ctx.truncate(&mut issues);
+        assert_eq!(issues.len(), 1);
+    }
+
+    #[test]
+    fn test_extensions() {
+        #[derive(Debug, PartialEq)]
+        struct UserId(u64);
+
+        let mut ctx = ValidationContext::new().with_extension(UserId(1));
+        assert_eq!(ctx.extension::<UserId>(), Some(&UserId(1)));
+        assert_eq!(ctx.extension::<String>(), None);
+
+        ctx.insert_extension(UserId(2));
+        assert_eq!(ctx.extension::<UserId>(), Some(&UserId(2)));
+    }
+
+    #[test]
+    fn test_enclosing_keys() {
+        let ctx = ValidationContext::new().with_strict(true);
+        assert!(matches!(ctx.without_enclosing_keys(), Cow::Borrowed(_)));
+
+        let ctx = ctx.with_enclosing_key("type");
+        assert!(ctx.is_enclosing_key("type"));
+        assert!(!ctx.is_enclosing_key("kind"));
+
+        let nested = ctx.without_enclosing_keys();
+        assert!(!nested.is_enclosing_key("type"));
+        assert!(nested.strict());
+    }
+}
@@ -0,0 +1,618 @@
+//! Error maps, which turn an issue's code and params into its message.
+//!
+//! Built-in validators and derived types give their issues English messages.
+//! An error map can replace them, for example to translate them. This mirrors
+//! `setErrorMap` in the TypeScript package:
+//!
+//! - `set_error_map` sets the global map, used by all validation.
+//! - `Valrs::validate_with_error_map` uses a map for a single call, on top of
+//!   the global map.
+//!
+//! Maps apply when an issue is created by one of the `ValidationIssue`
+//! constructors for a specific code, such as `too_small`. Custom issues and
+//! custom messages of derived types are set by the user and kept as is.
+//!
+//! `MessageTable` is an error map of message templates keyed by issue code,
+//! which can be loaded from a JSON file; `MessageTable::english()` is the
+//! bundled English table.
+//!
+//! ```rust
+//! use serde_json::json;
+//! use valrs::{MessageTable, Valrs};
+//!
+//! let table = MessageTable::from_json(r#"{
+//!     "invalid_type": "Se esperaba {expected}",
+//!     "too_big.number": "El número debe ser como máximo {maximum}"
+//! }"#)
+//! .unwrap();
+//! valrs::set_error_map(table);
+//!
+//! let result = u8::validate(&json!(300));
+//! assert_eq!(result.issues()[0].message, "El número debe ser como máximo 255");
+//! ```
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, PoisonError, RwLock};
+
+use crate::{IssueCode, ValidationIssue};
+
+/// Turns validation issues into messages.
+///
+/// Closures taking a `&ValidationIssue` and returning an `Option<String>` are
+/// error maps.
+pub trait ErrorMap: Send + Sync {
+    /// Returns the message for `issue`, or `None` to keep its message.
+    ///
+    /// The issue's message is the default one: the built-in message, or the
+    /// one given by the error map applied before this one.
+    fn message(&self, issue: &ValidationIssue) -> Option<String>;
+}
+
+impl<F> ErrorMap for F
+where
+    F: Fn(&ValidationIssue) -> Option<String> + Send + Sync,
+{
+    fn message(&self, issue: &ValidationIssue) -> Option<String> {
+        self(issue)
+    }
+}
+
+static GLOBAL_ERROR_MAP: RwLock<Option<Arc<dyn ErrorMap>>> = RwLock::new(None);
+
+thread_local! {
+    static SCOPED_ERROR_MAPS: RefCell<Vec<Arc<dyn ErrorMap>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Sets the global error map, replacing any previous one.
+pub fn set_error_map(error_map: impl ErrorMap + 'static) {
+    *GLOBAL_ERROR_MAP
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(error_map));
+}
+
+/// Removes the global error map, restoring the built-in messages.
+pub fn reset_error_map() {
+    *GLOBAL_ERROR_MAP
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+/// Runs `f` with `error_map` applying to the issues created on this thread,
+/// after the global map and any enclosing scoped maps.
+pub fn with_error_map<R>(error_map: Arc<dyn ErrorMap>, f: impl FnOnce() -> R) -> R {
+    // Pops the map even if `f` panics
+    struct Scope;
+
+    impl Drop for Scope {
+        fn drop(&mut self) {
+            SCOPED_ERROR_MAPS.with_borrow_mut(|maps| maps.pop());
+        }
+    }
+
+    SCOPED_ERROR_MAPS.with_borrow_mut(|maps| maps.push(error_map));
+    let _scope = Scope;
+    f()
+}
+
+/// Lets the error maps in effect replace the message of `issue`: the global
+/// map, then the scoped maps from the outermost.
+pub(crate) fn apply(issue: &mut ValidationIssue) {
+    let global = GLOBAL_ERROR_MAP
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone();
+    if let Some(message) = global.and_then(|error_map| error_map.message(issue)) {
+        issue.message = message;
+    }
+
+    SCOPED_ERROR_MAPS.with_borrow(|maps| {
+        for error_map in maps {
+            if let Some(message) = error_map.message(issue) {
+                issue.message = message;
+            }
+        }
+    });
+}
+
+/// The placeholders message templates may use, all issue params.
+const PLACEHOLDERS: [&str; 9] = [
+    "expected",
+    "received",
+    "minimum",
+    "maximum",
+    "validation",
+    "multipleOf",
+    "keys",
+    "options",
+    "index",
+];
+
+/// The messages of the bundled English table.
+const ENGLISH: [(&str, &str); 42] = [
+    ("invalid_type", "Expected {expected}"),
+    ("too_small", "Value must be at least {minimum}"),
+    (
+        "too_small.exclusive",
+        "Value must be greater than {minimum}",
+    ),
+    ("too_small.exact", "Value must be exactly {minimum}"),
+    (
+        "too_small.string",
+        "String must be at least {minimum} characters",
+    ),
+    (
+        "too_small.string.exclusive",
+        "String must be more than {minimum} characters",
+    ),
+    (
+        "too_small.string.exact",
+        "String must be exactly {minimum} characters",
+    ),
+    (
+        "too_small.number",
+        "Number must be greater than or equal to {minimum}",
+    ),
+    (
+        "too_small.number.exclusive",
+        "Number must be greater than {minimum}",
+    ),
+    (
+        "too_small.array",
+        "Array must contain at least {minimum} items",
+    ),
+    (
+        "too_small.array.exclusive",
+        "Array must contain more than {minimum} items",
+    ),
+    (
+        "too_small.array.exact",
+        "Array must contain exactly {minimum} items",
+    ),
+    ("too_big", "Value must be at most {maximum}"),
+    ("too_big.exclusive", "Value must be less than {maximum}"),
+    ("too_big.exact", "Value must be exactly {maximum}"),
+    (
+        "too_big.string",
+        "String must be at most {maximum} characters",
+    ),
+    (
+        "too_big.string.exclusive",
+        "String must be fewer than {maximum} characters",
+    ),
+    (
+        "too_big.string.exact",
+        "String must be exactly {maximum} characters",
+    ),
+    (
+        "too_big.number",
+        "Number must be less than or equal to {maximum}",
+    ),
+    (
+        "too_big.number.exclusive",
+        "Number must be less than {maximum}",
+    ),
+    (
+        "too_big.array",
+        "Array must contain at most {maximum} items",
+    ),
+    (
+        "too_big.array.exclusive",
+        "Array must contain fewer than {maximum} items",
+    ),
+    (
+        "too_big.array.exact",
+        "Array must contain exactly {maximum} items",
+    ),
+    ("invalid_string", "Invalid string"),
+    ("invalid_string.email", "Invalid email address"),
+    ("invalid_string.url", "Invalid URL"),
+    ("invalid_string.uuid", "Invalid UUID"),
+    ("invalid_string.datetime", "Invalid datetime format"),
+    ("invalid_string.date", "Invalid date format"),
+    ("invalid_string.ip", "Invalid IP address"),
+    ("invalid_string.hostname", "Invalid hostname"),
+    ("invalid_string.regex", "String does not match pattern"),
+    (
+        "not_multiple_of",
+        "Number must be a multiple of {multipleOf}",
+    ),
+    ("missing_field", "Required"),
+    ("missing_field.one_of", "At least one of {keys} is required"),
+    ("not_unique", "Duplicate item, same as index {index}"),
+    ("not_unique.key", "Duplicate key {keys}"),
+    ("key_conflict", "Conflicting keys: {keys}"),
+    ("unrecognized_keys", "Unrecognized key(s) in object: {keys}"),
+    (
+        "invalid_enum_value",
+        "Expected one of {options}, got \"{received}\"",
+    ),
+    ("invalid_union", "Value does not match any variant"),
+    ("invalid_union_discriminator", "Expected one of {options}"),
+];
+
+/// An error reading a message table.
+#[derive(Debug, thiserror::Error)]
+pub enum MessageTableError {
+    /// The file could not be read.
+    #[error("failed to read message table: {0}")]
+    Io(#[from] std::io::Error),
+    /// The file is not a JSON object of strings.
+    #[error("invalid message table: {0}")]
+    Json(#[from] serde_json::Error),
+    /// A message template is invalid.
+    #[error("invalid message for `{key}`: {reason}")]
+    Template {
+        /// The key of the message.
+        key: String,
+        /// What is wrong with it.
+        reason: String,
+    },
+}
+
+/// An error map of message templates keyed by issue code.
+///
+/// Keys are issue codes as serialized, such as `invalid_type`, optionally
+/// followed by the kind of value and the kind of bound for size issues
+/// (`too_small.string`, `too_big.number.exclusive`, `too_small.array.exact`),
+/// by the format for format issues (`invalid_string.email`), by `one_of` for
+/// a missing field among alternatives (`missing_field.one_of`), or by `key`
+/// for a duplicate map key (`not_unique.key`). The most
+/// specific message is used: for an exclusive number bound,
+/// `too_small.number.exclusive`, then `too_small.exclusive`, then `too_small`.
+/// Issues without a message keep their default message.
+///
+/// Templates refer to issue params as `{expected}`, `{received}`,
+/// `{minimum}`, `{maximum}`, `{validation}`, `{multipleOf}`, `{keys}`,
+/// `{options}` and `{index}`; `{{` and `}}` are literal braces.
+#[derive(Debug, Clone, Default)]
+pub struct MessageTable {
+    messages: HashMap<String, Vec<Piece>>,
+}
+
+/// A part of a message template.
+#[derive(Debug, Clone)]
+enum Piece {
+    Text(String),
+    Placeholder(&'static str),
+}
+
+impl MessageTable {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        MessageTable::default()
+    }
+
+    /// Returns the bundled English table.
+    pub fn english() -> Self {
+        let mut table = MessageTable::new();
+        for (key, template) in ENGLISH {
+            table
+                .insert(key, template)
+                .expect("bundled templates are valid");
+        }
+        table
+    }
+
+    /// Reads a table from a JSON object mapping keys to templates.
+    pub fn from_json(json: &str) -> Result<Self, MessageTableError> {
+        let messages: HashMap<String, String> = serde_json::from_str(json)?;
+        let mut table = MessageTable::new();
+        for (key, template) in messages {
+            table.insert(key, &template)?;
+        }
+        Ok(table)
+    }
+
+    /// Reads a table from a JSON file, as in `from_json`.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, MessageTableError> {
+        MessageTable::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Adds or replaces the message for `key`.
+    pub fn insert(
+        &mut self,
+        key: impl Into<String>,
+        template: &str,
+    ) -> Result<(), MessageTableError> {
+        let key = key.into();
+        match parse_template(template) {
+            Ok(pieces) => {
+                self.messages.insert(key, pieces);
+                Ok(())
+            }
+            Err(reason) => Err(MessageTableError::Template { key, reason }),
+        }
+    }
+
+    /// Returns the template for the most specific key matching `issue`.
+    fn lookup(&self, issue: &ValidationIssue) -> Option<&[Piece]> {
+        let code = issue.code.as_str();
+        let params = &issue.params;
+        let subject = match issue.code {
+            IssueCode::TooSmall | IssueCode::TooBig => {
+                params.size_type.map(|size_type| size_type.as_str())
+            }
+            IssueCode::InvalidFormat => params.validation.as_deref(),
+            IssueCode::MissingField => params.keys.as_ref().map(|_| "one_of"),
+            IssueCode::NotUnique => params.keys.as_ref().map(|_| "key"),
+            _ => None,
+        };
+        let bound = if params.exact == Some(true) {
+            Some("exact")
+        } else if params.inclusive == Some(false) {
+            Some("exclusive")
+        } else {
+            None
+        };
+
+        let mut keys = Vec::new();
+        match (subject, bound) {
+            (Some(subject), Some(bound)) => {
+                keys.push(format!("{}.{}.{}", code, subject, bound));
+                keys.push(format!("{}.{}", code, bound));
+            }
+            (Some(subject), None) => keys.push(format!("{}.{}", code, subject)),
+            (None, Some(bound)) => keys.push(format!("{}.{}", code, bound)),
+            (None, None) => {}
+        }
+        keys.push(code.to_string());
+
+        keys.iter()
+            .find_map(|key| self.messages.get(key))
+            .map(Vec::as_slice)
+    }
+}
+
+impl ErrorMap for MessageTable {
+    fn message(&self, issue: &ValidationIssue) -> Option<String> {
+        let pieces = self.lookup(issue)?;
+        let mut message = String::new();
+        for piece in pieces {
+            match piece {
+                Piece::Text(text) => message.push_str(text),
+                Piece::Placeholder(name) => message.push_str(&param(issue, name)),
+            }
+        }
+        Some(message)
+    }
+}
+
+/// Parses a template into text and placeholders.
+fn parse_template(template: &str) -> Result<Vec<Piece>, String> {
+    let mut pieces = Vec::new();
+    let mut text = String::new();
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                text.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                text.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err("unclosed `{`".to_string()),
+                    }
+                }
+                let Some(placeholder) = PLACEHOLDERS
+                    .iter()
+                    .find(|placeholder| **placeholder == name)
+                else {
+                    return Err(format!(
+                        "unknown placeholder {{{}}}, expected one of {}",
+                        name,
+                        PLACEHOLDERS
+                            .iter()
+                            .map(|placeholder| format!("{{{}}}", placeholder))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ));
+                };
+                if !text.is_empty() {
+                    pieces.push(Piece::Text(std::mem::take(&mut text)));
+                }
+                pieces.push(Piece::Placeholder(placeholder));
+            }
+            '}' => return Err("unmatched `}`, use `}}` for a literal brace".to_string()),
+            c => text.push(c),
+        }
+    }
+
+    if !text.is_empty() {
+        pieces.push(Piece::Text(text));
+    }
+    Ok(pieces)
+}
+
+/// Formats the issue param named `name` for a message, empty if it is unset.
+fn param(issue: &ValidationIssue, name: &str) -> String {
+    let params = &issue.params;
+    let list = |values: &Option<Vec<String>>| {
+        values
+            .iter()
+            .flatten()
+            .map(|value| format!("{:?}", value))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    match name {
+        "expected" => params.expected.clone().unwrap_or_default(),
+        "received" => params.received.clone().unwrap_or_default(),
+        "minimum" => params
+            .minimum
+            .as_ref()
+            .map(ToString::to_string)
+            .unwrap_or_default(),
+        "maximum" => params
+            .maximum
+            .as_ref()
+            .map(ToString::to_string)
+            .unwrap_or_default(),
+        "validation" => params.validation.clone().unwrap_or_default(),
+        "multipleOf" => params
+            .multiple_of
+            .as_ref()
+            .map(ToString::to_string)
+            .unwrap_or_default(),
+        "keys" => list(&params.keys),
+        "options" => list(&params.options),
+        "index" => params
+            .index
+            .as_ref()
+            .map(ToString::to_string)
+            .unwrap_or_default(),
+        _ => unreachable!("placeholders are checked when parsing templates"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SizeType, ValidationContext, Valrs};
+    use serde_json::json;
+
+    #[test]
+    fn test_english_table() {
+        let english = MessageTable::english();
+
+        let issue = ValidationIssue::too_small("", SizeType::String, 3.0, true);
+        assert_eq!(
+            english.message(&issue).unwrap(),
+            "String must be at least 3 characters"
+        );
+
+        let issue = ValidationIssue::too_big("", SizeType::Number, 1.5, false);
+        assert_eq!(
+            english.message(&issue).unwrap(),
+            "Number must be less than 1.5"
+        );
+
+        let issue = ValidationIssue::exact_size("", SizeType::Array, 2.0, 3.0);
+        assert_eq!(
+            english.message(&issue).unwrap(),
+            "Array must contain exactly 2 items"
+        );
+
+        let issue = ValidationIssue::invalid_format("", "email");
+        assert_eq!(english.message(&issue).unwrap(), "Invalid email address");
+
+        let issue = ValidationIssue::unknown_key("", "role");
+        assert_eq!(
+            english.message(&issue).unwrap(),
+            "Unrecognized key(s) in object: \"role\""
+        );
+
+        let issue = ValidationIssue::missing_field("");
+        assert_eq!(english.message(&issue).unwrap(), "Required");
+        let issue = ValidationIssue::missing_one_of("", &["email", "phone"]);
+        assert_eq!(
+            english.message(&issue).unwrap(),
+            "At least one of \"email\", \"phone\" is required"
+        );
+
+        let issue = ValidationIssue::not_unique("", 1);
+        assert_eq!(
+            english.message(&issue).unwrap(),
+            "Duplicate item, same as index 1"
+        );
+        let issue = ValidationIssue::duplicate_key("", "A");
+        assert_eq!(english.message(&issue).unwrap(), "Duplicate key \"A\"");
+
+        // Custom issues keep their message
+        assert_eq!(english.message(&ValidationIssue::new("Unknown SKU")), None);
+    }
+
+    #[test]
+    fn test_message_table_fallback_keys() {
+        let table = MessageTable::from_json(
+            r#"{ "too_small": "Trop petit", "too_small.exclusive": "Doit dépasser {minimum}" }"#,
+        )
+        .unwrap();
+
+        let issue = ValidationIssue::too_small("", SizeType::Number, 0.0, true);
+        assert_eq!(table.message(&issue).unwrap(), "Trop petit");
+        let issue = ValidationIssue::too_small("", SizeType::Number, 0.0, false);
+        assert_eq!(table.message(&issue).unwrap(), "Doit dépasser 0");
+        let issue = ValidationIssue::invalid_type("", "string", &json!(1));
+        assert_eq!(table.message(&issue), None);
+    }
+
+    #[test]
+    fn test_message_table_errors() {
+        assert!(matches!(
+            MessageTable::from_json("[]"),
+            Err(MessageTableError::Json(_))
+        ));
+
+        let err = MessageTable::from_json(r#"{ "too_small": "At least {min}" }"#).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid message for `too_small`: unknown placeholder {min}, expected one of \
+             {expected}, {received}, {minimum}, {maximum}, {validation}, {multipleOf}, {keys}, \
+             {options}, {index}"
+        );
+
+        let mut table = MessageTable::new();
+        assert!(table.insert("custom", "{{literal}}").is_ok());
+        assert!(table.insert("custom", "unmatched }").is_err());
+        assert!(table.insert("custom", "unclosed {expected").is_err());
+    }
+
+    #[test]
+    fn test_validate_with_error_map() {
+        let table: Arc<dyn ErrorMap> = Arc::new(MessageTable::english());
+        let ctx = ValidationContext::default();
+        let result = u8::validate_with_error_map(&json!(256), &ctx, table.clone());
+        assert_eq!(
+            result.issues()[0].message,
+            "Number must be less than or equal to 255"
+        );
+
+        // The context applies as in `validate_with`
+        let ctx = ValidationContext::new().with_coerce(true);
+        let result = u8::validate_with_error_map(&json!("256"), &ctx, table);
+        assert_eq!(
+            result.issues()[0].message,
+            "Number must be less than or equal to 255"
+        );
+
+        // The map only applies to the call
+        let result = u8::validate(&json!(256));
+        assert_eq!(
+            result.issues()[0].message,
+            "Integer out of range for u8 (0 to 255)"
+        );
+    }
+
+    #[test]
+    fn test_nested_error_maps() {
+        let outer: Arc<dyn ErrorMap> = Arc::new(|issue: &ValidationIssue| {
+            (issue.code == IssueCode::InvalidType).then(|| "outer".to_string())
+        });
+        let inner: Arc<dyn ErrorMap> =
+            Arc::new(|issue: &ValidationIssue| Some(format!("{} (inner)", issue.message)));
+
+        let message = with_error_map(outer, || {
+            with_error_map(inner, || {
+                ValidationIssue::invalid_type("Expected string", "string", &json!(1)).message
+            })
+        });
+        assert_eq!(message, "outer (inner)");
+
+        // Custom issues are not mapped
+        let always: Arc<dyn ErrorMap> = Arc::new(|_: &ValidationIssue| Some("mapped".to_string()));
+        let issue = with_error_map(always, || ValidationIssue::new("Unknown SKU"));
+        assert_eq!(issue.message, "Unknown SKU");
+    }
+}
@@ -28,9 +28,20 @@
 //! }
 //! ```
 
+mod context;
+pub mod error_map;
+mod maybe;
 mod traits;
 mod types;
 pub mod validators;
 
+pub use context::ValidationContext;
+pub use error_map::{
+    ErrorMap, MessageTable, MessageTableError, reset_error_map, set_error_map, with_error_map,
+};
+pub use maybe::Maybe;
 pub use traits::{StandardJsonSchema, Valrs};
-pub use types::{JsonSchemaTarget, PathSegment, ValidationIssue, ValidationResult};
+pub use types::{
+    IntoIssues, IssueCode, IssueParams, JsonSchemaTarget, NumberBound, ObjectProperties,
+    PathSegment, SizeType, ValidationIssue, ValidationResult,
+};
@@ -0,0 +1,158 @@
+//! A tri-state value for fields that can be absent, null or set.
+
+use crate::validators::{nullable_schema, strip_schema_uri};
+use crate::{JsonSchemaTarget, StandardJsonSchema, ValidationContext, ValidationResult, Valrs};
+use serde_json::Value;
+
+/// A value that distinguishes an absent field from an explicit `null`.
+///
+/// `Option<T>` maps both to `None`, which loses information for partial
+/// updates, where `null` usually means "clear" and a missing field means "leave
+/// unchanged". As a field of a derived struct, `Maybe<T>` is `Absent` when the
+/// key is missing, `Null` when it is `null` and `Value` otherwise. Validated on
+/// its own, a value is never absent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Maybe<T> {
+    /// The field was not given.
+    #[default]
+    Absent,
+    /// The field was given as `null`.
+    Null,
+    /// The field was given a value.
+    Value(T),
+}
+
+impl<T> Maybe<T> {
+    /// Returns `true` if the field was not given.
+    pub fn is_absent(&self) -> bool {
+        matches!(self, Maybe::Absent)
+    }
+
+    /// Returns `true` if the field was given as `null`.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Maybe::Null)
+    }
+
+    /// Returns `true` if the field was given a value.
+    pub fn is_value(&self) -> bool {
+        matches!(self, Maybe::Value(_))
+    }
+
+    /// Converts from `&Maybe<T>` to `Maybe<&T>`.
+    pub fn as_ref(&self) -> Maybe<&T> {
+        match self {
+            Maybe::Absent => Maybe::Absent,
+            Maybe::Null => Maybe::Null,
+            Maybe::Value(value) => Maybe::Value(value),
+        }
+    }
+
+    /// Returns the value, if the field was given one.
+    pub fn value(self) -> Option<T> {
+        match self {
+            Maybe::Value(value) => Some(value),
+            Maybe::Absent | Maybe::Null => None,
+        }
+    }
+
+    /// Converts to `None` if the field was not given, `Some(None)` if it was
+    /// `null`, and `Some(Some(value))` otherwise.
+    pub fn into_option(self) -> Option<Option<T>> {
+        match self {
+            Maybe::Absent => None,
+            Maybe::Null => Some(None),
+            Maybe::Value(value) => Some(Some(value)),
+        }
+    }
+
+    /// Maps the value with `f`, keeping `Absent` and `Null`.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Maybe<U> {
+        match self {
+            Maybe::Absent => Maybe::Absent,
+            Maybe::Null => Maybe::Null,
+            Maybe::Value(value) => Maybe::Value(f(value)),
+        }
+    }
+}
+
+impl<T: Valrs> Valrs for Maybe<T> {
+    type Input = Maybe<T::Input>;
+    type Output = Maybe<T::Output>;
+
+    fn validate(value: &Value) -> ValidationResult<Self::Output> {
+        Self::validate_with(value, &ValidationContext::default())
+    }
+
+    fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
+        match value {
+            Value::Null => ValidationResult::success(Maybe::Null),
+            _ => T::validate_with(value, ctx).map(Maybe::Value),
+        }
+    }
+}
+
+impl<T: StandardJsonSchema> StandardJsonSchema for Maybe<T> {
+    fn json_schema_input(target: JsonSchemaTarget) -> Value {
+        nullable_schema(strip_schema_uri(T::json_schema_input(target)), target)
+    }
+
+    fn json_schema_output(target: JsonSchemaTarget) -> Value {
+        nullable_schema(strip_schema_uri(T::json_schema_output(target)), target)
+    }
+
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        nullable_schema(
+            strip_schema_uri(T::json_schema_coerced_input(target)),
+            target,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_maybe_validation() {
+        let result = <Maybe<String>>::validate(&json!(null));
+        assert_eq!(result.ok(), Some(Maybe::Null));
+
+        let result = <Maybe<String>>::validate(&json!("hello"));
+        assert_eq!(result.ok(), Some(Maybe::Value("hello".to_string())));
+
+        assert!(<Maybe<String>>::validate(&json!(1)).is_failure());
+    }
+
+    #[test]
+    fn test_maybe_conversions() {
+        let value = Maybe::Value(2);
+        assert!(value.is_value());
+        assert_eq!(value.map(|n| n * 2).value(), Some(4));
+        assert_eq!(value.as_ref().into_option(), Some(Some(&2)));
+
+        assert!(Maybe::<i32>::default().is_absent());
+        assert_eq!(Maybe::<i32>::Absent.into_option(), None);
+        assert!(Maybe::<i32>::Null.is_null());
+        assert_eq!(Maybe::<i32>::Null.into_option(), Some(None));
+        assert_eq!(Maybe::<i32>::Null.value(), None);
+    }
+
+    #[test]
+    fn test_maybe_json_schema() {
+        let schema =
+            <Maybe<i32> as StandardJsonSchema>::json_schema_input(JsonSchemaTarget::OpenApi30);
+        assert_eq!(schema, json!({ "type": "integer", "nullable": true }));
+
+        let schema =
+            <Maybe<i32> as StandardJsonSchema>::json_schema_output(JsonSchemaTarget::Draft07);
+        assert_eq!(schema["anyOf"][1], json!({ "type": "null" }));
+
+        let schema = <Maybe<(i32, String)> as StandardJsonSchema>::json_schema_input(
+            JsonSchemaTarget::Draft202012,
+        );
+        let inner = &schema["anyOf"][0];
+        assert!(inner["prefixItems"].is_array());
+        assert!(inner.get("$schema").is_none());
+    }
+}
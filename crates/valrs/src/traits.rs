@@ -1,4 +1,8 @@
-use crate::types::{JsonSchemaTarget, ValidationResult};
+use std::sync::Arc;
+
+use crate::context::ValidationContext;
+use crate::error_map::{self, ErrorMap};
+use crate::types::{JsonSchemaTarget, ObjectProperties, ValidationResult};
 use serde_json::Value;
 
 /// The core Standard Schema trait for runtime validation.
@@ -55,6 +59,37 @@ pub trait Valrs: Sized {
     ///
     /// A `ValidationResult` containing either the validated output or validation issues.
     fn validate(value: &Value) -> ValidationResult<Self::Output>;
+
+    /// Validates an unknown JSON value with `ctx` and returns a typed result.
+    ///
+    /// The default implementation ignores `ctx` and calls `validate`. Types
+    /// that contain other values override it to pass `ctx` on to them, and
+    /// stop validating further values once `ctx.is_done` with the issues
+    /// found; their `validate` then calls this with the default context.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The JSON value to validate
+    /// * `ctx` - The validation options and user data
+    ///
+    /// # Returns
+    ///
+    /// A `ValidationResult` containing either the validated output or validation issues.
+    fn validate_with(value: &Value, _ctx: &ValidationContext) -> ValidationResult<Self::Output> {
+        Self::validate(value)
+    }
+
+    /// Validates a value like `validate_with`, with `error_map` producing the
+    /// messages of its issues on top of the global error map.
+    ///
+    /// See the `error_map` module.
+    fn validate_with_error_map(
+        value: &Value,
+        ctx: &ValidationContext,
+        error_map: Arc<dyn ErrorMap>,
+    ) -> ValidationResult<Self::Output> {
+        error_map::with_error_map(error_map, || Self::validate_with(value, ctx))
+    }
 }
 
 /// Extended trait for schemas that can generate JSON Schema.
@@ -96,4 +131,48 @@ pub trait StandardJsonSchema: Valrs {
     ///
     /// A JSON value representing the schema.
     fn json_schema_output(target: JsonSchemaTarget) -> Value;
+
+    /// Generates a JSON Schema for the input accepted in coercion mode (see
+    /// `ValidationContext::with_coerce`), such as numeric strings for numbers.
+    ///
+    /// Derived types use it for fields with `#[schema(coerce)]`. The default
+    /// implementation returns `json_schema_input`, for types that convert
+    /// nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The target JSON Schema version
+    ///
+    /// # Returns
+    ///
+    /// A JSON value representing the schema.
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        Self::json_schema_input(target)
+    }
+
+    /// Returns the properties of the input schema, if it is a plain object
+    /// schema whose properties can be merged into an enclosing object.
+    ///
+    /// This is used for flattened fields. Derived structs build them from
+    /// their fields; the default implementation extracts them from
+    /// `json_schema_input` with `ObjectProperties::from_schema`.
+    fn json_schema_input_properties(target: JsonSchemaTarget) -> Option<ObjectProperties> {
+        ObjectProperties::from_schema(&Self::json_schema_input(target))
+    }
+
+    /// Returns the properties of the output schema, if it is a plain object
+    /// schema whose properties can be merged into an enclosing object.
+    ///
+    /// See `json_schema_input_properties`.
+    fn json_schema_output_properties(target: JsonSchemaTarget) -> Option<ObjectProperties> {
+        ObjectProperties::from_schema(&Self::json_schema_output(target))
+    }
+
+    /// Returns the properties of the coerced input schema, if it is a plain
+    /// object schema whose properties can be merged into an enclosing object.
+    ///
+    /// See `json_schema_input_properties`.
+    fn json_schema_coerced_input_properties(target: JsonSchemaTarget) -> Option<ObjectProperties> {
+        ObjectProperties::from_schema(&Self::json_schema_coerced_input(target))
+    }
 }
@@ -1,4 +1,6 @@
+use crate::error_map;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Number, Value};
 
 /// The result of a validation operation.
 ///
@@ -75,14 +77,7 @@ impl<T> ValidationResult<T> {
             ValidationResult::Failure(issues) => {
                 let issues = issues
                     .into_iter()
-                    .map(|mut issue| {
-                        let mut new_path = vec![segment.clone()];
-                        if let Some(path) = issue.path.take() {
-                            new_path.extend(path);
-                        }
-                        issue.path = Some(new_path);
-                        issue
-                    })
+                    .map(|issue| issue.with_path_prefix(segment.clone()))
                     .collect();
                 ValidationResult::Failure(issues)
             }
@@ -114,35 +109,614 @@ impl<T: Serialize> Serialize for ValidationResult<T> {
 
 /// A validation issue describing why validation failed.
 ///
-/// This corresponds to the `Issue` interface in the TypeScript spec.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// This corresponds to the `Issue` interface in the TypeScript spec, extended
+/// with a machine-readable `code` and its `params`. Issues serialize like the
+/// TypeScript `ValIssue` shapes, with the params flattened next to `message`
+/// and `path`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ValidationIssue {
+    /// The kind of issue.
+    #[serde(default)]
+    pub code: IssueCode,
+
     /// The error message describing the issue.
     pub message: String,
 
     /// The path to the value that caused the issue.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub path: Option<Vec<PathSegment>>,
+
+    /// Details of the issue, depending on its code.
+    #[serde(flatten)]
+    pub params: IssueParams,
 }
 
 impl ValidationIssue {
-    /// Creates a new issue with a message.
+    /// Creates a new custom issue with a message.
+    ///
+    /// Error maps only apply to issues created by the constructors for a
+    /// specific code, such as `too_small`.
     pub fn new(message: impl Into<String>) -> Self {
         ValidationIssue {
+            code: IssueCode::Custom,
             message: message.into(),
             path: None,
+            params: IssueParams::default(),
         }
     }
 
-    /// Creates a new issue with a message and path.
+    /// Creates a new custom issue with a message and path.
     pub fn with_path(message: impl Into<String>, path: Vec<PathSegment>) -> Self {
-        ValidationIssue {
-            message: message.into(),
-            path: Some(path),
+        ValidationIssue::new(message).at(path)
+    }
+
+    /// Creates an issue for a value of the wrong JSON type, where `expected`
+    /// names the expected type.
+    pub fn invalid_type(message: impl Into<String>, expected: &str, received: &Value) -> Self {
+        ValidationIssue::coded(
+            message.into(),
+            IssueCode::InvalidType,
+            IssueParams {
+                expected: Some(expected.to_string()),
+                received: Some(json_type_name(received).to_string()),
+                ..IssueParams::default()
+            },
+        )
+    }
+
+    /// Creates an issue for a value below `minimum`, or not above it if the
+    /// bound is not `inclusive`. The size of strings and arrays is their length.
+    pub fn too_small(
+        message: impl Into<String>,
+        size_type: SizeType,
+        minimum: impl Into<NumberBound>,
+        inclusive: bool,
+    ) -> Self {
+        ValidationIssue::coded(
+            message.into(),
+            IssueCode::TooSmall,
+            IssueParams {
+                size_type: Some(size_type),
+                minimum: minimum.into().to_number(),
+                inclusive: Some(inclusive),
+                ..IssueParams::default()
+            },
+        )
+    }
+
+    /// Creates an issue for a value above `maximum`, or not below it if the
+    /// bound is not `inclusive`. The size of strings and arrays is their length.
+    pub fn too_big(
+        message: impl Into<String>,
+        size_type: SizeType,
+        maximum: impl Into<NumberBound>,
+        inclusive: bool,
+    ) -> Self {
+        ValidationIssue::coded(
+            message.into(),
+            IssueCode::TooBig,
+            IssueParams {
+                size_type: Some(size_type),
+                maximum: maximum.into().to_number(),
+                inclusive: Some(inclusive),
+                ..IssueParams::default()
+            },
+        )
+    }
+
+    /// Creates an issue for a size other than exactly `size`: too small if
+    /// `actual` is below it, else too big.
+    pub fn exact_size(
+        message: impl Into<String>,
+        size_type: SizeType,
+        size: f64,
+        actual: f64,
+    ) -> Self {
+        let (code, minimum, maximum) = if actual < size {
+            (IssueCode::TooSmall, number(size), None)
+        } else {
+            (IssueCode::TooBig, None, number(size))
+        };
+        ValidationIssue::coded(
+            message.into(),
+            code,
+            IssueParams {
+                size_type: Some(size_type),
+                minimum,
+                maximum,
+                inclusive: Some(true),
+                exact: Some(true),
+                ..IssueParams::default()
+            },
+        )
+    }
+
+    /// Creates an issue for a string not in the format named `validation`,
+    /// such as `email` or `regex`.
+    pub fn invalid_format(message: impl Into<String>, validation: &str) -> Self {
+        ValidationIssue::coded(
+            message.into(),
+            IssueCode::InvalidFormat,
+            IssueParams {
+                validation: Some(validation.to_string()),
+                ..IssueParams::default()
+            },
+        )
+    }
+
+    /// Creates an issue for a number that is not a multiple of `multiple_of`.
+    pub fn not_multiple_of(
+        message: impl Into<String>,
+        multiple_of: impl Into<NumberBound>,
+    ) -> Self {
+        ValidationIssue::coded(
+            message.into(),
+            IssueCode::NotMultipleOf,
+            IssueParams {
+                multiple_of: multiple_of.into().to_number(),
+                ..IssueParams::default()
+            },
+        )
+    }
+
+    /// Creates an issue for a missing required field.
+    pub fn missing_field(message: impl Into<String>) -> Self {
+        ValidationIssue::coded(
+            message.into(),
+            IssueCode::MissingField,
+            IssueParams::default(),
+        )
+    }
+
+    /// Creates an issue for an object missing all of the alternative `keys`,
+    /// of which at least one is required.
+    pub fn missing_one_of(message: impl Into<String>, keys: &[&str]) -> Self {
+        ValidationIssue::coded(
+            message.into(),
+            IssueCode::MissingField,
+            IssueParams {
+                keys: Some(keys.iter().map(|key| key.to_string()).collect()),
+                ..IssueParams::default()
+            },
+        )
+    }
+
+    /// Creates an issue for an array item equal to the item at `index`.
+    pub fn not_unique(message: impl Into<String>, index: usize) -> Self {
+        ValidationIssue::coded(
+            message.into(),
+            IssueCode::NotUnique,
+            IssueParams {
+                index: Some(index),
+                ..IssueParams::default()
+            },
+        )
+    }
+
+    /// Creates an issue for a map `key` that reads as the same key as an
+    /// earlier one.
+    pub fn duplicate_key(message: impl Into<String>, key: &str) -> Self {
+        ValidationIssue::coded(
+            message.into(),
+            IssueCode::NotUnique,
+            IssueParams {
+                keys: Some(vec![key.to_string()]),
+                ..IssueParams::default()
+            },
+        )
+    }
+
+    /// Creates an issue for an object with a field under several of its
+    /// names, the conflicting `keys`.
+    pub fn key_conflict(message: impl Into<String>, keys: &[&str]) -> Self {
+        ValidationIssue::coded(
+            message.into(),
+            IssueCode::KeyConflict,
+            IssueParams {
+                keys: Some(keys.iter().map(|key| key.to_string()).collect()),
+                ..IssueParams::default()
+            },
+        )
+    }
+
+    /// Creates an issue for an object key that is not allowed.
+    pub fn unknown_key(message: impl Into<String>, key: &str) -> Self {
+        ValidationIssue::coded(
+            message.into(),
+            IssueCode::UnknownKey,
+            IssueParams {
+                keys: Some(vec![key.to_string()]),
+                ..IssueParams::default()
+            },
+        )
+    }
+
+    /// Creates an issue for a string that is not one of `options`.
+    pub fn invalid_enum_value(
+        message: impl Into<String>,
+        options: &[&str],
+        received: &str,
+    ) -> Self {
+        ValidationIssue::coded(
+            message.into(),
+            IssueCode::InvalidEnumValue,
+            IssueParams {
+                options: Some(options.iter().map(|option| option.to_string()).collect()),
+                received: Some(received.to_string()),
+                ..IssueParams::default()
+            },
+        )
+    }
+
+    /// Creates an issue for a tag that names none of the variants in `options`.
+    pub fn invalid_union_discriminator(message: impl Into<String>, options: &[&str]) -> Self {
+        ValidationIssue::coded(
+            message.into(),
+            IssueCode::InvalidUnionDiscriminator,
+            IssueParams {
+                options: Some(options.iter().map(|option| option.to_string()).collect()),
+                ..IssueParams::default()
+            },
+        )
+    }
+
+    /// Creates an issue for a value that matches none of the variants of an
+    /// untagged enum, where `union_errors` holds the issues of each variant.
+    pub fn invalid_union(
+        message: impl Into<String>,
+        union_errors: Vec<Vec<ValidationIssue>>,
+    ) -> Self {
+        ValidationIssue::coded(
+            message.into(),
+            IssueCode::InvalidUnion,
+            IssueParams {
+                union_errors: Some(union_errors),
+                ..IssueParams::default()
+            },
+        )
+    }
+
+    /// Creates an issue with a code and params, then lets the error maps in
+    /// effect replace its message.
+    fn coded(message: String, code: IssueCode, params: IssueParams) -> Self {
+        let mut issue = ValidationIssue {
+            code,
+            message,
+            path: None,
+            params,
+        };
+        error_map::apply(&mut issue);
+        issue
+    }
+
+    /// Replaces the issue's message. Error maps do not apply to it.
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = message.into();
+        self
+    }
+
+    /// Sets the issue's code.
+    pub fn with_code(mut self, code: IssueCode) -> Self {
+        self.code = code;
+        self
+    }
+
+    /// Sets the issue's params.
+    pub fn with_params(mut self, params: IssueParams) -> Self {
+        self.params = params;
+        self
+    }
+
+    /// Sets the issue's path.
+    pub fn at(mut self, path: Vec<PathSegment>) -> Self {
+        self.path = Some(path);
+        self
+    }
+
+    /// Prepends a path segment to the issue's path, and to those of its
+    /// union errors.
+    pub fn with_path_prefix(mut self, segment: PathSegment) -> Self {
+        if let Some(union_errors) = self.params.union_errors.take() {
+            self.params.union_errors = Some(
+                union_errors
+                    .into_iter()
+                    .map(|issues| {
+                        issues
+                            .into_iter()
+                            .map(|issue| issue.with_path_prefix(segment.clone()))
+                            .collect()
+                    })
+                    .collect(),
+            );
+        }
+        let mut new_path = vec![segment];
+        if let Some(path) = self.path.take() {
+            new_path.extend(path);
+        }
+        self.path = Some(new_path);
+        self
+    }
+}
+
+/// A machine-readable code identifying the kind of a validation issue.
+///
+/// Codes serialize as the matching TypeScript `ValIssueCode`; `MissingField`,
+/// `NotUnique` and `KeyConflict` have no TypeScript counterpart and serialize
+/// as `missing_field`, `not_unique` and `key_conflict`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueCode {
+    /// The value has the wrong JSON type (`expected`, `received`).
+    InvalidType,
+    /// The value is below a bound (`type`, `minimum`, `inclusive`).
+    TooSmall,
+    /// The value is above a bound (`type`, `maximum`, `inclusive`).
+    TooBig,
+    /// The string is not in the expected format (`validation`).
+    #[serde(rename = "invalid_string")]
+    InvalidFormat,
+    /// The number is not a multiple of `multipleOf`.
+    NotMultipleOf,
+    /// A required field is missing, or none of several alternative fields
+    /// (`keys`) is present.
+    MissingField,
+    /// An array item equals an earlier item (`index`), or a map key reads
+    /// as the same key as an earlier one (`keys`).
+    NotUnique,
+    /// An object has a field under more than one of its names (`keys`).
+    KeyConflict,
+    /// The object has a key that is not allowed (`keys`).
+    #[serde(rename = "unrecognized_keys")]
+    UnknownKey,
+    /// The string is not one of the allowed values (`options`, `received`).
+    InvalidEnumValue,
+    /// The value matches none of the variants of an untagged enum
+    /// (`unionErrors`).
+    InvalidUnion,
+    /// The tag of a tagged enum is not a known variant (`options`).
+    InvalidUnionDiscriminator,
+    /// Any other issue, including those raised by custom validators.
+    #[default]
+    Custom,
+}
+
+impl IssueCode {
+    /// Returns the code as serialized, such as `too_small`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            IssueCode::InvalidType => "invalid_type",
+            IssueCode::TooSmall => "too_small",
+            IssueCode::TooBig => "too_big",
+            IssueCode::InvalidFormat => "invalid_string",
+            IssueCode::NotMultipleOf => "not_multiple_of",
+            IssueCode::MissingField => "missing_field",
+            IssueCode::NotUnique => "not_unique",
+            IssueCode::KeyConflict => "key_conflict",
+            IssueCode::UnknownKey => "unrecognized_keys",
+            IssueCode::InvalidEnumValue => "invalid_enum_value",
+            IssueCode::InvalidUnion => "invalid_union",
+            IssueCode::InvalidUnionDiscriminator => "invalid_union_discriminator",
+            IssueCode::Custom => "custom",
+        }
+    }
+}
+
+/// The kind of value a size bound applies to.
+///
+/// Strings and arrays are bounded by their length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SizeType {
+    /// A string, by length.
+    String,
+    /// A number.
+    Number,
+    /// An array, by length.
+    Array,
+}
+
+impl SizeType {
+    /// Returns the size type as serialized, such as `string`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SizeType::String => "string",
+            SizeType::Number => "number",
+            SizeType::Array => "array",
+        }
+    }
+}
+
+/// A numeric bound, such as the minimum of a constraint. Integer bounds are
+/// kept exact, so bounds beyond 2^53 are not rounded.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumberBound {
+    /// An integer bound.
+    Integer(i128),
+    /// A floating point bound.
+    Float(f64),
+}
+
+impl NumberBound {
+    /// The bound as a float, rounded if it is a large integer.
+    pub fn as_f64(self) -> f64 {
+        match self {
+            NumberBound::Integer(n) => n as f64,
+            NumberBound::Float(n) => n,
+        }
+    }
+
+    /// Converts the bound to a JSON number. Integers outside the range of
+    /// `i64` and `u64` are rounded.
+    fn to_number(self) -> Option<Number> {
+        match self {
+            NumberBound::Integer(n) => i64::try_from(n)
+                .map(Number::from)
+                .or_else(|_| u64::try_from(n).map(Number::from))
+                .ok()
+                .or_else(|| number(n as f64)),
+            NumberBound::Float(n) => number(n),
         }
     }
 }
 
+impl From<f64> for NumberBound {
+    fn from(value: f64) -> Self {
+        NumberBound::Float(value)
+    }
+}
+
+impl From<i128> for NumberBound {
+    fn from(value: i128) -> Self {
+        NumberBound::Integer(value)
+    }
+}
+
+/// The details of a validation issue. Which params are set depends on the
+/// issue's code; unset params are not serialized.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueParams {
+    /// The expected type.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    /// The received type, or for `InvalidEnumValue` the received string.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub received: Option<String>,
+    /// The kind of value a size bound applies to.
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub size_type: Option<SizeType>,
+    /// The lower bound.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<Number>,
+    /// The upper bound.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<Number>,
+    /// Whether the bound itself is allowed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inclusive: Option<bool>,
+    /// Whether the size must be exactly the bound.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exact: Option<bool>,
+    /// The name of the expected string format.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validation: Option<String>,
+    /// The number the value must be a multiple of.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multiple_of: Option<Number>,
+    /// The keys that are not allowed, that conflict or are duplicates, or of
+    /// which one is required.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keys: Option<Vec<String>>,
+    /// The index of the earlier item a duplicate equals.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+    /// The allowed values.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<Vec<String>>,
+    /// The issues of each variant of a union, in order.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub union_errors: Option<Vec<Vec<ValidationIssue>>>,
+}
+
+/// Converts a bound to a JSON number, as an integer if it is one. `None` for
+/// infinite and NaN bounds, which JSON cannot represent.
+fn number(value: f64) -> Option<Number> {
+    if value.fract() == 0.0 && value.abs() < 9_007_199_254_740_992.0 {
+        Some(Number::from(value as i64))
+    } else {
+        Number::from_f64(value)
+    }
+}
+
+/// The name of `value`'s JSON type, as reported in `received`.
+fn json_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Conversion of a custom validator's return value into issues.
+///
+/// Derived types call custom validator functions (`validate_with`) and use
+/// this trait to accept either a `Result<(), String>`, where an error becomes
+/// a single issue, or a `Vec<ValidationIssue>`.
+pub trait IntoIssues {
+    /// Converts the value into a list of issues, empty if it is valid.
+    fn into_issues(self) -> Vec<ValidationIssue>;
+}
+
+impl IntoIssues for Result<(), String> {
+    fn into_issues(self) -> Vec<ValidationIssue> {
+        match self {
+            Ok(()) => Vec::new(),
+            Err(message) => vec![ValidationIssue::new(message)],
+        }
+    }
+}
+
+impl IntoIssues for Vec<ValidationIssue> {
+    fn into_issues(self) -> Vec<ValidationIssue> {
+        self
+    }
+}
+
+/// The properties of an object schema.
+///
+/// Derived types expose these so that a struct flattened into another one
+/// can have its properties merged into the enclosing object schema.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ObjectProperties {
+    /// The property schemas, by key.
+    pub properties: Map<String, Value>,
+    /// The keys of the required properties.
+    pub required: Vec<String>,
+    /// Schemas the object must also match, such as presence rules. They are
+    /// added to the `allOf` of the enclosing object schema.
+    pub all_of: Vec<Value>,
+}
+
+impl ObjectProperties {
+    /// Extracts the properties of an object schema that has no keywords
+    /// besides `type`, `properties`, `required` and `$schema`.
+    ///
+    /// Returns `None` for any other schema, since its other keywords would be
+    /// lost by merging only the properties.
+    pub fn from_schema(schema: &Value) -> Option<Self> {
+        let map = schema.as_object()?;
+        if map.get("type") != Some(&Value::String("object".to_string()))
+            || map
+                .keys()
+                .any(|key| !matches!(key.as_str(), "type" | "properties" | "required" | "$schema"))
+        {
+            return None;
+        }
+
+        let properties = match map.get("properties") {
+            Some(properties) => properties.as_object()?.clone(),
+            None => Map::new(),
+        };
+        let required = match map.get("required") {
+            Some(required) => required
+                .as_array()?
+                .iter()
+                .map(|key| key.as_str().map(String::from))
+                .collect::<Option<Vec<_>>>()?,
+            None => Vec::new(),
+        };
+
+        Some(ObjectProperties {
+            properties,
+            required,
+            all_of: Vec::new(),
+        })
+    }
+}
+
 /// A segment in a validation path.
 ///
 /// This corresponds to the path item types in the TypeScript spec:
@@ -213,7 +787,10 @@ mod tests {
     fn test_validation_result_failure_serialization() {
         let result: ValidationResult<i32> = ValidationResult::failure("Invalid");
         let json = serde_json::to_value(&result).unwrap();
-        assert_eq!(json, json!({ "issues": [{ "message": "Invalid" }] }));
+        assert_eq!(
+            json,
+            json!({ "issues": [{ "code": "custom", "message": "Invalid" }] })
+        );
     }
 
     #[test]
@@ -226,12 +803,78 @@ mod tests {
         assert_eq!(
             json,
             json!({
+                "code": "custom",
                 "message": "Invalid value",
                 "path": ["user", 0]
             })
         );
     }
 
+    #[test]
+    fn test_validation_issue_params_serialization() {
+        let issue = ValidationIssue::too_small("Too short", SizeType::String, 3.0, true)
+            .at(vec![PathSegment::Key("name".into())]);
+        let json = serde_json::to_value(&issue).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "code": "too_small",
+                "message": "Too short",
+                "path": ["name"],
+                "type": "string",
+                "minimum": 3,
+                "inclusive": true
+            })
+        );
+
+        let issue = ValidationIssue::not_multiple_of("Not a multiple", 0.5);
+        let json = serde_json::to_value(&issue).unwrap();
+        assert_eq!(
+            json,
+            json!({ "code": "not_multiple_of", "message": "Not a multiple", "multipleOf": 0.5 })
+        );
+
+        let issue = ValidationIssue::invalid_type("Expected string", "string", &json!(42));
+        let json = serde_json::to_value(&issue).unwrap();
+        assert_eq!(json["expected"], "string");
+        assert_eq!(json["received"], "number");
+
+        let issue = ValidationIssue::invalid_union(
+            "No match",
+            vec![vec![ValidationIssue::new("Expected null")]],
+        )
+        .with_path_prefix(PathSegment::Index(0));
+        let json = serde_json::to_value(&issue).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "code": "invalid_union",
+                "message": "No match",
+                "path": [0],
+                "unionErrors": [[{ "code": "custom", "message": "Expected null", "path": [0] }]]
+            })
+        );
+    }
+
+    #[test]
+    fn test_validation_issue_deserialization() {
+        let issue: ValidationIssue = serde_json::from_value(json!({
+            "code": "unrecognized_keys",
+            "message": "Unrecognized key 'extra'",
+            "path": ["extra"],
+            "keys": ["extra"]
+        }))
+        .unwrap();
+        assert_eq!(issue.code, IssueCode::UnknownKey);
+        assert_eq!(issue.params.keys, Some(vec!["extra".to_string()]));
+
+        // Standard Schema issues without a code are custom
+        let issue: ValidationIssue =
+            serde_json::from_value(json!({ "message": "Invalid" })).unwrap();
+        assert_eq!(issue.code, IssueCode::Custom);
+        assert_eq!(issue.params, IssueParams::default());
+    }
+
     #[test]
     fn test_path_prefix() {
         let result: ValidationResult<i32> = ValidationResult::failure("error");
@@ -240,4 +883,42 @@ mod tests {
             assert_eq!(issues[0].path, Some(vec![PathSegment::Key("field".into())]));
         }
     }
+
+    #[test]
+    fn test_into_issues() {
+        let ok: Result<(), String> = Ok(());
+        assert!(ok.into_issues().is_empty());
+
+        let err: Result<(), String> = Err("Unknown SKU".into());
+        let issues = err.into_issues();
+        assert_eq!(issues[0].message, "Unknown SKU");
+        assert!(issues[0].path.is_none());
+
+        let issue = ValidationIssue::with_path("Too long", vec![PathSegment::Index(2)]);
+        let issue = issue.with_path_prefix(PathSegment::Key("tags".into()));
+        assert_eq!(
+            issue.path,
+            Some(vec![PathSegment::Key("tags".into()), PathSegment::Index(2)])
+        );
+    }
+
+    #[test]
+    fn test_object_properties_from_schema() {
+        let schema = json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "properties": { "page": { "type": "integer" } },
+            "required": ["page"]
+        });
+        let properties = ObjectProperties::from_schema(&schema).unwrap();
+        assert_eq!(properties.properties["page"], json!({ "type": "integer" }));
+        assert_eq!(properties.required, vec!["page".to_string()]);
+
+        let schema = json!({ "type": "object", "additionalProperties": { "type": "string" } });
+        assert_eq!(ObjectProperties::from_schema(&schema), None);
+        assert_eq!(
+            ObjectProperties::from_schema(&json!({ "type": "string" })),
+            None
+        );
+    }
 }
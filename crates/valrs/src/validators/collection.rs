@@ -0,0 +1,246 @@
+//! Sequence validation implementations.
+
+use std::collections::VecDeque;
+
+use crate::validators::{add_schema_uri, invalid_type, strip_schema_uri};
+use crate::{
+    JsonSchemaTarget, PathSegment, StandardJsonSchema, ValidationContext, ValidationResult, Valrs,
+};
+use serde_json::{Value, json};
+
+/// Validates every element of a JSON array, prefixing element issues with their index.
+///
+/// All elements are validated so that every failing index is reported at
+/// once, unless the context's issue limit is reached first.
+fn validate_elements<T: Valrs>(
+    value: &Value,
+    ctx: &ValidationContext,
+) -> ValidationResult<Vec<T::Output>> {
+    let items = match value.as_array() {
+        Some(items) => items,
+        None => return invalid_type("Expected array", "array", value),
+    };
+
+    let mut output = Vec::with_capacity(items.len());
+    let mut issues = Vec::new();
+
+    for (index, item) in items.iter().enumerate() {
+        match T::validate_with(item, ctx).with_path_prefix(PathSegment::Index(index)) {
+            ValidationResult::Success(v) => output.push(v),
+            ValidationResult::Failure(errs) => {
+                issues.extend(errs);
+                if ctx.is_done(issues.len()) {
+                    break;
+                }
+            }
+        }
+    }
+
+    if issues.is_empty() {
+        ValidationResult::success(output)
+    } else {
+        ctx.truncate(&mut issues);
+        ValidationResult::failures(issues)
+    }
+}
+
+/// Finds the items equal to an earlier item.
+///
+/// Returns the index of each repeated item with the index of the first item
+/// equal to it. Used by derived types with a `unique_items` constraint.
+pub fn duplicate_items<'a, T: PartialEq + 'a>(
+    items: impl IntoIterator<Item = &'a T>,
+) -> Vec<(usize, usize)> {
+    let items: Vec<&T> = items.into_iter().collect();
+    (1..items.len())
+        .filter_map(|index| {
+            items[..index]
+                .iter()
+                .position(|earlier| *earlier == items[index])
+                .map(|first| (index, first))
+        })
+        .collect()
+}
+
+/// Builds an array schema whose elements all match `items`.
+fn array_schema(items: Value, target: JsonSchemaTarget) -> Value {
+    let mut schema = json!({
+        "type": "array",
+        "items": items
+    });
+    add_schema_uri(&mut schema, target);
+    schema
+}
+
+impl<T: Valrs> Valrs for Vec<T> {
+    type Input = Vec<T::Input>;
+    type Output = Vec<T::Output>;
+
+    fn validate(value: &Value) -> ValidationResult<Self::Output> {
+        Self::validate_with(value, &ValidationContext::default())
+    }
+
+    fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
+        validate_elements::<T>(value, ctx)
+    }
+}
+
+impl<T: StandardJsonSchema> StandardJsonSchema for Vec<T> {
+    fn json_schema_input(target: JsonSchemaTarget) -> Value {
+        array_schema(strip_schema_uri(T::json_schema_input(target)), target)
+    }
+
+    fn json_schema_output(target: JsonSchemaTarget) -> Value {
+        array_schema(strip_schema_uri(T::json_schema_output(target)), target)
+    }
+
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        array_schema(
+            strip_schema_uri(T::json_schema_coerced_input(target)),
+            target,
+        )
+    }
+}
+
+impl<T: Valrs> Valrs for VecDeque<T> {
+    type Input = VecDeque<T::Input>;
+    type Output = VecDeque<T::Output>;
+
+    fn validate(value: &Value) -> ValidationResult<Self::Output> {
+        Self::validate_with(value, &ValidationContext::default())
+    }
+
+    fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
+        validate_elements::<T>(value, ctx).map(VecDeque::from)
+    }
+}
+
+impl<T: StandardJsonSchema> StandardJsonSchema for VecDeque<T> {
+    fn json_schema_input(target: JsonSchemaTarget) -> Value {
+        <Vec<T> as StandardJsonSchema>::json_schema_input(target)
+    }
+
+    fn json_schema_output(target: JsonSchemaTarget) -> Value {
+        <Vec<T> as StandardJsonSchema>::json_schema_output(target)
+    }
+
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        <Vec<T> as StandardJsonSchema>::json_schema_coerced_input(target)
+    }
+}
+
+impl<T: Valrs> Valrs for Box<[T]> {
+    type Input = Box<[T::Input]>;
+    type Output = Box<[T::Output]>;
+
+    fn validate(value: &Value) -> ValidationResult<Self::Output> {
+        Self::validate_with(value, &ValidationContext::default())
+    }
+
+    fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
+        validate_elements::<T>(value, ctx).map(Vec::into_boxed_slice)
+    }
+}
+
+impl<T: StandardJsonSchema> StandardJsonSchema for Box<[T]> {
+    fn json_schema_input(target: JsonSchemaTarget) -> Value {
+        <Vec<T> as StandardJsonSchema>::json_schema_input(target)
+    }
+
+    fn json_schema_output(target: JsonSchemaTarget) -> Value {
+        <Vec<T> as StandardJsonSchema>::json_schema_output(target)
+    }
+
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        <Vec<T> as StandardJsonSchema>::json_schema_coerced_input(target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_vec_validation() {
+        let result = <Vec<i32>>::validate(&json!([1, 2, 3]));
+        assert_eq!(result.ok(), Some(vec![1, 2, 3]));
+
+        let result = <Vec<i32>>::validate(&json!([]));
+        assert_eq!(result.ok(), Some(vec![]));
+
+        assert!(<Vec<i32>>::validate(&json!({ "0": 1 })).is_failure());
+        assert!(<Vec<i32>>::validate(&json!(null)).is_failure());
+    }
+
+    #[test]
+    fn test_vec_reports_every_failing_index() {
+        let result = <Vec<u8>>::validate(&json!([1, "two", 3, 300]));
+        let issues = result.issues();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].path, Some(vec![PathSegment::Index(1)]));
+        assert_eq!(issues[1].path, Some(vec![PathSegment::Index(3)]));
+    }
+
+    #[test]
+    fn test_nested_vec_paths() {
+        let result = <Vec<Vec<String>>>::validate(&json!([["a"], ["b", 2]]));
+        let issues = result.issues();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(
+            issues[0].path,
+            Some(vec![PathSegment::Index(1), PathSegment::Index(1)])
+        );
+    }
+
+    #[test]
+    fn test_duplicate_items() {
+        assert_eq!(duplicate_items(&[1, 2, 3]), []);
+        assert_eq!(duplicate_items(&["a", "b", "a", "a"]), [(2, 0), (3, 0)]);
+        assert_eq!(duplicate_items(&VecDeque::from([0.5, 0.5])), [(1, 0)]);
+        assert_eq!(duplicate_items::<u8>(&[]), []);
+    }
+
+    #[test]
+    fn test_vec_deque_validation() {
+        let result = <VecDeque<String>>::validate(&json!(["a", "b"]));
+        assert_eq!(
+            result.ok(),
+            Some(VecDeque::from(vec!["a".to_string(), "b".to_string()]))
+        );
+    }
+
+    #[test]
+    fn test_boxed_slice_validation() {
+        let result = <Box<[bool]>>::validate(&json!([true, false]));
+        assert_eq!(result.ok().as_deref(), Some(&[true, false][..]));
+        assert!(<Box<[bool]>>::validate(&json!([1])).is_failure());
+    }
+
+    #[test]
+    fn test_vec_json_schema() {
+        let schema =
+            <Vec<String> as StandardJsonSchema>::json_schema_input(JsonSchemaTarget::Draft202012);
+        assert_eq!(schema["type"], "array");
+        assert_eq!(schema["items"], json!({ "type": "string" }));
+        assert_eq!(
+            schema["$schema"],
+            "https://json-schema.org/draft/2020-12/schema"
+        );
+    }
+
+    #[test]
+    fn test_vec_of_option_json_schema_follows_target() {
+        let schema = <Vec<Option<i32>> as StandardJsonSchema>::json_schema_input(
+            JsonSchemaTarget::OpenApi30,
+        );
+        assert_eq!(schema["items"]["type"], "integer");
+        assert_eq!(schema["items"]["nullable"], true);
+        assert!(schema.get("$schema").is_none());
+
+        let schema =
+            <Vec<Option<i32>> as StandardJsonSchema>::json_schema_input(JsonSchemaTarget::Draft07);
+        assert!(schema["items"]["anyOf"].is_array());
+        assert!(schema["items"].get("$schema").is_none());
+    }
+}
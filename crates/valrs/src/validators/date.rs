@@ -0,0 +1,275 @@
+//! Date validation implementations for `chrono` types.
+//!
+//! Dates have no JSON type, so they are read from ISO 8601 strings: `NaiveDate`
+//! from a full date such as `"2024-05-31"`, and `DateTime` from an RFC 3339
+//! date-time such as `"2024-05-31T12:00:00Z"`. In coercion mode, other ISO
+//! strings are converted as well: a date-time to its date, and a date or a
+//! date-time without offset to a UTC date-time.
+
+use std::sync::LazyLock;
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, Utc};
+use regex::Regex;
+use serde_json::{Value, json};
+
+use crate::validators::{add_schema_uri, coerced_schema, invalid_type};
+use crate::{
+    JsonSchemaTarget, StandardJsonSchema, ValidationContext, ValidationIssue, ValidationResult,
+    Valrs,
+};
+
+/// Matches a full date, such as `2024-05-31`.
+static DATE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[0-9]{4}-[0-9]{2}-[0-9]{2}$").unwrap());
+
+/// Matches a date-time without offset, such as `2024-05-31T12:00:00`.
+const LOCAL_DATE_TIME_PATTERN: &str =
+    r"^[0-9]{4}-[0-9]{2}-[0-9]{2}T[0-9]{2}:[0-9]{2}:[0-9]{2}(\.[0-9]+)?$";
+
+static LOCAL_DATE_TIME: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(LOCAL_DATE_TIME_PATTERN).unwrap());
+
+/// Reads a full date in the `YYYY-MM-DD` form.
+///
+/// chrono's parser alone also accepts single-digit months and days and
+/// signed five-digit years, which the `date` format does not.
+fn parse_date(s: &str) -> Option<NaiveDate> {
+    if DATE.is_match(s) {
+        s.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// Reads an RFC 3339 date-time, or in coercion mode a date or a date-time
+/// without offset, taken as UTC.
+fn parse_date_time(s: &str, ctx: &ValidationContext) -> Option<DateTime<FixedOffset>> {
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(s) {
+        return Some(date_time);
+    }
+    if !ctx.coerce() {
+        return None;
+    }
+
+    let naive = if LOCAL_DATE_TIME.is_match(s) {
+        s.parse::<NaiveDateTime>().ok()?
+    } else {
+        parse_date(s)?.and_hms_opt(0, 0, 0)?
+    };
+    Some(naive.and_utc().fixed_offset())
+}
+
+/// Validates a date-time string, explaining which strings are accepted when
+/// it cannot be read.
+fn validate_date_time(
+    value: &Value,
+    ctx: &ValidationContext,
+) -> ValidationResult<DateTime<FixedOffset>> {
+    let Some(s) = value.as_str() else {
+        return invalid_type("Expected date-time string", "string", value);
+    };
+
+    match parse_date_time(s, ctx) {
+        Some(date_time) => ValidationResult::success(date_time),
+        None => {
+            let expected = if ctx.coerce() {
+                "an ISO 8601 date or date-time"
+            } else {
+                "an RFC 3339 date-time"
+            };
+            ValidationResult::failures(vec![ValidationIssue::invalid_format(
+                format!("Invalid date-time '{}', expected {}", s, expected),
+                "datetime",
+            )])
+        }
+    }
+}
+
+/// Builds the input schema of a date-time.
+fn date_time_schema(target: JsonSchemaTarget) -> Value {
+    let mut schema = json!({ "type": "string", "format": "date-time" });
+    add_schema_uri(&mut schema, target);
+    schema
+}
+
+/// Builds the coerced input schema of a date-time, which also accepts dates
+/// and date-times without offset.
+fn coerced_date_time_schema(target: JsonSchemaTarget) -> Value {
+    let mut schema = json!({
+        "anyOf": [
+            { "type": "string", "format": "date-time" },
+            { "type": "string", "format": "date" },
+            { "type": "string", "pattern": LOCAL_DATE_TIME_PATTERN }
+        ]
+    });
+    add_schema_uri(&mut schema, target);
+    schema
+}
+
+impl Valrs for NaiveDate {
+    type Input = String;
+    type Output = NaiveDate;
+
+    fn validate(value: &Value) -> ValidationResult<Self::Output> {
+        Self::validate_with(value, &ValidationContext::default())
+    }
+
+    fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
+        let Some(s) = value.as_str() else {
+            return invalid_type("Expected date string", "string", value);
+        };
+
+        if let Some(date) = parse_date(s) {
+            return ValidationResult::success(date);
+        }
+        if ctx.coerce()
+            && let Ok(date_time) = DateTime::parse_from_rfc3339(s)
+        {
+            return ValidationResult::success(date_time.date_naive());
+        }
+
+        let expected = if ctx.coerce() {
+            "an ISO 8601 date or date-time"
+        } else {
+            "YYYY-MM-DD"
+        };
+        ValidationResult::failures(vec![ValidationIssue::invalid_format(
+            format!("Invalid date '{}', expected {}", s, expected),
+            "date",
+        )])
+    }
+}
+
+impl StandardJsonSchema for NaiveDate {
+    fn json_schema_input(target: JsonSchemaTarget) -> Value {
+        let mut schema = json!({ "type": "string", "format": "date" });
+        add_schema_uri(&mut schema, target);
+        schema
+    }
+
+    fn json_schema_output(target: JsonSchemaTarget) -> Value {
+        Self::json_schema_input(target)
+    }
+
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        coerced_schema(
+            Self::json_schema_input(target),
+            json!({ "type": "string", "format": "date-time" }),
+            target,
+        )
+    }
+}
+
+impl Valrs for DateTime<FixedOffset> {
+    type Input = String;
+    type Output = DateTime<FixedOffset>;
+
+    fn validate(value: &Value) -> ValidationResult<Self::Output> {
+        Self::validate_with(value, &ValidationContext::default())
+    }
+
+    fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
+        validate_date_time(value, ctx)
+    }
+}
+
+impl StandardJsonSchema for DateTime<FixedOffset> {
+    fn json_schema_input(target: JsonSchemaTarget) -> Value {
+        date_time_schema(target)
+    }
+
+    fn json_schema_output(target: JsonSchemaTarget) -> Value {
+        date_time_schema(target)
+    }
+
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        coerced_date_time_schema(target)
+    }
+}
+
+/// Date-times with any offset are accepted, and converted to UTC.
+impl Valrs for DateTime<Utc> {
+    type Input = String;
+    type Output = DateTime<Utc>;
+
+    fn validate(value: &Value) -> ValidationResult<Self::Output> {
+        Self::validate_with(value, &ValidationContext::default())
+    }
+
+    fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
+        validate_date_time(value, ctx).map(|date_time| date_time.to_utc())
+    }
+}
+
+impl StandardJsonSchema for DateTime<Utc> {
+    fn json_schema_input(target: JsonSchemaTarget) -> Value {
+        date_time_schema(target)
+    }
+
+    fn json_schema_output(target: JsonSchemaTarget) -> Value {
+        date_time_schema(target)
+    }
+
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        coerced_date_time_schema(target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IssueCode;
+
+    #[test]
+    fn test_naive_date_validation() {
+        let result = NaiveDate::validate(&json!("2024-05-31"));
+        assert_eq!(result.ok(), NaiveDate::from_ymd_opt(2024, 5, 31));
+
+        let result = NaiveDate::validate(&json!("2024-02-30"));
+        assert_eq!(result.issues()[0].code, IssueCode::InvalidFormat);
+        assert_eq!(
+            result.issues()[0].message,
+            "Invalid date '2024-02-30', expected YYYY-MM-DD"
+        );
+        assert!(NaiveDate::validate(&json!("2024-05-31T12:00:00Z")).is_failure());
+        assert!(NaiveDate::validate(&json!(20240531)).is_failure());
+        assert!(NaiveDate::validate(&json!("2024-5-3")).is_failure());
+        assert!(NaiveDate::validate(&json!("+12024-05-31")).is_failure());
+        assert!(NaiveDate::validate(&json!("٢٠٢٤-٠٥-٣١")).is_failure());
+
+        let ctx = ValidationContext::new().with_coerce(true);
+        let result = NaiveDate::validate_with(&json!("2024-05-31T23:00:00-02:00"), &ctx);
+        assert_eq!(result.ok(), NaiveDate::from_ymd_opt(2024, 5, 31));
+    }
+
+    #[test]
+    fn test_date_time_validation() {
+        let result = DateTime::<Utc>::validate(&json!("2024-05-31T12:00:00+02:00"));
+        assert_eq!(
+            result.ok().unwrap().to_rfc3339(),
+            "2024-05-31T10:00:00+00:00"
+        );
+        assert!(DateTime::<Utc>::validate(&json!("2024-05-31")).is_failure());
+
+        let ctx = ValidationContext::new().with_coerce(true);
+        let result = DateTime::<FixedOffset>::validate_with(&json!("2024-05-31"), &ctx);
+        assert_eq!(
+            result.ok().unwrap().to_rfc3339(),
+            "2024-05-31T00:00:00+00:00"
+        );
+        let result = DateTime::<Utc>::validate_with(&json!("2024-05-31T12:30:00"), &ctx);
+        assert_eq!(
+            result.ok().unwrap().to_rfc3339(),
+            "2024-05-31T12:30:00+00:00"
+        );
+
+        assert!(DateTime::<Utc>::validate_with(&json!("2024-5-31"), &ctx).is_failure());
+        assert!(DateTime::<Utc>::validate_with(&json!("2024-05-31T1:00:00"), &ctx).is_failure());
+
+        let result = DateTime::<Utc>::validate_with(&json!("tomorrow"), &ctx);
+        assert_eq!(
+            result.issues()[0].message,
+            "Invalid date-time 'tomorrow', expected an ISO 8601 date or date-time"
+        );
+    }
+}
@@ -0,0 +1,366 @@
+//! String format and pattern checks.
+//!
+//! The format checks mirror the TypeScript `ValString` methods (`email()`,
+//! `url()`, `uuid()`, `datetime()`, `ip()`), using the same expressions where
+//! JSON Schema's formats allow it. Digits are spelled `[0-9]` rather than
+//! `\d`, which matches any Unicode digit in Rust but only ASCII digits in
+//! ECMAScript. The differences follow the formats:
+//!
+//! - `uri` accepts any RFC 3986 scheme, such as `mailto:` or `urn:`, where
+//!   the TypeScript `url()` only accepts `http`, `https` and `ftp` URLs.
+//! - `date-time` (RFC 3339) requires the time and the `Z` or `±hh:mm` offset,
+//!   which the TypeScript `datetime()` may leave out; dates have their own
+//!   `date` format.
+//! - Dates and times are range-checked: the day must exist in its month, and
+//!   hours, minutes and seconds must be in range, with a second of 60 for leap
+//!   seconds.
+//!
+//! They are used by derived types with a `format` or `pattern` attribute.
+
+use regex::{Captures, Regex};
+use std::sync::{LazyLock, OnceLock};
+
+static EMAIL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[^\s@]+@[^\s@]+\.[^\s@]+$").unwrap());
+
+static URI: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[A-Za-z][A-Za-z0-9+.-]*:[^\s]*$").unwrap());
+
+static UUID: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        r"(?i)^(?:[0-9a-f]{8}-[0-9a-f]{4}-[1-8][0-9a-f]{3}-[89ab][0-9a-f]{3}-[0-9a-f]{12}",
+        r"|00000000-0000-0000-0000-000000000000|ffffffff-ffff-ffff-ffff-ffffffffffff)$",
+    ))
+    .unwrap()
+});
+
+static DATE_TIME: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        r"^([0-9]{4})-([0-9]{2})-([0-9]{2})",
+        r"T([0-9]{2}):([0-9]{2}):([0-9]{2})(?:\.[0-9]+)?(?:Z|[+-]([0-9]{2}):([0-9]{2}))$",
+    ))
+    .unwrap()
+});
+
+static DATE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^([0-9]{4})-([0-9]{2})-([0-9]{2})$").unwrap());
+
+static IPV4: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        r"^(?:(?:25[0-5]|2[0-4][0-9]|[01]?[0-9][0-9]?)\.){3}",
+        r"(?:25[0-5]|2[0-4][0-9]|[01]?[0-9][0-9]?)$",
+    ))
+    .unwrap()
+});
+
+static IPV6: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        r"^(?:[0-9a-fA-F]{1,4}:){7}[0-9a-fA-F]{1,4}$",
+        r"|^::(?:[0-9a-fA-F]{1,4}:){0,6}[0-9a-fA-F]{1,4}$",
+        r"|^[0-9a-fA-F]{1,4}::(?:[0-9a-fA-F]{1,4}:){0,5}[0-9a-fA-F]{1,4}$",
+        r"|^(?:[0-9a-fA-F]{1,4}:){1,2}:(?:[0-9a-fA-F]{1,4}:){0,4}[0-9a-fA-F]{1,4}$",
+        r"|^(?:[0-9a-fA-F]{1,4}:){1,3}:(?:[0-9a-fA-F]{1,4}:){0,3}[0-9a-fA-F]{1,4}$",
+        r"|^(?:[0-9a-fA-F]{1,4}:){1,4}:(?:[0-9a-fA-F]{1,4}:){0,2}[0-9a-fA-F]{1,4}$",
+        r"|^(?:[0-9a-fA-F]{1,4}:){1,5}:(?:[0-9a-fA-F]{1,4}:)?[0-9a-fA-F]{1,4}$",
+        r"|^(?:[0-9a-fA-F]{1,4}:){1,6}:[0-9a-fA-F]{1,4}$",
+    ))
+    .unwrap()
+});
+
+/// Reads the number in capture group `index` of `captures`, or 0 if the group
+/// did not match. The groups only match ASCII digits.
+fn capture_number(captures: &Captures, index: usize) -> u32 {
+    captures
+        .get(index)
+        .map_or(0, |m| m.as_str().parse().expect("digits"))
+}
+
+/// Checks that `day` exists in `month` of `year`.
+fn is_valid_date(year: u32, month: u32, day: u32) -> bool {
+    let leap = year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+    let days = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return false,
+    };
+    (1..=days).contains(&day)
+}
+
+/// Checks a `YYYY-MM-DD` date for an existing day.
+fn is_date(value: &str) -> bool {
+    DATE.captures(value).is_some_and(|c| {
+        is_valid_date(
+            capture_number(&c, 1),
+            capture_number(&c, 2),
+            capture_number(&c, 3),
+        )
+    })
+}
+
+/// Checks an RFC 3339 date-time for an existing day and in-range time and
+/// offset.
+fn is_date_time(value: &str) -> bool {
+    DATE_TIME.captures(value).is_some_and(|c| {
+        let n = |index| capture_number(&c, index);
+        is_valid_date(n(1), n(2), n(3))
+            && n(4) <= 23
+            && n(5) <= 59
+            && n(6) <= 60
+            && n(7) <= 23
+            && n(8) <= 59
+    })
+}
+
+/// Checks a hostname per RFC 1123: dot-separated labels of 1 to 63 letters,
+/// digits and hyphens, not starting or ending with a hyphen, 253 characters
+/// at most in total.
+fn is_hostname(value: &str) -> bool {
+    value.len() <= 253
+        && value.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        })
+}
+
+/// A string format, named as in JSON Schema's `format` keyword.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StringFormat {
+    /// `email`
+    Email,
+    /// `uri` (any RFC 3986 scheme, followed by `:`)
+    Uri,
+    /// `uuid` (RFC 9562 versions 1 to 8, and the nil and max UUIDs)
+    Uuid,
+    /// `date-time` (RFC 3339, with a `Z` or `±hh:mm` offset)
+    DateTime,
+    /// `date` (RFC 3339 full date)
+    Date,
+    /// `ipv4`
+    Ipv4,
+    /// `ipv6`
+    Ipv6,
+    /// `hostname`
+    Hostname,
+}
+
+impl StringFormat {
+    /// All formats, in the order listed in errors.
+    pub const ALL: [StringFormat; 8] = [
+        StringFormat::Email,
+        StringFormat::Uri,
+        StringFormat::Uuid,
+        StringFormat::DateTime,
+        StringFormat::Date,
+        StringFormat::Ipv4,
+        StringFormat::Ipv6,
+        StringFormat::Hostname,
+    ];
+
+    /// Looks up a format by its JSON Schema name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|format| format.name() == name)
+    }
+
+    /// Returns the JSON Schema name of the format.
+    pub fn name(self) -> &'static str {
+        match self {
+            StringFormat::Email => "email",
+            StringFormat::Uri => "uri",
+            StringFormat::Uuid => "uuid",
+            StringFormat::DateTime => "date-time",
+            StringFormat::Date => "date",
+            StringFormat::Ipv4 => "ipv4",
+            StringFormat::Ipv6 => "ipv6",
+            StringFormat::Hostname => "hostname",
+        }
+    }
+
+    /// Returns the name of the format in `invalid_string` issues, as in the
+    /// TypeScript `StringValidation` type.
+    pub fn validation(self) -> &'static str {
+        match self {
+            StringFormat::Email => "email",
+            StringFormat::Uri => "url",
+            StringFormat::Uuid => "uuid",
+            StringFormat::DateTime => "datetime",
+            StringFormat::Date => "date",
+            StringFormat::Ipv4 | StringFormat::Ipv6 => "ip",
+            StringFormat::Hostname => "hostname",
+        }
+    }
+
+    /// Returns whether `value` is in this format.
+    pub fn is_valid(self, value: &str) -> bool {
+        match self {
+            StringFormat::Email => EMAIL.is_match(value),
+            StringFormat::Uri => URI.is_match(value),
+            StringFormat::Uuid => UUID.is_match(value),
+            StringFormat::DateTime => is_date_time(value),
+            StringFormat::Date => is_date(value),
+            StringFormat::Ipv4 => IPV4.is_match(value),
+            StringFormat::Ipv6 => IPV6.is_match(value),
+            StringFormat::Hostname => is_hostname(value),
+        }
+    }
+
+    /// Returns the issue message for a value not in this format.
+    pub fn message(self) -> &'static str {
+        match self {
+            StringFormat::Email => "Invalid email address",
+            StringFormat::Uri => "Invalid URL",
+            StringFormat::Uuid => "Invalid UUID",
+            StringFormat::DateTime => "Invalid datetime format",
+            StringFormat::Date => "Invalid date format",
+            StringFormat::Ipv4 => "Invalid IPv4 address",
+            StringFormat::Ipv6 => "Invalid IPv6 address",
+            StringFormat::Hostname => "Invalid hostname",
+        }
+    }
+}
+
+/// A regular expression compiled on first use, for use in a `static`.
+///
+/// Like JSON Schema's `pattern`, a match anywhere in the string is accepted;
+/// anchor the pattern with `^` and `$` to match the whole string.
+pub struct Pattern {
+    source: &'static str,
+    regex: OnceLock<Regex>,
+}
+
+impl Pattern {
+    /// Creates a pattern from its source. It is compiled on first use.
+    pub const fn new(source: &'static str) -> Self {
+        Pattern {
+            source,
+            regex: OnceLock::new(),
+        }
+    }
+
+    /// Returns the pattern source.
+    pub fn source(&self) -> &'static str {
+        self.source
+    }
+
+    /// Returns whether the pattern matches `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the source is not a valid regular expression. Derived types
+    /// check their patterns at compile time.
+    pub fn is_match(&self, value: &str) -> bool {
+        self.regex
+            .get_or_init(|| Regex::new(self.source).expect("invalid pattern"))
+            .is_match(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_email() {
+        assert!(StringFormat::Email.is_valid("ada@example.com"));
+        assert!(!StringFormat::Email.is_valid("ada@example"));
+        assert!(!StringFormat::Email.is_valid("ada example@x.com"));
+    }
+
+    #[test]
+    fn test_uri() {
+        assert!(StringFormat::Uri.is_valid("https://example.com/path?q=1"));
+        assert!(StringFormat::Uri.is_valid("FTP://files.example.com"));
+        assert!(StringFormat::Uri.is_valid("mailto:ada@example.com"));
+        assert!(StringFormat::Uri.is_valid("urn:isbn:0451450523"));
+        assert!(StringFormat::Uri.is_valid("file:///etc/hosts"));
+        assert!(StringFormat::Uri.is_valid("x-app+v1.2:open"));
+        assert!(!StringFormat::Uri.is_valid("example.com"));
+        assert!(!StringFormat::Uri.is_valid("1http://example.com"));
+        assert!(!StringFormat::Uri.is_valid("https://example.com/a b"));
+    }
+
+    #[test]
+    fn test_uuid() {
+        assert!(StringFormat::Uuid.is_valid("123e4567-e89b-42d3-a456-426614174000"));
+        assert!(StringFormat::Uuid.is_valid("01890a5d-ac96-774b-bcce-b302099a8057"));
+        assert!(StringFormat::Uuid.is_valid("00000000-0000-0000-0000-000000000000"));
+        assert!(StringFormat::Uuid.is_valid("FFFFFFFF-FFFF-FFFF-FFFF-FFFFFFFFFFFF"));
+        assert!(!StringFormat::Uuid.is_valid("123e4567-e89b-92d3-a456-426614174000"));
+        assert!(!StringFormat::Uuid.is_valid("123e4567-e89b-02d3-a456-426614174000"));
+        assert!(!StringFormat::Uuid.is_valid("123e4567e89b42d3a456426614174000"));
+    }
+
+    #[test]
+    fn test_date_time() {
+        assert!(StringFormat::DateTime.is_valid("2024-01-31T12:30:00.5+02:00"));
+        assert!(StringFormat::DateTime.is_valid("2024-01-31T12:30:00Z"));
+        assert!(!StringFormat::DateTime.is_valid("2024-01-31T12:30:00"));
+        assert!(!StringFormat::DateTime.is_valid("2024-01-31"));
+        assert!(!StringFormat::DateTime.is_valid("2024-01-31 12:30:00"));
+        assert!(!StringFormat::DateTime.is_valid("٢٠٢٤-٠١-٣١"));
+        assert!(!StringFormat::DateTime.is_valid("2024-01-31T12:30:0٠Z"));
+        assert!(StringFormat::DateTime.is_valid("2016-12-31T23:59:60Z"));
+        assert!(!StringFormat::DateTime.is_valid("2024-13-45T12:30:00Z"));
+        assert!(!StringFormat::DateTime.is_valid("2024-01-31T25:61:61Z"));
+        assert!(!StringFormat::DateTime.is_valid("2024-01-31T24:00:00Z"));
+        assert!(!StringFormat::DateTime.is_valid("2024-01-31T12:30:00+24:00"));
+    }
+
+    #[test]
+    fn test_date() {
+        assert!(StringFormat::Date.is_valid("2024-01-31"));
+        assert!(!StringFormat::Date.is_valid("2024-01-31T12:30:00Z"));
+        assert!(!StringFormat::Date.is_valid("31/01/2024"));
+        assert!(StringFormat::Date.is_valid("2024-02-29"));
+        assert!(StringFormat::Date.is_valid("2000-02-29"));
+        assert!(!StringFormat::Date.is_valid("2023-02-29"));
+        assert!(!StringFormat::Date.is_valid("1900-02-29"));
+        assert!(!StringFormat::Date.is_valid("2024-04-31"));
+        assert!(!StringFormat::Date.is_valid("2024-13-01"));
+        assert!(!StringFormat::Date.is_valid("2024-00-10"));
+    }
+
+    #[test]
+    fn test_ip() {
+        assert!(StringFormat::Ipv4.is_valid("192.168.0.1"));
+        assert!(!StringFormat::Ipv4.is_valid("256.1.1.1"));
+        assert!(!StringFormat::Ipv4.is_valid("١٢٧.0.0.1"));
+        assert!(StringFormat::Ipv6.is_valid("2001:db8::1"));
+        assert!(StringFormat::Ipv6.is_valid("::1"));
+        assert!(!StringFormat::Ipv6.is_valid("192.168.0.1"));
+    }
+
+    #[test]
+    fn test_hostname() {
+        assert!(StringFormat::Hostname.is_valid("api.example-1.com"));
+        assert!(!StringFormat::Hostname.is_valid("-api.example.com"));
+        assert!(!StringFormat::Hostname.is_valid("api..example.com"));
+        assert!(!StringFormat::Hostname.is_valid(&"a".repeat(64)));
+    }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(
+            StringFormat::from_name("date-time"),
+            Some(StringFormat::DateTime)
+        );
+        assert_eq!(StringFormat::from_name("datetime"), None);
+    }
+
+    #[test]
+    fn test_pattern() {
+        static CODE: Pattern = Pattern::new("^[A-Z]{3}$");
+        assert!(CODE.is_match("USD"));
+        assert!(!CODE.is_match("usd"));
+        assert_eq!(CODE.source(), "^[A-Z]{3}$");
+
+        static DIGIT: Pattern = Pattern::new(r"\d");
+        assert!(DIGIT.is_match("abc1"));
+    }
+}
@@ -0,0 +1,433 @@
+//! Map validation implementations.
+//!
+//! Maps are validated as JSON objects with dynamic keys (records). Keys are
+//! validated with the key type's own `Valrs` implementation: the key string is
+//! tried first, and keys that look like numbers are validated as JSON numbers,
+//! so `HashMap<u32, V>` accepts `{"1": ..., "2": ...}`. A key that reads as
+//! the same key as an earlier one is reported rather than replacing it.
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
+
+use crate::validators::number::{INTEGER_STRING_PATTERN, NUMBER_STRING_PATTERN};
+use crate::validators::{add_schema_uri, strip_schema_uri};
+use crate::{
+    JsonSchemaTarget, PathSegment, StandardJsonSchema, ValidationContext, ValidationIssue,
+    ValidationResult, Valrs,
+};
+use serde_json::{Number, Value, json};
+
+/// Validates a single object key against the key type `K`.
+fn validate_key<K: Valrs>(key: &str, ctx: &ValidationContext) -> ValidationResult<K::Output> {
+    match K::validate_with(&Value::String(key.to_string()), ctx) {
+        ValidationResult::Success(k) => ValidationResult::success(k),
+        ValidationResult::Failure(issues) => match key.parse::<Number>() {
+            Ok(n) => K::validate_with(&Value::Number(n), ctx),
+            Err(_) => ValidationResult::failures(issues),
+        },
+    }
+}
+
+/// Validates every entry of a JSON object, collecting issues at `PathSegment::Key`.
+///
+/// Entries are passed to `insert` in the object's iteration order, which
+/// returns whether the key is new. Stops early once the context's issue limit
+/// is reached.
+fn validate_entries<K: Valrs, V: Valrs>(
+    value: &Value,
+    ctx: &ValidationContext,
+    mut insert: impl FnMut(K::Output, V::Output) -> bool,
+) -> Result<(), Vec<ValidationIssue>> {
+    let obj = match value.as_object() {
+        Some(o) => o,
+        None => {
+            return Err(vec![ValidationIssue::invalid_type(
+                "Expected object",
+                "object",
+                value,
+            )]);
+        }
+    };
+
+    let mut issues = Vec::new();
+
+    for (key, item) in obj {
+        let segment = PathSegment::Key(key.clone());
+        let key_result = validate_key::<K>(key, ctx).with_path_prefix(segment.clone());
+        let value_result = V::validate_with(item, ctx).with_path_prefix(segment);
+
+        match (key_result, value_result) {
+            (ValidationResult::Success(k), ValidationResult::Success(v)) => {
+                if insert(k, v) {
+                    continue;
+                }
+                issues.push(
+                    ValidationIssue::duplicate_key(
+                        format!("Duplicate key '{}', same as an earlier key", key),
+                        key,
+                    )
+                    .at(vec![PathSegment::Key(key.clone())]),
+                );
+            }
+            (key_result, value_result) => {
+                issues.extend_from_slice(key_result.issues());
+                issues.extend_from_slice(value_result.issues());
+            }
+        }
+        if ctx.is_done(issues.len()) {
+            break;
+        }
+    }
+
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        ctx.truncate(&mut issues);
+        Err(issues)
+    }
+}
+
+/// Converts a key type's schema into a `propertyNames` schema.
+///
+/// Object keys are always strings in JSON, so numeric key schemas are
+/// expressed as string patterns. Returns `None` when any string is accepted.
+fn property_names_schema(key_schema: Value) -> Option<Value> {
+    match key_schema.get("type").and_then(Value::as_str) {
+        Some("integer") => Some(json!({ "type": "string", "pattern": INTEGER_STRING_PATTERN })),
+        Some("number") => Some(json!({ "type": "string", "pattern": NUMBER_STRING_PATTERN })),
+        Some("string") if key_schema.as_object().is_some_and(|m| m.len() == 1) => None,
+        _ => Some(key_schema),
+    }
+}
+
+/// Builds a record schema from the key and value schemas.
+fn record_schema(key_schema: Value, value_schema: Value, target: JsonSchemaTarget) -> Value {
+    let mut schema = json!({
+        "type": "object",
+        "additionalProperties": strip_schema_uri(value_schema)
+    });
+
+    // OpenAPI 3.0 has no `propertyNames` keyword
+    if target != JsonSchemaTarget::OpenApi30
+        && let Some(names) = property_names_schema(strip_schema_uri(key_schema))
+        && let Value::Object(map) = &mut schema
+    {
+        map.insert("propertyNames".to_string(), names);
+    }
+
+    add_schema_uri(&mut schema, target);
+    schema
+}
+
+impl<K, V, S> Valrs for HashMap<K, V, S>
+where
+    K: Valrs,
+    K::Output: Eq + Hash,
+    V: Valrs,
+    S: BuildHasher + Default,
+{
+    type Input = HashMap<K::Input, V::Input, S>;
+    type Output = HashMap<K::Output, V::Output, S>;
+
+    fn validate(value: &Value) -> ValidationResult<Self::Output> {
+        Self::validate_with(value, &ValidationContext::default())
+    }
+
+    fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
+        let mut output = HashMap::with_hasher(S::default());
+        match validate_entries::<K, V>(value, ctx, |k, v| output.insert(k, v).is_none()) {
+            Ok(()) => ValidationResult::success(output),
+            Err(issues) => ValidationResult::failures(issues),
+        }
+    }
+}
+
+impl<K, V, S> StandardJsonSchema for HashMap<K, V, S>
+where
+    K: StandardJsonSchema,
+    K::Output: Eq + Hash,
+    V: StandardJsonSchema,
+    S: BuildHasher + Default,
+{
+    fn json_schema_input(target: JsonSchemaTarget) -> Value {
+        record_schema(
+            K::json_schema_input(target),
+            V::json_schema_input(target),
+            target,
+        )
+    }
+
+    fn json_schema_output(target: JsonSchemaTarget) -> Value {
+        record_schema(
+            K::json_schema_output(target),
+            V::json_schema_output(target),
+            target,
+        )
+    }
+
+    // Keys are strings in JSON, and always read as numbers when `K` is numeric
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        record_schema(
+            K::json_schema_input(target),
+            V::json_schema_coerced_input(target),
+            target,
+        )
+    }
+}
+
+impl<K, V> Valrs for BTreeMap<K, V>
+where
+    K: Valrs,
+    K::Output: Ord,
+    V: Valrs,
+{
+    type Input = BTreeMap<K::Input, V::Input>;
+    type Output = BTreeMap<K::Output, V::Output>;
+
+    fn validate(value: &Value) -> ValidationResult<Self::Output> {
+        Self::validate_with(value, &ValidationContext::default())
+    }
+
+    fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
+        let mut output = BTreeMap::new();
+        match validate_entries::<K, V>(value, ctx, |k, v| output.insert(k, v).is_none()) {
+            Ok(()) => ValidationResult::success(output),
+            Err(issues) => ValidationResult::failures(issues),
+        }
+    }
+}
+
+impl<K, V> StandardJsonSchema for BTreeMap<K, V>
+where
+    K: StandardJsonSchema,
+    K::Output: Ord,
+    V: StandardJsonSchema,
+{
+    fn json_schema_input(target: JsonSchemaTarget) -> Value {
+        record_schema(
+            K::json_schema_input(target),
+            V::json_schema_input(target),
+            target,
+        )
+    }
+
+    fn json_schema_output(target: JsonSchemaTarget) -> Value {
+        record_schema(
+            K::json_schema_output(target),
+            V::json_schema_output(target),
+            target,
+        )
+    }
+
+    // Keys are strings in JSON, and always read as numbers when `K` is numeric
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        record_schema(
+            K::json_schema_input(target),
+            V::json_schema_coerced_input(target),
+            target,
+        )
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<K, V, S> Valrs for indexmap::IndexMap<K, V, S>
+where
+    K: Valrs,
+    K::Output: Eq + Hash,
+    V: Valrs,
+    S: BuildHasher + Default,
+{
+    type Input = indexmap::IndexMap<K::Input, V::Input, S>;
+    type Output = indexmap::IndexMap<K::Output, V::Output, S>;
+
+    fn validate(value: &Value) -> ValidationResult<Self::Output> {
+        Self::validate_with(value, &ValidationContext::default())
+    }
+
+    fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
+        let mut output = indexmap::IndexMap::with_hasher(S::default());
+        match validate_entries::<K, V>(value, ctx, |k, v| output.insert(k, v).is_none()) {
+            Ok(()) => ValidationResult::success(output),
+            Err(issues) => ValidationResult::failures(issues),
+        }
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<K, V, S> StandardJsonSchema for indexmap::IndexMap<K, V, S>
+where
+    K: StandardJsonSchema,
+    K::Output: Eq + Hash,
+    V: StandardJsonSchema,
+    S: BuildHasher + Default,
+{
+    fn json_schema_input(target: JsonSchemaTarget) -> Value {
+        record_schema(
+            K::json_schema_input(target),
+            V::json_schema_input(target),
+            target,
+        )
+    }
+
+    fn json_schema_output(target: JsonSchemaTarget) -> Value {
+        record_schema(
+            K::json_schema_output(target),
+            V::json_schema_output(target),
+            target,
+        )
+    }
+
+    // Keys are strings in JSON, and always read as numbers when `K` is numeric
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        record_schema(
+            K::json_schema_input(target),
+            V::json_schema_coerced_input(target),
+            target,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IssueCode;
+    use serde_json::json;
+
+    #[test]
+    fn test_hash_map_string_keys() {
+        let result =
+            <HashMap<String, String>>::validate(&json!({ "en": "Hello", "fr": "Bonjour" }));
+        let map = result.ok().unwrap();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["fr"], "Bonjour");
+
+        assert!(<HashMap<String, String>>::validate(&json!(["en"])).is_failure());
+        assert!(<HashMap<String, String>>::validate(&json!({})).is_success());
+    }
+
+    #[test]
+    fn test_map_value_issue_paths() {
+        let result = <BTreeMap<String, u8>>::validate(&json!({ "a": 1, "b": "x", "c": 999 }));
+        let issues = result.issues();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].path, Some(vec![PathSegment::Key("b".into())]));
+        assert_eq!(issues[1].path, Some(vec![PathSegment::Key("c".into())]));
+    }
+
+    #[test]
+    fn test_map_numeric_keys() {
+        let result = <BTreeMap<u32, bool>>::validate(&json!({ "1": true, "20": false }));
+        let map = result.ok().unwrap();
+        assert_eq!(map.get(&20), Some(&false));
+
+        let result = <BTreeMap<u32, bool>>::validate(&json!({ "one": true, "-1": false }));
+        let issues = result.issues();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].path, Some(vec![PathSegment::Key("-1".into())]));
+        assert_eq!(issues[1].path, Some(vec![PathSegment::Key("one".into())]));
+    }
+
+    /// A key type that ignores case.
+    struct Lowercase;
+
+    impl Valrs for Lowercase {
+        type Input = String;
+        type Output = String;
+
+        fn validate(value: &Value) -> ValidationResult<Self::Output> {
+            String::validate(value).map(|s| s.to_lowercase())
+        }
+    }
+
+    /// A numeric key type reading whole numbers, so `1` and `1.0` are the
+    /// same key.
+    struct Whole;
+
+    impl Valrs for Whole {
+        type Input = f64;
+        type Output = i64;
+
+        fn validate(value: &Value) -> ValidationResult<Self::Output> {
+            match value.as_f64() {
+                Some(n) if n.fract() == 0.0 => ValidationResult::success(n as i64),
+                _ => ValidationResult::failure("Expected whole number"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_map_duplicate_keys() {
+        let result = <BTreeMap<Whole, bool>>::validate(&json!({ "1": true, "1.0": false }));
+        let issues = result.issues();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, IssueCode::NotUnique);
+        assert_eq!(
+            issues[0].message,
+            "Duplicate key '1.0', same as an earlier key"
+        );
+        assert_eq!(issues[0].path, Some(vec![PathSegment::Key("1.0".into())]));
+
+        // Entries are read in key order, so "EN" comes first
+        let result = <HashMap<Lowercase, i32>>::validate(&json!({ "EN": 1, "en": 2 }));
+        let issues = result.issues();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, IssueCode::NotUnique);
+        assert_eq!(
+            issues[0].message,
+            "Duplicate key 'en', same as an earlier key"
+        );
+        assert_eq!(issues[0].path, Some(vec![PathSegment::Key("en".into())]));
+    }
+
+    #[test]
+    fn test_map_nested_paths() {
+        let result = <HashMap<String, Vec<i32>>>::validate(&json!({ "xs": [1, "2"] }));
+        assert_eq!(
+            result.issues()[0].path,
+            Some(vec![PathSegment::Key("xs".into()), PathSegment::Index(1)])
+        );
+    }
+
+    #[test]
+    fn test_map_json_schema() {
+        let schema = <HashMap<String, i32> as StandardJsonSchema>::json_schema_input(
+            JsonSchemaTarget::Draft202012,
+        );
+        assert_eq!(schema["type"], "object");
+        assert_eq!(schema["additionalProperties"], json!({ "type": "integer" }));
+        assert!(schema.get("propertyNames").is_none());
+        assert_eq!(
+            schema["$schema"],
+            "https://json-schema.org/draft/2020-12/schema"
+        );
+    }
+
+    #[test]
+    fn test_map_numeric_key_json_schema() {
+        let schema = <BTreeMap<u32, String> as StandardJsonSchema>::json_schema_input(
+            JsonSchemaTarget::Draft07,
+        );
+        assert_eq!(schema["propertyNames"]["type"], "string");
+        assert_eq!(schema["propertyNames"]["pattern"], "^-?(0|[1-9][0-9]*)$");
+
+        let schema = <BTreeMap<u32, String> as StandardJsonSchema>::json_schema_input(
+            JsonSchemaTarget::OpenApi30,
+        );
+        assert!(schema.get("propertyNames").is_none());
+        assert!(schema.get("$schema").is_none());
+    }
+
+    #[cfg(feature = "indexmap")]
+    #[test]
+    fn test_index_map_validation() {
+        let result = <indexmap::IndexMap<String, i32>>::validate(&json!({ "b": 1, "a": 2 }));
+        let map = result.ok().unwrap();
+        assert_eq!(map.get("b"), Some(&1));
+
+        let result = <indexmap::IndexMap<String, i32>>::validate(&json!({ "b": "1" }));
+        assert_eq!(
+            result.issues()[0].path,
+            Some(vec![PathSegment::Key("b".into())])
+        );
+    }
+}
@@ -3,13 +3,27 @@
 //! This module provides `Valrs` and `StandardJsonSchema` implementations
 //! for common Rust types.
 
+mod collection;
+#[cfg(feature = "chrono")]
+mod date;
+mod format;
+mod map;
 mod number;
 mod option;
 mod string;
+mod tuple;
 
+pub use collection::duplicate_items;
+pub use format::{Pattern, StringFormat};
+pub use number::{BoundedNumber, is_multiple_of};
+pub use option::nullable_schema;
 pub use string::{MaxLengthString, MinLengthString, NonEmptyString};
+pub use tuple::{expect_array_of_len, tuple_schema};
 
-use crate::{JsonSchemaTarget, StandardJsonSchema, ValidationResult, Valrs};
+use crate::{
+    JsonSchemaTarget, StandardJsonSchema, ValidationContext, ValidationIssue, ValidationResult,
+    Valrs,
+};
 use serde_json::{Value, json};
 
 // =============================================================================
@@ -17,14 +31,27 @@ use serde_json::{Value, json};
 // =============================================================================
 
 /// Validates that a value is a boolean.
+///
+/// In coercion mode, the strings `"true"` and `"1"` are `true`, and `"false"`
+/// and `"0"` are `false`.
 impl Valrs for bool {
     type Input = bool;
     type Output = bool;
 
     fn validate(value: &Value) -> ValidationResult<Self::Output> {
+        Self::validate_with(value, &ValidationContext::default())
+    }
+
+    fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
         match value {
             Value::Bool(b) => ValidationResult::success(*b),
-            _ => ValidationResult::failure("Expected boolean"),
+            Value::String(s) if ctx.coerce() && matches!(s.as_str(), "true" | "1") => {
+                ValidationResult::success(true)
+            }
+            Value::String(s) if ctx.coerce() && matches!(s.as_str(), "false" | "0") => {
+                ValidationResult::success(false)
+            }
+            _ => invalid_coerced_type("Expected boolean", "boolean", value, ctx),
         }
     }
 }
@@ -39,6 +66,14 @@ impl StandardJsonSchema for bool {
     fn json_schema_output(target: JsonSchemaTarget) -> Value {
         Self::json_schema_input(target)
     }
+
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        coerced_schema(
+            Self::json_schema_input(target),
+            json!({ "type": "string", "enum": ["true", "false", "1", "0"] }),
+            target,
+        )
+    }
 }
 
 // =============================================================================
@@ -53,7 +88,7 @@ impl Valrs for () {
     fn validate(value: &Value) -> ValidationResult<Self::Output> {
         match value {
             Value::Null => ValidationResult::success(()),
-            _ => ValidationResult::failure("Expected null"),
+            _ => invalid_type("Expected null", "null", value),
         }
     }
 }
@@ -74,6 +109,39 @@ impl StandardJsonSchema for () {
 // Helper functions
 // =============================================================================
 
+/// Fails with `message` for a value that is not of the `expected` JSON type.
+pub(crate) fn invalid_type<T>(message: &str, expected: &str, value: &Value) -> ValidationResult<T> {
+    ValidationResult::failures(vec![ValidationIssue::invalid_type(
+        message, expected, value,
+    )])
+}
+
+/// Fails like `invalid_type` for a type converting strings in coercion mode,
+/// where the message also names the string that could not be converted.
+pub(crate) fn invalid_coerced_type<T>(
+    message: &str,
+    expected: &str,
+    value: &Value,
+    ctx: &ValidationContext,
+) -> ValidationResult<T> {
+    match value {
+        Value::String(s) if ctx.coerce() => invalid_type(
+            &format!("{}, could not convert '{}'", message, s),
+            expected,
+            value,
+        ),
+        _ => invalid_type(message, expected, value),
+    }
+}
+
+/// Builds the coerced input schema of a type accepting input matching either
+/// `schema` or, once converted, `converted`.
+pub(crate) fn coerced_schema(schema: Value, converted: Value, target: JsonSchemaTarget) -> Value {
+    let mut schema = json!({ "anyOf": [strip_schema_uri(schema), converted] });
+    add_schema_uri(&mut schema, target);
+    schema
+}
+
 /// Adds the `$schema` URI to a schema object if applicable for the target.
 pub(crate) fn add_schema_uri(schema: &mut Value, target: JsonSchemaTarget) {
     if let Value::Object(map) = schema {
@@ -84,6 +152,14 @@ pub(crate) fn add_schema_uri(schema: &mut Value, target: JsonSchemaTarget) {
     }
 }
 
+/// Removes the `$schema` URI so a schema can be embedded inside another schema.
+pub(crate) fn strip_schema_uri(mut schema: Value) -> Value {
+    if let Value::Object(map) = &mut schema {
+        map.remove("$schema");
+    }
+    schema
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -119,4 +195,21 @@ mod tests {
         let schema = <() as StandardJsonSchema>::json_schema_input(JsonSchemaTarget::Draft202012);
         assert_eq!(schema["type"], "null");
     }
+
+    #[test]
+    fn test_coerced_bool() {
+        let ctx = ValidationContext::new().with_coerce(true);
+        assert_eq!(bool::validate_with(&json!("true"), &ctx).ok(), Some(true));
+        assert_eq!(bool::validate_with(&json!("1"), &ctx).ok(), Some(true));
+        assert_eq!(bool::validate_with(&json!("false"), &ctx).ok(), Some(false));
+        assert_eq!(bool::validate_with(&json!("0"), &ctx).ok(), Some(false));
+        assert!(bool::validate(&json!("true")).is_failure());
+
+        let result = bool::validate_with(&json!("yes"), &ctx);
+        assert_eq!(
+            result.issues()[0].message,
+            "Expected boolean, could not convert 'yes'"
+        );
+        assert!(bool::validate_with(&json!(1), &ctx).is_failure());
+    }
 }
@@ -1,8 +1,152 @@
 //! Number validation implementations.
 
-use crate::validators::add_schema_uri;
-use crate::{JsonSchemaTarget, StandardJsonSchema, ValidationResult, Valrs};
-use serde_json::{Value, json};
+use std::borrow::Cow;
+use std::cmp::Ordering;
+
+use crate::validators::{add_schema_uri, coerced_schema, invalid_coerced_type};
+use crate::{
+    JsonSchemaTarget, NumberBound, SizeType, StandardJsonSchema, ValidationContext,
+    ValidationIssue, ValidationResult, Valrs,
+};
+use serde_json::{Number, Value, json};
+
+/// Returns whether `value` is a multiple of `divisor`.
+///
+/// The check tolerates floating point rounding, so `0.3` is a multiple of `0.1`.
+/// Used by derived types with a `multiple_of` constraint.
+pub fn is_multiple_of(value: f64, divisor: f64) -> bool {
+    let quotient = value / divisor;
+    (quotient - quotient.round()).abs() <= 1e-9 * quotient.abs().max(1.0)
+}
+
+/// A primitive number checked against the bounds of derived constraints.
+///
+/// Integers are compared with integer bounds as `i128`, so bounds beyond 2^53
+/// are exact. Comparisons involving a float are done as `f64`.
+pub trait BoundedNumber: Copy {
+    /// Orders the value against `bound`, or `None` if either is NaN.
+    fn cmp_bound(self, bound: NumberBound) -> Option<Ordering>;
+
+    /// Returns whether the value is a multiple of `divisor`, which is
+    /// positive.
+    fn is_multiple_of_bound(self, divisor: NumberBound) -> bool;
+}
+
+macro_rules! impl_bounded_integer {
+    ($($ty:ty),*) => {
+        $(
+            impl BoundedNumber for $ty {
+                fn cmp_bound(self, bound: NumberBound) -> Option<Ordering> {
+                    match (i128::try_from(self), bound) {
+                        (Ok(n), NumberBound::Integer(bound)) => Some(n.cmp(&bound)),
+                        // Only a `u128` above `i128::MAX` does not convert
+                        (Err(_), NumberBound::Integer(_)) => Some(Ordering::Greater),
+                        (_, NumberBound::Float(bound)) => (self as f64).partial_cmp(&bound),
+                    }
+                }
+
+                fn is_multiple_of_bound(self, divisor: NumberBound) -> bool {
+                    match (i128::try_from(self), divisor) {
+                        (Ok(n), NumberBound::Integer(divisor)) => n.checked_rem(divisor) == Some(0),
+                        _ => is_multiple_of(self as f64, divisor.as_f64()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_bounded_integer!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+
+macro_rules! impl_bounded_float {
+    ($($ty:ty),*) => {
+        $(
+            impl BoundedNumber for $ty {
+                fn cmp_bound(self, bound: NumberBound) -> Option<Ordering> {
+                    (self as f64).partial_cmp(&bound.as_f64())
+                }
+
+                fn is_multiple_of_bound(self, divisor: NumberBound) -> bool {
+                    is_multiple_of(self as f64, divisor.as_f64())
+                }
+            }
+        )*
+    };
+}
+
+impl_bounded_float!(f32, f64);
+
+/// Fails with `message` for the integer `n` outside `min..=max`, reporting
+/// the bound it crosses.
+fn out_of_range<T>(message: String, n: i128, min: i128, max: i128) -> ValidationResult<T> {
+    let issue = if n < min {
+        ValidationIssue::too_small(message, SizeType::Number, min, true)
+    } else {
+        ValidationIssue::too_big(message, SizeType::Number, max, true)
+    };
+    ValidationResult::failures(vec![issue])
+}
+
+/// Matches the strings converted to integers in coercion mode, and integer
+/// map keys.
+pub(crate) const INTEGER_STRING_PATTERN: &str = "^-?(0|[1-9][0-9]*)$";
+
+/// Matches the strings converted to numbers in coercion mode, and number map
+/// keys.
+pub(crate) const NUMBER_STRING_PATTERN: &str = "^-?(0|[1-9][0-9]*)(\\.[0-9]+)?([eE][+-]?[0-9]+)?$";
+
+/// In coercion mode, converts a string holding a JSON number, such as `"42"`,
+/// to that number. Other values, and strings that are not numbers, are
+/// returned as they are.
+fn coerce_number<'a>(value: &'a Value, ctx: &ValidationContext) -> Cow<'a, Value> {
+    match value {
+        Value::String(s) if ctx.coerce() => match s.parse::<Number>() {
+            Ok(n) => Cow::Owned(Value::Number(n)),
+            Err(_) => Cow::Borrowed(value),
+        },
+        _ => Cow::Borrowed(value),
+    }
+}
+
+/// Builds the coerced input schema of a number type, which also accepts
+/// strings matching `pattern`.
+fn coerced_number_schema(number_type: &str, pattern: &str, target: JsonSchemaTarget) -> Value {
+    coerced_schema(
+        json!({ "type": number_type }),
+        json!({ "type": "string", "pattern": pattern }),
+        target,
+    )
+}
+
+/// Fails for a value that is not an integer. The issue describes `value` as
+/// it was received, before any coercion.
+fn expected_integer<T>(value: &Value, ctx: &ValidationContext) -> ValidationResult<T> {
+    invalid_coerced_type("Expected integer", "integer", value, ctx)
+}
+
+/// Fails for a value that is not a non-negative integer: negative integers
+/// are too small, anything else has the wrong type.
+///
+/// `number` is `value` after coercion, and decides which issue applies; the
+/// issue describes `value` as it was received.
+fn expected_non_negative_integer<T>(
+    value: &Value,
+    number: &Value,
+    ctx: &ValidationContext,
+) -> ValidationResult<T> {
+    let message = "Expected non-negative integer";
+    match number.as_i64() {
+        Some(_) => ValidationResult::failures(vec![ValidationIssue::too_small(
+            message,
+            SizeType::Number,
+            0.0,
+            true,
+        )]),
+        None => invalid_coerced_type(message, "integer", value, ctx),
+    }
+}
 
 // =============================================================================
 // Signed integer implementations
@@ -13,16 +157,22 @@ impl Valrs for i8 {
     type Output = i8;
 
     fn validate(value: &Value) -> ValidationResult<Self::Output> {
-        match value.as_i64() {
+        Self::validate_with(value, &ValidationContext::default())
+    }
+
+    fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
+        let number = coerce_number(value, ctx);
+        match number.as_i64() {
             Some(n) if n >= i8::MIN as i64 && n <= i8::MAX as i64 => {
                 ValidationResult::success(n as i8)
             }
-            Some(_) => ValidationResult::failure(format!(
-                "Integer out of range for i8 ({} to {})",
-                i8::MIN,
-                i8::MAX
-            )),
-            None => ValidationResult::failure("Expected integer"),
+            Some(n) => out_of_range(
+                format!("Integer out of range for i8 ({} to {})", i8::MIN, i8::MAX),
+                n as i128,
+                i8::MIN as i128,
+                i8::MAX as i128,
+            ),
+            None => expected_integer(value, ctx),
         }
     }
 }
@@ -37,6 +187,10 @@ impl StandardJsonSchema for i8 {
     fn json_schema_output(target: JsonSchemaTarget) -> Value {
         Self::json_schema_input(target)
     }
+
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        coerced_number_schema("integer", INTEGER_STRING_PATTERN, target)
+    }
 }
 
 impl Valrs for i16 {
@@ -44,16 +198,26 @@ impl Valrs for i16 {
     type Output = i16;
 
     fn validate(value: &Value) -> ValidationResult<Self::Output> {
-        match value.as_i64() {
+        Self::validate_with(value, &ValidationContext::default())
+    }
+
+    fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
+        let number = coerce_number(value, ctx);
+        match number.as_i64() {
             Some(n) if n >= i16::MIN as i64 && n <= i16::MAX as i64 => {
                 ValidationResult::success(n as i16)
             }
-            Some(_) => ValidationResult::failure(format!(
-                "Integer out of range for i16 ({} to {})",
-                i16::MIN,
-                i16::MAX
-            )),
-            None => ValidationResult::failure("Expected integer"),
+            Some(n) => out_of_range(
+                format!(
+                    "Integer out of range for i16 ({} to {})",
+                    i16::MIN,
+                    i16::MAX
+                ),
+                n as i128,
+                i16::MIN as i128,
+                i16::MAX as i128,
+            ),
+            None => expected_integer(value, ctx),
         }
     }
 }
@@ -68,6 +232,10 @@ impl StandardJsonSchema for i16 {
     fn json_schema_output(target: JsonSchemaTarget) -> Value {
         Self::json_schema_input(target)
     }
+
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        coerced_number_schema("integer", INTEGER_STRING_PATTERN, target)
+    }
 }
 
 impl Valrs for i32 {
@@ -75,16 +243,26 @@ impl Valrs for i32 {
     type Output = i32;
 
     fn validate(value: &Value) -> ValidationResult<Self::Output> {
-        match value.as_i64() {
+        Self::validate_with(value, &ValidationContext::default())
+    }
+
+    fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
+        let number = coerce_number(value, ctx);
+        match number.as_i64() {
             Some(n) if n >= i32::MIN as i64 && n <= i32::MAX as i64 => {
                 ValidationResult::success(n as i32)
             }
-            Some(_) => ValidationResult::failure(format!(
-                "Integer out of range for i32 ({} to {})",
-                i32::MIN,
-                i32::MAX
-            )),
-            None => ValidationResult::failure("Expected integer"),
+            Some(n) => out_of_range(
+                format!(
+                    "Integer out of range for i32 ({} to {})",
+                    i32::MIN,
+                    i32::MAX
+                ),
+                n as i128,
+                i32::MIN as i128,
+                i32::MAX as i128,
+            ),
+            None => expected_integer(value, ctx),
         }
     }
 }
@@ -99,6 +277,10 @@ impl StandardJsonSchema for i32 {
     fn json_schema_output(target: JsonSchemaTarget) -> Value {
         Self::json_schema_input(target)
     }
+
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        coerced_number_schema("integer", INTEGER_STRING_PATTERN, target)
+    }
 }
 
 impl Valrs for i64 {
@@ -106,9 +288,14 @@ impl Valrs for i64 {
     type Output = i64;
 
     fn validate(value: &Value) -> ValidationResult<Self::Output> {
-        match value.as_i64() {
+        Self::validate_with(value, &ValidationContext::default())
+    }
+
+    fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
+        let number = coerce_number(value, ctx);
+        match number.as_i64() {
             Some(n) => ValidationResult::success(n),
-            None => ValidationResult::failure("Expected integer"),
+            None => expected_integer(value, ctx),
         }
     }
 }
@@ -123,6 +310,10 @@ impl StandardJsonSchema for i64 {
     fn json_schema_output(target: JsonSchemaTarget) -> Value {
         Self::json_schema_input(target)
     }
+
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        coerced_number_schema("integer", INTEGER_STRING_PATTERN, target)
+    }
 }
 
 impl Valrs for isize {
@@ -130,12 +321,22 @@ impl Valrs for isize {
     type Output = isize;
 
     fn validate(value: &Value) -> ValidationResult<Self::Output> {
-        match value.as_i64() {
+        Self::validate_with(value, &ValidationContext::default())
+    }
+
+    fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
+        let number = coerce_number(value, ctx);
+        match number.as_i64() {
             Some(n) if n >= isize::MIN as i64 && n <= isize::MAX as i64 => {
                 ValidationResult::success(n as isize)
             }
-            Some(_) => ValidationResult::failure("Integer out of range for isize"),
-            None => ValidationResult::failure("Expected integer"),
+            Some(n) => out_of_range(
+                "Integer out of range for isize".to_string(),
+                n as i128,
+                isize::MIN as i128,
+                isize::MAX as i128,
+            ),
+            None => expected_integer(value, ctx),
         }
     }
 }
@@ -150,6 +351,10 @@ impl StandardJsonSchema for isize {
     fn json_schema_output(target: JsonSchemaTarget) -> Value {
         Self::json_schema_input(target)
     }
+
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        coerced_number_schema("integer", INTEGER_STRING_PATTERN, target)
+    }
 }
 
 // =============================================================================
@@ -161,12 +366,20 @@ impl Valrs for u8 {
     type Output = u8;
 
     fn validate(value: &Value) -> ValidationResult<Self::Output> {
-        match value.as_u64() {
+        Self::validate_with(value, &ValidationContext::default())
+    }
+
+    fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
+        let number = coerce_number(value, ctx);
+        match number.as_u64() {
             Some(n) if n <= u8::MAX as u64 => ValidationResult::success(n as u8),
-            Some(_) => {
-                ValidationResult::failure(format!("Integer out of range for u8 (0 to {})", u8::MAX))
-            }
-            None => ValidationResult::failure("Expected non-negative integer"),
+            Some(n) => out_of_range(
+                format!("Integer out of range for u8 (0 to {})", u8::MAX),
+                n as i128,
+                0,
+                u8::MAX as i128,
+            ),
+            None => expected_non_negative_integer(value, &number, ctx),
         }
     }
 }
@@ -181,6 +394,10 @@ impl StandardJsonSchema for u8 {
     fn json_schema_output(target: JsonSchemaTarget) -> Value {
         Self::json_schema_input(target)
     }
+
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        coerced_number_schema("integer", INTEGER_STRING_PATTERN, target)
+    }
 }
 
 impl Valrs for u16 {
@@ -188,13 +405,20 @@ impl Valrs for u16 {
     type Output = u16;
 
     fn validate(value: &Value) -> ValidationResult<Self::Output> {
-        match value.as_u64() {
+        Self::validate_with(value, &ValidationContext::default())
+    }
+
+    fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
+        let number = coerce_number(value, ctx);
+        match number.as_u64() {
             Some(n) if n <= u16::MAX as u64 => ValidationResult::success(n as u16),
-            Some(_) => ValidationResult::failure(format!(
-                "Integer out of range for u16 (0 to {})",
-                u16::MAX
-            )),
-            None => ValidationResult::failure("Expected non-negative integer"),
+            Some(n) => out_of_range(
+                format!("Integer out of range for u16 (0 to {})", u16::MAX),
+                n as i128,
+                0,
+                u16::MAX as i128,
+            ),
+            None => expected_non_negative_integer(value, &number, ctx),
         }
     }
 }
@@ -209,6 +433,10 @@ impl StandardJsonSchema for u16 {
     fn json_schema_output(target: JsonSchemaTarget) -> Value {
         Self::json_schema_input(target)
     }
+
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        coerced_number_schema("integer", INTEGER_STRING_PATTERN, target)
+    }
 }
 
 impl Valrs for u32 {
@@ -216,13 +444,20 @@ impl Valrs for u32 {
     type Output = u32;
 
     fn validate(value: &Value) -> ValidationResult<Self::Output> {
-        match value.as_u64() {
+        Self::validate_with(value, &ValidationContext::default())
+    }
+
+    fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
+        let number = coerce_number(value, ctx);
+        match number.as_u64() {
             Some(n) if n <= u32::MAX as u64 => ValidationResult::success(n as u32),
-            Some(_) => ValidationResult::failure(format!(
-                "Integer out of range for u32 (0 to {})",
-                u32::MAX
-            )),
-            None => ValidationResult::failure("Expected non-negative integer"),
+            Some(n) => out_of_range(
+                format!("Integer out of range for u32 (0 to {})", u32::MAX),
+                n as i128,
+                0,
+                u32::MAX as i128,
+            ),
+            None => expected_non_negative_integer(value, &number, ctx),
         }
     }
 }
@@ -237,6 +472,10 @@ impl StandardJsonSchema for u32 {
     fn json_schema_output(target: JsonSchemaTarget) -> Value {
         Self::json_schema_input(target)
     }
+
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        coerced_number_schema("integer", INTEGER_STRING_PATTERN, target)
+    }
 }
 
 impl Valrs for u64 {
@@ -244,9 +483,14 @@ impl Valrs for u64 {
     type Output = u64;
 
     fn validate(value: &Value) -> ValidationResult<Self::Output> {
-        match value.as_u64() {
+        Self::validate_with(value, &ValidationContext::default())
+    }
+
+    fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
+        let number = coerce_number(value, ctx);
+        match number.as_u64() {
             Some(n) => ValidationResult::success(n),
-            None => ValidationResult::failure("Expected non-negative integer"),
+            None => expected_non_negative_integer(value, &number, ctx),
         }
     }
 }
@@ -261,6 +505,10 @@ impl StandardJsonSchema for u64 {
     fn json_schema_output(target: JsonSchemaTarget) -> Value {
         Self::json_schema_input(target)
     }
+
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        coerced_number_schema("integer", INTEGER_STRING_PATTERN, target)
+    }
 }
 
 impl Valrs for usize {
@@ -268,10 +516,20 @@ impl Valrs for usize {
     type Output = usize;
 
     fn validate(value: &Value) -> ValidationResult<Self::Output> {
-        match value.as_u64() {
+        Self::validate_with(value, &ValidationContext::default())
+    }
+
+    fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
+        let number = coerce_number(value, ctx);
+        match number.as_u64() {
             Some(n) if n <= usize::MAX as u64 => ValidationResult::success(n as usize),
-            Some(_) => ValidationResult::failure("Integer out of range for usize"),
-            None => ValidationResult::failure("Expected non-negative integer"),
+            Some(n) => out_of_range(
+                "Integer out of range for usize".to_string(),
+                n as i128,
+                0,
+                usize::MAX as i128,
+            ),
+            None => expected_non_negative_integer(value, &number, ctx),
         }
     }
 }
@@ -286,6 +544,10 @@ impl StandardJsonSchema for usize {
     fn json_schema_output(target: JsonSchemaTarget) -> Value {
         Self::json_schema_input(target)
     }
+
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        coerced_number_schema("integer", INTEGER_STRING_PATTERN, target)
+    }
 }
 
 // =============================================================================
@@ -297,9 +559,14 @@ impl Valrs for f32 {
     type Output = f32;
 
     fn validate(value: &Value) -> ValidationResult<Self::Output> {
-        match value.as_f64() {
+        Self::validate_with(value, &ValidationContext::default())
+    }
+
+    fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
+        let number = coerce_number(value, ctx);
+        match number.as_f64() {
             Some(n) => ValidationResult::success(n as f32),
-            None => ValidationResult::failure("Expected number"),
+            None => invalid_coerced_type("Expected number", "number", value, ctx),
         }
     }
 }
@@ -314,6 +581,10 @@ impl StandardJsonSchema for f32 {
     fn json_schema_output(target: JsonSchemaTarget) -> Value {
         Self::json_schema_input(target)
     }
+
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        coerced_number_schema("number", NUMBER_STRING_PATTERN, target)
+    }
 }
 
 impl Valrs for f64 {
@@ -321,9 +592,14 @@ impl Valrs for f64 {
     type Output = f64;
 
     fn validate(value: &Value) -> ValidationResult<Self::Output> {
-        match value.as_f64() {
+        Self::validate_with(value, &ValidationContext::default())
+    }
+
+    fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
+        let number = coerce_number(value, ctx);
+        match number.as_f64() {
             Some(n) => ValidationResult::success(n),
-            None => ValidationResult::failure("Expected number"),
+            None => invalid_coerced_type("Expected number", "number", value, ctx),
         }
     }
 }
@@ -338,11 +614,16 @@ impl StandardJsonSchema for f64 {
     fn json_schema_output(target: JsonSchemaTarget) -> Value {
         Self::json_schema_input(target)
     }
+
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        coerced_number_schema("number", NUMBER_STRING_PATTERN, target)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::IssueCode;
     use serde_json::json;
 
     #[test]
@@ -378,6 +659,34 @@ mod tests {
         assert!(i8::validate(&json!(-129)).is_failure());
     }
 
+    #[test]
+    fn test_integer_issue_codes() {
+        let result = i8::validate(&json!(128));
+        let issue = &result.issues()[0];
+        assert_eq!(issue.code, IssueCode::TooBig);
+        assert_eq!(issue.params.maximum, Some(127.into()));
+
+        let result = i8::validate(&json!(-129));
+        let issue = &result.issues()[0];
+        assert_eq!(issue.code, IssueCode::TooSmall);
+        assert_eq!(issue.params.minimum, Some((-128).into()));
+
+        let result = u32::validate(&json!(-1));
+        let issue = &result.issues()[0];
+        assert_eq!(issue.message, "Expected non-negative integer");
+        assert_eq!(issue.code, IssueCode::TooSmall);
+        assert_eq!(issue.params.minimum, Some(0.into()));
+
+        let result = i32::validate(&json!("42"));
+        let issue = &result.issues()[0];
+        assert_eq!(issue.code, IssueCode::InvalidType);
+        assert_eq!(issue.params.expected.as_deref(), Some("integer"));
+        assert_eq!(issue.params.received.as_deref(), Some("string"));
+
+        let result = f64::validate(&json!(null));
+        assert_eq!(result.issues()[0].params.received.as_deref(), Some("null"));
+    }
+
     #[test]
     fn test_i32_json_schema() {
         let schema = <i32 as StandardJsonSchema>::json_schema_input(JsonSchemaTarget::Draft202012);
@@ -401,4 +710,85 @@ mod tests {
         assert_eq!(schema["type"], "number");
         assert_eq!(schema["$schema"], "http://json-schema.org/draft-07/schema#");
     }
+
+    #[test]
+    fn test_is_multiple_of() {
+        assert!(is_multiple_of(15.0, 5.0));
+        assert!(is_multiple_of(-10.0, 5.0));
+        assert!(is_multiple_of(0.3, 0.1));
+        assert!(!is_multiple_of(7.0, 5.0));
+        assert!(!is_multiple_of(0.35, 0.1));
+    }
+
+    #[test]
+    fn test_bounded_number() {
+        // 2^53 + 1 rounds to 2^53 as a float
+        let bound = NumberBound::Integer(9_007_199_254_740_993);
+        assert_eq!(
+            9_007_199_254_740_992i64.cmp_bound(bound),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            9_007_199_254_740_993u64.cmp_bound(bound),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(u128::MAX.cmp_bound(bound), Some(Ordering::Greater));
+        assert_eq!(
+            2i32.cmp_bound(NumberBound::Float(1.5)),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(f64::NAN.cmp_bound(NumberBound::Integer(0)), None);
+
+        assert!(9_007_199_254_740_993i64.is_multiple_of_bound(NumberBound::Integer(3)));
+        assert!(!9_007_199_254_740_992i64.is_multiple_of_bound(NumberBound::Integer(3)));
+        assert!(0.3f64.is_multiple_of_bound(NumberBound::Float(0.1)));
+    }
+
+    #[test]
+    fn test_coerced_numbers() {
+        let ctx = ValidationContext::new().with_coerce(true);
+        assert_eq!(i32::validate_with(&json!("42"), &ctx).ok(), Some(42));
+        assert_eq!(i32::validate_with(&json!(42), &ctx).ok(), Some(42));
+        assert_eq!(f64::validate_with(&json!("-2.5e1"), &ctx).ok(), Some(-25.0));
+
+        // Converted integers are range checked
+        let result = u8::validate_with(&json!("300"), &ctx);
+        assert_eq!(result.issues()[0].code, IssueCode::TooBig);
+        let result = u8::validate_with(&json!("-1"), &ctx);
+        assert_eq!(result.issues()[0].code, IssueCode::TooSmall);
+
+        let result = i32::validate_with(&json!("forty-two"), &ctx);
+        assert_eq!(result.issues()[0].code, IssueCode::InvalidType);
+        assert_eq!(
+            result.issues()[0].message,
+            "Expected integer, could not convert 'forty-two'"
+        );
+        assert!(i32::validate_with(&json!(" 42"), &ctx).is_failure());
+
+        // Numbers that are not integers are reported as the string received
+        let result = i32::validate_with(&json!("4.2"), &ctx);
+        let issue = &result.issues()[0];
+        assert_eq!(issue.message, "Expected integer, could not convert '4.2'");
+        assert_eq!(issue.params.received.as_deref(), Some("string"));
+        let result = u32::validate_with(&json!("1e3"), &ctx);
+        assert_eq!(
+            result.issues()[0].message,
+            "Expected non-negative integer, could not convert '1e3'"
+        );
+        let result = u32::validate_with(&json!("-1"), &ctx);
+        assert_eq!(result.issues()[0].code, IssueCode::TooSmall);
+    }
+
+    #[test]
+    fn test_coerced_number_json_schema() {
+        assert_eq!(
+            u16::json_schema_coerced_input(JsonSchemaTarget::OpenApi30),
+            json!({
+                "anyOf": [
+                    { "type": "integer" },
+                    { "type": "string", "pattern": "^-?(0|[1-9][0-9]*)$" }
+                ]
+            })
+        );
+    }
 }
@@ -1,7 +1,7 @@
 //! Option validation implementations.
 
-use crate::validators::add_schema_uri;
-use crate::{JsonSchemaTarget, StandardJsonSchema, ValidationResult, Valrs};
+use crate::validators::{add_schema_uri, strip_schema_uri};
+use crate::{JsonSchemaTarget, StandardJsonSchema, ValidationContext, ValidationResult, Valrs};
 use serde_json::{Value, json};
 
 impl<T: Valrs> Valrs for Option<T> {
@@ -9,43 +9,61 @@ impl<T: Valrs> Valrs for Option<T> {
     type Output = Option<T::Output>;
 
     fn validate(value: &Value) -> ValidationResult<Self::Output> {
+        Self::validate_with(value, &ValidationContext::default())
+    }
+
+    fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
         match value {
             Value::Null => ValidationResult::success(None),
-            _ => T::validate(value).map(Some),
+            _ => T::validate_with(value, ctx).map(Some),
         }
     }
 }
 
-impl<T: StandardJsonSchema> StandardJsonSchema for Option<T> {
-    fn json_schema_input(target: JsonSchemaTarget) -> Value {
-        // Get the inner type's schema without $schema field
-        let inner_schema = T::json_schema_input(JsonSchemaTarget::OpenApi30);
-
-        match target {
-            // OpenAPI 3.0 uses nullable: true
-            JsonSchemaTarget::OpenApi30 => {
-                let mut schema = inner_schema;
-                if let Value::Object(map) = &mut schema {
-                    map.insert("nullable".to_string(), Value::Bool(true));
-                }
-                schema
-            }
-            // JSON Schema uses anyOf with null type
-            JsonSchemaTarget::Draft202012 | JsonSchemaTarget::Draft07 => {
-                let mut schema = json!({
-                    "anyOf": [
-                        inner_schema,
-                        { "type": "null" }
-                    ]
-                });
-                add_schema_uri(&mut schema, target);
-                schema
+/// Makes `inner_schema` nullable for `target`: `nullable: true` in OpenAPI 3.0,
+/// an `anyOf` with the `null` type in JSON Schema.
+///
+/// The inner schema should not contain `$schema`; the returned schema has
+/// `$schema` set for `target`. This is also used by `Maybe` and by derived
+/// types for nullable optional fields.
+pub fn nullable_schema(inner_schema: Value, target: JsonSchemaTarget) -> Value {
+    match target {
+        // OpenAPI 3.0 uses nullable: true
+        JsonSchemaTarget::OpenApi30 => {
+            let mut schema = inner_schema;
+            if let Value::Object(map) = &mut schema {
+                map.insert("nullable".to_string(), Value::Bool(true));
             }
+            schema
+        }
+        // JSON Schema uses anyOf with null type
+        JsonSchemaTarget::Draft202012 | JsonSchemaTarget::Draft07 => {
+            let mut schema = json!({
+                "anyOf": [
+                    inner_schema,
+                    { "type": "null" }
+                ]
+            });
+            add_schema_uri(&mut schema, target);
+            schema
         }
     }
+}
+
+impl<T: StandardJsonSchema> StandardJsonSchema for Option<T> {
+    fn json_schema_input(target: JsonSchemaTarget) -> Value {
+        nullable_schema(strip_schema_uri(T::json_schema_input(target)), target)
+    }
 
     fn json_schema_output(target: JsonSchemaTarget) -> Value {
-        Self::json_schema_input(target)
+        nullable_schema(strip_schema_uri(T::json_schema_output(target)), target)
+    }
+
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        nullable_schema(
+            strip_schema_uri(T::json_schema_coerced_input(target)),
+            target,
+        )
     }
 }
 
@@ -125,4 +143,21 @@ mod tests {
         assert_eq!(schema["type"], "integer");
         assert_eq!(schema["nullable"], true);
     }
+
+    #[test]
+    fn test_option_inner_schema_uses_target() {
+        let schema = <Option<(i32, String)> as StandardJsonSchema>::json_schema_input(
+            JsonSchemaTarget::Draft202012,
+        );
+        let inner = &schema["anyOf"][0];
+        assert!(inner["prefixItems"].is_array());
+        assert_eq!(inner["items"], false);
+        assert!(inner.get("$schema").is_none());
+
+        let schema = <Option<(i32, String)> as StandardJsonSchema>::json_schema_input(
+            JsonSchemaTarget::OpenApi30,
+        );
+        assert!(schema["items"]["anyOf"].is_array());
+        assert_eq!(schema["nullable"], true);
+    }
 }
@@ -1,17 +1,42 @@
 //! String validation implementations.
 
-use crate::validators::add_schema_uri;
-use crate::{JsonSchemaTarget, StandardJsonSchema, ValidationResult, Valrs};
+use std::borrow::Cow;
+
+use crate::validators::{add_schema_uri, coerced_schema, invalid_type};
+use crate::{
+    JsonSchemaTarget, SizeType, StandardJsonSchema, ValidationContext, ValidationIssue,
+    ValidationResult, Valrs,
+};
 use serde_json::{Value, json};
 
+/// In coercion mode, converts a number to its string, such as `42` to `"42"`.
+/// Other values are returned as they are.
+fn coerce_string<'a>(value: &'a Value, ctx: &ValidationContext) -> Cow<'a, Value> {
+    match value {
+        Value::Number(n) if ctx.coerce() => Cow::Owned(Value::String(n.to_string())),
+        _ => Cow::Borrowed(value),
+    }
+}
+
+/// Builds the coerced input schema of a string type, which also accepts
+/// numbers.
+fn coerced_string_schema(schema: Value, target: JsonSchemaTarget) -> Value {
+    coerced_schema(schema, json!({ "type": "number" }), target)
+}
+
 impl Valrs for String {
     type Input = String;
     type Output = String;
 
     fn validate(value: &Value) -> ValidationResult<Self::Output> {
+        Self::validate_with(value, &ValidationContext::default())
+    }
+
+    fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
+        let value = &coerce_string(value, ctx);
         match value.as_str() {
             Some(s) => ValidationResult::success(s.to_string()),
-            None => ValidationResult::failure("Expected string"),
+            None => invalid_type("Expected string", "string", value),
         }
     }
 }
@@ -26,6 +51,10 @@ impl StandardJsonSchema for String {
     fn json_schema_output(target: JsonSchemaTarget) -> Value {
         Self::json_schema_input(target)
     }
+
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        coerced_string_schema(Self::json_schema_input(target), target)
+    }
 }
 
 /// A non-empty string validator.
@@ -37,10 +66,20 @@ impl Valrs for NonEmptyString {
     type Output = NonEmptyString;
 
     fn validate(value: &Value) -> ValidationResult<Self::Output> {
+        Self::validate_with(value, &ValidationContext::default())
+    }
+
+    fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
+        let value = &coerce_string(value, ctx);
         match value.as_str() {
             Some(s) if !s.is_empty() => ValidationResult::success(NonEmptyString(s.to_string())),
-            Some(_) => ValidationResult::failure("String must not be empty"),
-            None => ValidationResult::failure("Expected string"),
+            Some(_) => ValidationResult::failures(vec![ValidationIssue::too_small(
+                "String must not be empty",
+                SizeType::String,
+                1.0,
+                true,
+            )]),
+            None => invalid_type("Expected string", "string", value),
         }
     }
 }
@@ -58,6 +97,10 @@ impl StandardJsonSchema for NonEmptyString {
     fn json_schema_output(target: JsonSchemaTarget) -> Value {
         Self::json_schema_input(target)
     }
+
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        coerced_string_schema(Self::json_schema_input(target), target)
+    }
 }
 
 /// A string with minimum length validation.
@@ -69,14 +112,20 @@ impl<const N: usize> Valrs for MinLengthString<N> {
     type Output = MinLengthString<N>;
 
     fn validate(value: &Value) -> ValidationResult<Self::Output> {
+        Self::validate_with(value, &ValidationContext::default())
+    }
+
+    fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
+        let value = &coerce_string(value, ctx);
         match value.as_str() {
             Some(s) if s.len() >= N => ValidationResult::success(MinLengthString(s.to_string())),
-            Some(s) => ValidationResult::failure(format!(
-                "String must be at least {} characters, got {}",
-                N,
-                s.len()
-            )),
-            None => ValidationResult::failure("Expected string"),
+            Some(s) => ValidationResult::failures(vec![ValidationIssue::too_small(
+                format!("String must be at least {} characters, got {}", N, s.len()),
+                SizeType::String,
+                N as f64,
+                true,
+            )]),
+            None => invalid_type("Expected string", "string", value),
         }
     }
 }
@@ -94,6 +143,10 @@ impl<const N: usize> StandardJsonSchema for MinLengthString<N> {
     fn json_schema_output(target: JsonSchemaTarget) -> Value {
         Self::json_schema_input(target)
     }
+
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        coerced_string_schema(Self::json_schema_input(target), target)
+    }
 }
 
 /// A string with maximum length validation.
@@ -105,14 +158,20 @@ impl<const N: usize> Valrs for MaxLengthString<N> {
     type Output = MaxLengthString<N>;
 
     fn validate(value: &Value) -> ValidationResult<Self::Output> {
+        Self::validate_with(value, &ValidationContext::default())
+    }
+
+    fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
+        let value = &coerce_string(value, ctx);
         match value.as_str() {
             Some(s) if s.len() <= N => ValidationResult::success(MaxLengthString(s.to_string())),
-            Some(s) => ValidationResult::failure(format!(
-                "String must be at most {} characters, got {}",
-                N,
-                s.len()
-            )),
-            None => ValidationResult::failure("Expected string"),
+            Some(s) => ValidationResult::failures(vec![ValidationIssue::too_big(
+                format!("String must be at most {} characters, got {}", N, s.len()),
+                SizeType::String,
+                N as f64,
+                true,
+            )]),
+            None => invalid_type("Expected string", "string", value),
         }
     }
 }
@@ -130,11 +189,16 @@ impl<const N: usize> StandardJsonSchema for MaxLengthString<N> {
     fn json_schema_output(target: JsonSchemaTarget) -> Value {
         Self::json_schema_input(target)
     }
+
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        coerced_string_schema(Self::json_schema_input(target), target)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::IssueCode;
     use serde_json::json;
 
     #[test]
@@ -165,6 +229,20 @@ mod tests {
         assert!(MaxLengthString::<5>::validate(&json!("abcdef")).is_failure());
     }
 
+    #[test]
+    fn test_string_issue_codes() {
+        let result = String::validate(&json!(true));
+        let issue = &result.issues()[0];
+        assert_eq!(issue.code, IssueCode::InvalidType);
+        assert_eq!(issue.params.received.as_deref(), Some("boolean"));
+
+        let result = MaxLengthString::<5>::validate(&json!("abcdef"));
+        let issue = &result.issues()[0];
+        assert_eq!(issue.code, IssueCode::TooBig);
+        assert_eq!(issue.params.size_type, Some(SizeType::String));
+        assert_eq!(issue.params.maximum, Some(5.into()));
+    }
+
     #[test]
     fn test_string_json_schema() {
         let schema =
@@ -201,4 +279,20 @@ mod tests {
         assert_eq!(schema["type"], "string");
         assert_eq!(schema["maxLength"], 10);
     }
+
+    #[test]
+    fn test_coerced_strings() {
+        let ctx = ValidationContext::new().with_coerce(true);
+        assert_eq!(
+            String::validate_with(&json!(42), &ctx).ok(),
+            Some("42".to_string())
+        );
+        assert_eq!(
+            String::validate_with(&json!(-0.5), &ctx).ok(),
+            Some("-0.5".to_string())
+        );
+        assert!(String::validate_with(&json!(true), &ctx).is_failure());
+        assert!(MaxLengthString::<2>::validate_with(&json!(123), &ctx).is_failure());
+        assert!(String::validate(&json!(42)).is_failure());
+    }
 }
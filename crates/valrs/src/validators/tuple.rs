@@ -0,0 +1,342 @@
+//! Tuple and fixed-size array validation implementations.
+//!
+//! Tuples are validated as fixed-length JSON arrays where each position has
+//! its own type. Fixed-size arrays `[T; N]` are validated as arrays of exactly
+//! `N` elements of the same type.
+
+use crate::validators::{add_schema_uri, strip_schema_uri};
+use crate::{
+    JsonSchemaTarget, PathSegment, SizeType, StandardJsonSchema, ValidationContext,
+    ValidationIssue, ValidationResult, Valrs,
+};
+use serde_json::{Value, json};
+
+/// Checks that a value is a JSON array of exactly `len` elements.
+///
+/// Used by tuples, fixed-size arrays and derived tuple structs.
+pub fn expect_array_of_len(value: &Value, len: usize) -> Result<&[Value], Vec<ValidationIssue>> {
+    match value.as_array() {
+        Some(items) if items.len() == len => Ok(items),
+        Some(items) => Err(vec![ValidationIssue::exact_size(
+            format!("Expected array of length {}, got {}", len, items.len()),
+            SizeType::Array,
+            len as f64,
+            items.len() as f64,
+        )]),
+        None => Err(vec![ValidationIssue::invalid_type(
+            "Expected array",
+            "array",
+            value,
+        )]),
+    }
+}
+
+/// Builds a schema for a fixed-length array with one schema per position.
+///
+/// - Draft 2020-12 uses `prefixItems` and disallows further items with `items: false`
+/// - Draft 07 uses the array form of `items` with `additionalItems: false`
+/// - OpenAPI 3.0 has no positional items, so it falls back to a single `items`
+///   schema (an `anyOf` of the distinct position schemas) with a fixed length
+///
+/// The position schemas should not contain `$schema`; the returned schema has
+/// `$schema` set for `target`. This is also used by derived tuple structs.
+pub fn tuple_schema(items: Vec<Value>, target: JsonSchemaTarget) -> Value {
+    let len = items.len();
+    let mut schema = match target {
+        JsonSchemaTarget::Draft202012 => json!({
+            "type": "array",
+            "prefixItems": items,
+            "items": false,
+            "minItems": len,
+            "maxItems": len
+        }),
+        JsonSchemaTarget::Draft07 => json!({
+            "type": "array",
+            "items": items,
+            "additionalItems": false,
+            "minItems": len,
+            "maxItems": len
+        }),
+        JsonSchemaTarget::OpenApi30 => {
+            let mut distinct: Vec<Value> = Vec::new();
+            for item in items {
+                if !distinct.contains(&item) {
+                    distinct.push(item);
+                }
+            }
+            let items = match distinct.len() {
+                0 => json!({}),
+                1 => distinct.remove(0),
+                _ => json!({ "anyOf": distinct }),
+            };
+            json!({
+                "type": "array",
+                "items": items,
+                "minItems": len,
+                "maxItems": len
+            })
+        }
+    };
+    add_schema_uri(&mut schema, target);
+    schema
+}
+
+macro_rules! impl_tuple {
+    ($len:expr => $($name:ident : $idx:tt),+) => {
+        impl<$($name: Valrs),+> Valrs for ($($name,)+) {
+            type Input = ($($name::Input,)+);
+            type Output = ($($name::Output,)+);
+
+            fn validate(value: &Value) -> ValidationResult<Self::Output> {
+                Self::validate_with(value, &ValidationContext::default())
+            }
+
+            #[allow(non_snake_case)]
+            fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
+                let items = match expect_array_of_len(value, $len) {
+                    Ok(items) => items,
+                    Err(issues) => return ValidationResult::failures(issues),
+                };
+
+                let mut issues = Vec::new();
+                let output = ($(
+                    if ctx.is_done(issues.len()) {
+                        None
+                    } else {
+                        match $name::validate_with(&items[$idx], ctx)
+                            .with_path_prefix(PathSegment::Index($idx))
+                        {
+                            ValidationResult::Success(v) => Some(v),
+                            ValidationResult::Failure(errs) => {
+                                issues.extend(errs);
+                                None
+                            }
+                        }
+                    },
+                )+);
+
+                match output {
+                    ($(Some($name),)+) => ValidationResult::success(($($name,)+)),
+                    _ => {
+                        ctx.truncate(&mut issues);
+                        ValidationResult::failures(issues)
+                    }
+                }
+            }
+        }
+
+        impl<$($name: StandardJsonSchema),+> StandardJsonSchema for ($($name,)+) {
+            fn json_schema_input(target: JsonSchemaTarget) -> Value {
+                tuple_schema(
+                    vec![$(strip_schema_uri($name::json_schema_input(target))),+],
+                    target,
+                )
+            }
+
+            fn json_schema_output(target: JsonSchemaTarget) -> Value {
+                tuple_schema(
+                    vec![$(strip_schema_uri($name::json_schema_output(target))),+],
+                    target,
+                )
+            }
+
+            fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+                tuple_schema(
+                    vec![$(strip_schema_uri($name::json_schema_coerced_input(target))),+],
+                    target,
+                )
+            }
+        }
+    };
+}
+
+impl_tuple!(1 => A: 0);
+impl_tuple!(2 => A: 0, B: 1);
+impl_tuple!(3 => A: 0, B: 1, C: 2);
+impl_tuple!(4 => A: 0, B: 1, C: 2, D: 3);
+impl_tuple!(5 => A: 0, B: 1, C: 2, D: 3, E: 4);
+impl_tuple!(6 => A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
+impl_tuple!(7 => A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6);
+impl_tuple!(8 => A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7);
+impl_tuple!(9 => A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8);
+impl_tuple!(10 => A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9);
+impl_tuple!(11 => A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9, K: 10);
+impl_tuple!(12 => A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7, I: 8, J: 9, K: 10, L: 11);
+
+impl<T: Valrs, const N: usize> Valrs for [T; N] {
+    type Input = [T::Input; N];
+    type Output = [T::Output; N];
+
+    fn validate(value: &Value) -> ValidationResult<Self::Output> {
+        Self::validate_with(value, &ValidationContext::default())
+    }
+
+    fn validate_with(value: &Value, ctx: &ValidationContext) -> ValidationResult<Self::Output> {
+        let items = match expect_array_of_len(value, N) {
+            Ok(items) => items,
+            Err(issues) => return ValidationResult::failures(issues),
+        };
+
+        let mut output = Vec::with_capacity(N);
+        let mut issues = Vec::new();
+
+        for (index, item) in items.iter().enumerate() {
+            match T::validate_with(item, ctx).with_path_prefix(PathSegment::Index(index)) {
+                ValidationResult::Success(v) => output.push(v),
+                ValidationResult::Failure(errs) => {
+                    issues.extend(errs);
+                    if ctx.is_done(issues.len()) {
+                        break;
+                    }
+                }
+            }
+        }
+
+        if !issues.is_empty() {
+            ctx.truncate(&mut issues);
+            return ValidationResult::failures(issues);
+        }
+
+        match output.try_into() {
+            Ok(array) => ValidationResult::success(array),
+            Err(_) => unreachable!("array length was checked above"),
+        }
+    }
+}
+
+/// Builds a schema for an array of exactly `len` elements matching `items`.
+fn fixed_array_schema(items: Value, len: usize, target: JsonSchemaTarget) -> Value {
+    let mut schema = json!({
+        "type": "array",
+        "items": items,
+        "minItems": len,
+        "maxItems": len
+    });
+    add_schema_uri(&mut schema, target);
+    schema
+}
+
+impl<T: StandardJsonSchema, const N: usize> StandardJsonSchema for [T; N] {
+    fn json_schema_input(target: JsonSchemaTarget) -> Value {
+        fixed_array_schema(strip_schema_uri(T::json_schema_input(target)), N, target)
+    }
+
+    fn json_schema_output(target: JsonSchemaTarget) -> Value {
+        fixed_array_schema(strip_schema_uri(T::json_schema_output(target)), N, target)
+    }
+
+    fn json_schema_coerced_input(target: JsonSchemaTarget) -> Value {
+        fixed_array_schema(
+            strip_schema_uri(T::json_schema_coerced_input(target)),
+            N,
+            target,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_pair_validation() {
+        let result = <(String, i32)>::validate(&json!(["width", 42]));
+        assert_eq!(result.ok(), Some(("width".to_string(), 42)));
+
+        assert!(<(String, i32)>::validate(&json!([42, "width"])).is_failure());
+        assert!(<(String, i32)>::validate(&json!({ "0": "a", "1": 1 })).is_failure());
+    }
+
+    #[test]
+    fn test_tuple_length_mismatch() {
+        let result = <(f64, f64)>::validate(&json!([1.0, 2.0, 3.0]));
+        assert_eq!(result.issues().len(), 1);
+        assert_eq!(
+            result.issues()[0].message,
+            "Expected array of length 2, got 3"
+        );
+        assert!(result.issues()[0].path.is_none());
+    }
+
+    #[test]
+    fn test_tuple_issue_paths() {
+        let result = <(u8, bool, String)>::validate(&json!([256, true, 3]));
+        let issues = result.issues();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].path, Some(vec![PathSegment::Index(0)]));
+        assert_eq!(issues[1].path, Some(vec![PathSegment::Index(2)]));
+    }
+
+    #[test]
+    fn test_twelve_tuple() {
+        type T12 = (u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8);
+        let result = T12::validate(&json!([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]));
+        assert_eq!(result.ok().map(|t| t.11), Some(11));
+    }
+
+    #[test]
+    fn test_fixed_array_validation() {
+        let result = <[f64; 3]>::validate(&json!([1.0, 2.5, -3]));
+        assert_eq!(result.ok(), Some([1.0, 2.5, -3.0]));
+
+        assert!(<[f64; 3]>::validate(&json!([1.0, 2.5])).is_failure());
+
+        let result = <[i32; 2]>::validate(&json!([1, "2"]));
+        assert_eq!(result.issues()[0].path, Some(vec![PathSegment::Index(1)]));
+    }
+
+    #[test]
+    fn test_tuple_json_schema_draft_2020_12() {
+        let schema =
+            <(String, i32) as StandardJsonSchema>::json_schema_input(JsonSchemaTarget::Draft202012);
+        assert_eq!(schema["type"], "array");
+        assert_eq!(
+            schema["prefixItems"],
+            json!([{ "type": "string" }, { "type": "integer" }])
+        );
+        assert_eq!(schema["items"], false);
+        assert_eq!(schema["minItems"], 2);
+        assert_eq!(schema["maxItems"], 2);
+        assert_eq!(
+            schema["$schema"],
+            "https://json-schema.org/draft/2020-12/schema"
+        );
+    }
+
+    #[test]
+    fn test_tuple_json_schema_draft_07() {
+        let schema =
+            <(String, i32) as StandardJsonSchema>::json_schema_input(JsonSchemaTarget::Draft07);
+        assert_eq!(
+            schema["items"],
+            json!([{ "type": "string" }, { "type": "integer" }])
+        );
+        assert_eq!(schema["additionalItems"], false);
+        assert!(schema.get("prefixItems").is_none());
+    }
+
+    #[test]
+    fn test_tuple_json_schema_openapi() {
+        let schema =
+            <(String, i32) as StandardJsonSchema>::json_schema_input(JsonSchemaTarget::OpenApi30);
+        assert_eq!(
+            schema["items"]["anyOf"],
+            json!([{ "type": "string" }, { "type": "integer" }])
+        );
+        assert_eq!(schema["minItems"], 2);
+        assert_eq!(schema["maxItems"], 2);
+
+        let schema =
+            <(f64, f64) as StandardJsonSchema>::json_schema_input(JsonSchemaTarget::OpenApi30);
+        assert_eq!(schema["items"], json!({ "type": "number" }));
+    }
+
+    #[test]
+    fn test_fixed_array_json_schema() {
+        let schema = <[u8; 4] as StandardJsonSchema>::json_schema_input(JsonSchemaTarget::Draft07);
+        assert_eq!(schema["items"], json!({ "type": "integer" }));
+        assert_eq!(schema["minItems"], 4);
+        assert_eq!(schema["maxItems"], 4);
+    }
+}
@@ -473,6 +473,13 @@ describe('ValString validation methods', () => {
         expect(() => schema.parse('not-a-uuid')).toThrow(ValError);
         expect(() => schema.parse('123e4567-e89b-12d3-a456-42661417400')).toThrow(ValError);
       });
+
+      it('accepts v7 and nil UUIDs', () => {
+        const schema = v.string().uuid();
+        expect(schema.parse('01890a5d-ac96-774b-bcce-b302099a8057')).toBe('01890a5d-ac96-774b-bcce-b302099a8057');
+        expect(schema.parse('00000000-0000-0000-0000-000000000000')).toBe('00000000-0000-0000-0000-000000000000');
+        expect(() => schema.parse('123e4567-e89b-92d3-a456-426614174000')).toThrow(ValError);
+      });
     });
 
     describe('.cuid()', () => {